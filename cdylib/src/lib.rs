@@ -0,0 +1,26 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `libsolar_clock`, the shared and static libraries exporting the C and
+//! WebAssembly bindings of `solar-clock-rs`. Each is enabled by the feature of
+//! the same name; see the `ffi` and `wasm` modules of the main crate.
+
+#[cfg(feature = "ffi")]
+pub use solar_clock_rs::ffi::*;
+#[cfg(feature = "wasm")]
+pub use solar_clock_rs::wasm::*;
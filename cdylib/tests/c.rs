@@ -0,0 +1,125 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! The C interface as seen from C: `include/solar_clock.h` is what cbindgen
+//! generates, and a C program built against it links `libsolar_clock` and
+//! agrees with the Rust API.
+
+use std::path::Path;
+
+/// Root of the solar-clock-rs package, where `src/ffi.rs` and the header live.
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+#[test]
+fn header_matches_cbindgen() {
+    let config = cbindgen::Config::from_file(root().join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(root().join("src/ffi.rs"))
+        .generate()
+        .expect("cbindgen parses src/ffi.rs")
+        .write(&mut generated);
+    let checked_in = std::fs::read_to_string(root().join("include/solar_clock.h")).unwrap();
+    // A Windows checkout may have turned the line endings into CRLF
+    let checked_in = checked_in.replace("\r\n", "\n");
+    assert!(
+        String::from_utf8(generated).unwrap() == checked_in,
+        "include/solar_clock.h is stale; regenerate it with \
+         `cbindgen --config cbindgen.toml --output include/solar_clock.h src/ffi.rs`"
+    );
+}
+
+// Windows has no `cc`, and links against an import library instead
+#[cfg(unix)]
+mod c_program {
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use chrono::{DateTime, FixedOffset, NaiveTime};
+    use solar_clock_rs::SolarClockBuilder;
+
+    use super::root;
+
+    /// Directory of the libraries built for these tests: `target/<profile>`,
+    /// above the `deps` directory of the test executable.
+    fn library_dir() -> PathBuf {
+        let exe = std::env::current_exe().unwrap();
+        exe.parent().unwrap().parent().unwrap().to_owned()
+    }
+
+    #[test]
+    fn c_program_agrees_with_the_rust_api() {
+        let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+        let program = out_dir.join("solar-clock-smoke");
+        let libraries = library_dir();
+        let status = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_owned()))
+            .args(["-std=c99", "-Wall", "-Wextra", "-Werror", "-pedantic"])
+            .arg("-I")
+            .arg(root().join("include"))
+            .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/smoke.c"))
+            .arg("-o")
+            .arg(&program)
+            .arg("-L")
+            .arg(&libraries)
+            .arg(format!("-Wl,-rpath,{}", libraries.display()))
+            .arg("-lsolar_clock")
+            .status()
+            .expect("a C compiler is installed as cc, or named by CC");
+        assert!(status.success(), "the smoke test does not compile");
+
+        let now = 1_781_000_000i64;
+        let output = Command::new(&program)
+            .arg(now.to_string())
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let values: Vec<f64> = stdout
+            .split_whitespace()
+            .map(|value| value.parse().unwrap())
+            .collect();
+        let [solar, delta] = values[..] else {
+            panic!("expected the solar instant and the delta: {stdout}");
+        };
+
+        let hour = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+        let clock = SolarClockBuilder::new()
+            .coordinates(40.4168, -3.7038)
+            .targets(hour(8), hour(14), hour(20))
+            .solar_offset(FixedOffset::east_opt(3600).unwrap())
+            .build()
+            .unwrap();
+        let expected = clock
+            .solar_time(DateTime::from_timestamp(now, 0).unwrap())
+            .unwrap();
+        assert!(
+            (delta - expected.delta).abs() < 1e-6,
+            "{delta} vs {expected:?}"
+        );
+        let expected_solar = expected.solar_time.timestamp_micros() as f64 / 1e6;
+        assert!((solar - expected_solar).abs() < 1e-6, "{solar}");
+        assert!((solar - now as f64 - delta).abs() < 1e-3);
+    }
+}
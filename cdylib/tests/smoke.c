@@ -0,0 +1,65 @@
+/*
+ * solar-clock-rs - High-precision solar clock calculator
+ * Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <https://www.gnu.org/licenses/>.
+ */
+
+/*
+ * Smoke test of include/solar_clock.h against libsolar_clock: prints the
+ * solar instant and the delta of a Madrid clock at the Unix second given as
+ * the only argument, after checking the error paths.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+
+#include "solar_clock.h"
+
+#define CHECK(condition)                                                  \
+  do {                                                                    \
+    if (!(condition)) {                                                   \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,   \
+              #condition);                                                \
+      return 1;                                                           \
+    }                                                                     \
+  } while (0)
+
+int main(int argc, char **argv) {
+  CHECK(argc == 2);
+  double now = strtod(argv[1], NULL);
+
+  /* Invalid arguments give no handle, and freeing null is harmless */
+  CHECK(sc_new(91.0, 0.0, 8 * 3600, 14 * 3600, 20 * 3600, 3600) == NULL);
+  CHECK(sc_new(40.4168, -3.7038, 86400, 14 * 3600, 20 * 3600, 3600) == NULL);
+  sc_free(NULL);
+
+  SolarClockHandle *clock =
+      sc_new(40.4168, -3.7038, 8 * 3600, 14 * 3600, 20 * 3600, 3600);
+  CHECK(clock != NULL);
+
+  double solar = -1.0, delta = -1.0;
+  CHECK(sc_solar_time(NULL, now, &solar, &delta) == SC_STATUS_NULL_POINTER);
+  CHECK(sc_solar_time(clock, now, NULL, &delta) == SC_STATUS_NULL_POINTER);
+  CHECK(sc_solar_time(clock, 1.0 / 0.0, &solar, &delta) ==
+        SC_STATUS_INVALID_ARGUMENT);
+  /* Outputs are untouched on failure */
+  CHECK(solar == -1.0 && delta == -1.0);
+
+  CHECK(sc_solar_time(clock, now, &solar, &delta) == SC_STATUS_OK);
+  printf("%.6f %.6f\n", solar, delta);
+
+  sc_free(clock);
+  return 0;
+}
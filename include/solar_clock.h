@@ -0,0 +1,87 @@
+#ifndef SOLAR_CLOCK_H
+#define SOLAR_CLOCK_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stdint.h>
+
+/**
+ * Status returned as `int` by [`sc_solar_time`].
+ */
+typedef enum ScStatus {
+  SC_STATUS_OK = 0,
+  /**
+   * A pointer argument was null.
+   */
+  SC_STATUS_NULL_POINTER = 1,
+  /**
+   * An argument was out of range or not finite.
+   */
+  SC_STATUS_INVALID_ARGUMENT = 2,
+  /**
+   * The events needed do not occur, as in polar day or night.
+   */
+  SC_STATUS_NO_SOLAR_DATA = 3,
+  /**
+   * The solar position or the interpolation failed.
+   */
+  SC_STATUS_INTERNAL = 4,
+  /**
+   * The library panicked; the handle should be freed.
+   */
+  SC_STATUS_PANIC = 5,
+} ScStatus;
+
+/**
+ * Opaque solar clock owned by C code.
+ */
+typedef struct SolarClockHandle SolarClockHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a clock for the observer at `lat`, `lon` degrees, with the
+ * sunrise, transit and sunset targets in seconds after midnight and the
+ * solar wall time `offset_s` seconds east of UTC.
+ *
+ * Returns null when an argument is invalid. Free the handle with
+ * [`sc_free`].
+ */
+struct SolarClockHandle *sc_new(double lat,
+                                double lon,
+                                uint32_t sunrise_s,
+                                uint32_t transit_s,
+                                uint32_t sunset_s,
+                                int32_t offset_s);
+
+/**
+ * Evaluates the clock at `epoch_seconds` and writes the solar instant, in
+ * Unix seconds, to `out_epoch_seconds` and the delta in seconds to
+ * `out_delta`. The outputs are left untouched on failure.
+ *
+ * # Safety
+ *
+ * `handle` must come from [`sc_new`] and not have been freed, and the
+ * outputs must be valid for writes. Any of them may be null.
+ */
+int sc_solar_time(const struct SolarClockHandle *handle,
+                  double epoch_seconds,
+                  double *out_epoch_seconds,
+                  double *out_delta);
+
+/**
+ * Frees a clock created by [`sc_new`]. Null is ignored.
+ *
+ * # Safety
+ *
+ * `handle` must come from [`sc_new`] and not have been freed.
+ */
+void sc_free(struct SolarClockHandle *handle);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SOLAR_CLOCK_H */
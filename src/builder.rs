@@ -0,0 +1,365 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Builder for [`SolarClock`] with validation of every parameter.
+
+use chrono::{FixedOffset, NaiveTime};
+
+use crate::error::BuildError;
+use crate::interp::InterpolationMethod;
+use crate::solar_clock::{
+    AnchorEvent, AnchorSpec, Coordinates, DEFAULT_COORDINATES, DEFAULT_MAX_WINDOW_DAYS,
+    DEFAULT_MERGE_EPSILON, DEFAULT_TARGETS, DEFAULT_WINDOW_DAYS, ModelOptions,
+    SOLAR_TIMEZONE_OFFSET, SolarClock, SolarTargets, check_order,
+};
+use crate::spa::{Refinement, STANDARD_ZENITH};
+
+/// Configures a [`SolarClock`]. Unset parameters keep the crate defaults.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolarClockBuilder {
+    coords: Coordinates,
+    anchors: Vec<AnchorSpec>,
+    offset: FixedOffset,
+    window_days: u32,
+    max_window_days: u32,
+    merge_epsilon: f64,
+    polar_fallback: bool,
+    strict_monotonic: bool,
+    interpolation: InterpolationMethod,
+    refinement: Refinement,
+    delta_t: Option<f64>,
+    zenith: f64,
+    elevation: f64,
+    temperature: Option<f64>,
+    pressure: Option<f64>,
+}
+
+impl Default for SolarClockBuilder {
+    fn default() -> Self {
+        Self {
+            coords: DEFAULT_COORDINATES,
+            anchors: DEFAULT_TARGETS.anchors(),
+            offset: SOLAR_TIMEZONE_OFFSET,
+            window_days: DEFAULT_WINDOW_DAYS,
+            max_window_days: DEFAULT_MAX_WINDOW_DAYS,
+            merge_epsilon: DEFAULT_MERGE_EPSILON,
+            polar_fallback: false,
+            strict_monotonic: true,
+            interpolation: InterpolationMethod::Pchip,
+            refinement: Refinement::Iterative,
+            delta_t: None,
+            zenith: STANDARD_ZENITH,
+            elevation: 0.0,
+            temperature: None,
+            pressure: None,
+        }
+    }
+}
+
+impl SolarClockBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Observer location in decimal degrees (positive north and east).
+    pub fn coordinates(mut self, latitude: f64, longitude: f64) -> Self {
+        self.coords = Coordinates {
+            latitude,
+            longitude,
+        };
+        self
+    }
+
+    /// Solar wall times for sunrise, transit and sunset. The solar midnight
+    /// target, if any, is kept.
+    pub fn targets(mut self, sunrise: NaiveTime, transit: NaiveTime, sunset: NaiveTime) -> Self {
+        let targets = SolarTargets {
+            sunrise,
+            transit,
+            sunset,
+            midnight: self.midnight_target(),
+        };
+        self.anchors = targets.anchors();
+        self
+    }
+
+    /// Solar wall time for solar midnight, e.g.
+    /// [`DEFAULT_MIDNIGHT`](crate::solar_clock::DEFAULT_MIDNIGHT), or
+    /// `None` to not anchor it (default).
+    pub fn midnight(mut self, target: Option<NaiveTime>) -> Self {
+        self.anchors
+            .retain(|anchor| anchor.event != AnchorEvent::Midnight);
+        if let Some(target) = target {
+            self.anchors.insert(
+                0,
+                AnchorSpec::new("midnight", AnchorEvent::Midnight, target),
+            );
+        }
+        self
+    }
+
+    fn midnight_target(&self) -> Option<NaiveTime> {
+        self.anchors
+            .iter()
+            .find(|anchor| anchor.event == AnchorEvent::Midnight)
+            .map(|anchor| anchor.target)
+    }
+
+    /// Custom set of anchors, replacing the standard targets.
+    pub fn anchors(mut self, anchors: Vec<AnchorSpec>) -> Self {
+        self.anchors = anchors;
+        self
+    }
+
+    /// Reference offset in which solar wall times are expressed.
+    pub fn solar_offset(mut self, offset: FixedOffset) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Number of days taken on each side of the evaluated date.
+    pub fn window_days(mut self, days: u32) -> Self {
+        self.window_days = days;
+        self
+    }
+
+    /// Largest window the model may widen to when events are missing, e.g.
+    /// during polar night. See [`ModelOptions::max_window_days`].
+    pub fn max_window_days(mut self, days: u32) -> Self {
+        self.max_window_days = days;
+        self
+    }
+
+    /// Distance in seconds below which anchors are merged. See
+    /// [`ModelOptions::merge_epsilon`].
+    pub fn merge_epsilon(mut self, seconds: f64) -> Self {
+        self.merge_epsilon = seconds;
+        self
+    }
+
+    /// Whether to anchor only transits and solar midnights around days
+    /// without sunrise or sunset. See [`ModelOptions::polar_fallback`].
+    pub fn polar_fallback(mut self, enabled: bool) -> Self {
+        self.polar_fallback = enabled;
+        self
+    }
+
+    /// Whether anchors that would make the solar clock run backwards are an
+    /// error (`true`, the default) or are dropped. See
+    /// [`ModelOptions::strict_monotonic`].
+    pub fn strict_monotonic(mut self, strict: bool) -> Self {
+        self.strict_monotonic = strict;
+        self
+    }
+
+    /// Interpolation between anchors (default PCHIP).
+    pub fn interpolation(mut self, method: InterpolationMethod) -> Self {
+        self.interpolation = method;
+        self
+    }
+
+    /// How far sunrise and sunset are refined (default iterative). See
+    /// [`ModelOptions::refinement`].
+    pub fn refinement(mut self, refinement: Refinement) -> Self {
+        self.refinement = refinement;
+        self
+    }
+
+    /// ΔT in seconds, terrestrial time minus UT, instead of the built-in
+    /// estimate. See [`ModelOptions::delta_t`].
+    pub fn delta_t(mut self, seconds: f64) -> Self {
+        self.delta_t = Some(seconds);
+        self
+    }
+
+    /// Zenith angle in degrees at which sunrise and sunset are taken
+    /// (default [`STANDARD_ZENITH`]), strictly between 0 and 180.
+    pub fn zenith(mut self, degrees: f64) -> Self {
+        self.zenith = degrees;
+        self
+    }
+
+    /// Observer elevation in metres above the horizon (default 0), which
+    /// lowers the horizon. See [`ModelOptions::elevation`].
+    pub fn elevation(mut self, metres: f64) -> Self {
+        self.elevation = metres;
+        self
+    }
+
+    /// Air temperature in °C for the refraction at the horizon. See
+    /// [`ModelOptions::temperature`].
+    pub fn temperature(mut self, celsius: f64) -> Self {
+        self.temperature = Some(celsius);
+        self
+    }
+
+    /// Air pressure in hPa for the refraction at the horizon. See
+    /// [`ModelOptions::pressure`].
+    pub fn pressure(mut self, hpa: f64) -> Self {
+        self.pressure = Some(hpa);
+        self
+    }
+
+    pub fn build(self) -> Result<SolarClock, BuildError> {
+        let coords = Coordinates::new(self.coords.latitude, self.coords.longitude)?;
+
+        // Only the order of the standard events is known in advance
+        let target_of = |event: AnchorEvent| {
+            self.anchors
+                .iter()
+                .find(|anchor| anchor.event == event)
+                .map(|anchor| anchor.target)
+        };
+        let standard = [
+            target_of(AnchorEvent::Midnight),
+            target_of(AnchorEvent::Sunrise),
+            target_of(AnchorEvent::Transit),
+            target_of(AnchorEvent::Sunset),
+        ];
+        let present: Vec<NaiveTime> = standard.into_iter().flatten().collect();
+        check_order(&present)?;
+
+        if self.window_days == 0 {
+            return Err(BuildError::InvalidWindow(self.window_days));
+        }
+        if !(self.zenith > 0.0 && self.zenith < 180.0) {
+            return Err(BuildError::InvalidZenith(self.zenith));
+        }
+        if !(self.elevation.is_finite() && self.elevation >= 0.0) {
+            return Err(BuildError::InvalidElevation(self.elevation));
+        }
+        if let Some(celsius) = self.temperature
+            && !(celsius.is_finite() && celsius > -273.15)
+        {
+            return Err(BuildError::InvalidTemperature(celsius));
+        }
+        if let Some(hpa) = self.pressure
+            && !(hpa.is_finite() && hpa > 0.0)
+        {
+            return Err(BuildError::InvalidPressure(hpa));
+        }
+        if let Some(seconds) = self.delta_t
+            && !seconds.is_finite()
+        {
+            return Err(BuildError::InvalidDeltaT(seconds));
+        }
+
+        Ok(SolarClock::with_options(
+            coords,
+            ModelOptions {
+                anchors: self.anchors,
+                offset: self.offset,
+                window_days: self.window_days,
+                max_window_days: self.max_window_days,
+                merge_epsilon: self.merge_epsilon,
+                polar_fallback: self.polar_fallback,
+                strict_monotonic: self.strict_monotonic,
+                interpolation: self.interpolation,
+                refinement: self.refinement,
+                delta_t: self.delta_t,
+                zenith: self.zenith,
+                elevation: self.elevation,
+                temperature: self.temperature,
+                pressure: self.pressure,
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_validates_every_parameter() {
+        let clock = SolarClockBuilder::new().build().unwrap();
+        assert_eq!(clock.coordinates(), &DEFAULT_COORDINATES);
+        assert_eq!(clock.options(), &ModelOptions::default());
+
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let invalid = [
+            (
+                SolarClockBuilder::new().coordinates(91.0, 0.0),
+                BuildError::Coordinates(crate::CoordError::InvalidLatitude(91.0)),
+            ),
+            (
+                SolarClockBuilder::new().coordinates(0.0, -181.0),
+                BuildError::Coordinates(crate::CoordError::InvalidLongitude(-181.0)),
+            ),
+            (
+                SolarClockBuilder::new().targets(time(7), time(21), time(14)),
+                BuildError::Targets(crate::TargetsError::Unordered {
+                    earlier: time(21),
+                    later: time(14),
+                }),
+            ),
+            (
+                SolarClockBuilder::new().window_days(0),
+                BuildError::InvalidWindow(0),
+            ),
+            (
+                SolarClockBuilder::new().zenith(180.0),
+                BuildError::InvalidZenith(180.0),
+            ),
+            (
+                SolarClockBuilder::new().elevation(-1.0),
+                BuildError::InvalidElevation(-1.0),
+            ),
+            (
+                SolarClockBuilder::new().temperature(-300.0),
+                BuildError::InvalidTemperature(-300.0),
+            ),
+            (
+                SolarClockBuilder::new().pressure(0.0),
+                BuildError::InvalidPressure(0.0),
+            ),
+            (
+                SolarClockBuilder::new().delta_t(f64::INFINITY),
+                BuildError::InvalidDeltaT(f64::INFINITY),
+            ),
+        ];
+        for (builder, error) in invalid {
+            assert_eq!(builder.build().map(|_| ()), Err(error));
+        }
+    }
+
+    #[test]
+    fn targets_keep_the_midnight_anchor_and_stay_ordered() {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let builder =
+            SolarClockBuilder::new()
+                .midnight(Some(time(1)))
+                .targets(time(7), time(13), time(19));
+        let clock = builder.clone().build().unwrap();
+        let anchors = &clock.options().anchors;
+        assert_eq!(anchors.len(), 4);
+        assert_eq!(anchors[0].event, AnchorEvent::Midnight);
+        assert_eq!(anchors[0].target, time(1));
+        assert_eq!(anchors[2].target, time(13));
+
+        // Removing the midnight leaves the other targets alone
+        let unanchored = builder.midnight(None).build().unwrap();
+        assert_eq!(&unanchored.options().anchors[..], &anchors[1..]);
+
+        // A midnight after sunrise is out of order
+        assert!(matches!(
+            SolarClockBuilder::new().midnight(Some(time(9))).build(),
+            Err(BuildError::Targets(_))
+        ));
+    }
+}
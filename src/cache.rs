@@ -0,0 +1,258 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Model cache of `--cached`, for shell prompts that evaluate the clock on
+//! every redraw.
+//!
+//! The model of the current solar date is saved under
+//! `$XDG_CACHE_HOME/solar-clock/`, in a file named after the date, the
+//! coordinates and a hash of the other settings. Later runs on the same date
+//! load it instead of computing the solar positions; on the next date the
+//! file no longer matches, the model is rebuilt and the old file removed.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use solar_clock_rs::{ModelFileError, SolarClock};
+
+/// Directory of the cached models, if a home can be determined.
+pub fn dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))?;
+    Some(base.join("solar-clock"))
+}
+
+/// Makes `clock` use the cached model for the solar date of `now`, loading it
+/// when the cache holds one for that date and settings, and otherwise
+/// building it and rewriting the cache. Returns whether the model was loaded
+/// from the cache.
+///
+/// The cache only saves work: when it cannot be read or written, the clock
+/// builds its models as usual.
+pub fn prime(clock: &SolarClock, now: DateTime<Utc>) -> bool {
+    let Some(dir) = dir() else {
+        tracing::debug!("no cache directory: neither XDG_CACHE_HOME nor HOME is set");
+        return false;
+    };
+    prime_in(&dir, clock, now)
+}
+
+/// [`prime`] with the cache in `dir`.
+fn prime_in(dir: &Path, clock: &SolarClock, now: DateTime<Utc>) -> bool {
+    let date = now.with_timezone(&clock.offset()).date_naive();
+    let key = key(clock);
+    let path = dir.join(format!("{date}_{key}.bin"));
+
+    match clock.load_model(&path, false) {
+        Ok(loaded) if loaded == date => {
+            tracing::debug!("using the cached model {}", path.display());
+            return true;
+        }
+        Ok(loaded) => tracing::debug!("cached model {} is for {loaded}", path.display()),
+        Err(ModelFileError::Io(e)) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => tracing::debug!("cached model {} not used: {e}", path.display()),
+    }
+    match store(clock, date, &path) {
+        Ok(()) => remove_stale(dir, &key, &path),
+        // The evaluation reports the same failure
+        Err(ModelFileError::SolarClock(e)) => tracing::debug!("model not cached: {e}"),
+        Err(e) => tracing::warn!("model not cached at {}: {e}", path.display()),
+    }
+    false
+}
+
+/// Part of the file names shared by every date of this configuration: the
+/// coordinates, for people looking at the directory, and a hash of the
+/// model options.
+fn key(clock: &SolarClock) -> String {
+    let coords = clock.coordinates();
+    format!(
+        "{:+.4}_{:+.4}_{:016x}",
+        coords.latitude(),
+        coords.longitude(),
+        fnv1a(format!("{:?}", clock.options()).as_bytes())
+    )
+}
+
+/// 64-bit FNV-1a, stable across builds unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Saves the model of `date` to `path`.
+///
+/// Concurrent runs each write a file of their own and rename it over
+/// `path`, so readers see either the previous file or the new one whole.
+fn store(clock: &SolarClock, date: NaiveDate, path: &Path) -> Result<(), ModelFileError> {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        unreachable!("cache paths are built from a directory and a file name");
+    };
+    fs::create_dir_all(dir)?;
+    let tmp = dir.join(format!(
+        ".{}.{}.tmp",
+        name.to_string_lossy(),
+        std::process::id()
+    ));
+    let result = clock
+        .save_model(date, &tmp)
+        .and_then(|()| fs::rename(&tmp, path).map_err(ModelFileError::from));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Removes the files of other dates with the same configuration as `current`.
+fn remove_stale(dir: &Path, key: &str, current: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let suffix = format!("_{key}.bin");
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let stale = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.ends_with(&suffix) && !name.starts_with('.'));
+        if stale && path != current {
+            tracing::debug!("removing the stale cached model {}", path.display());
+            let _ = fs::remove_file(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{FixedOffset, TimeZone};
+    use solar_clock_rs::solar_clock::{DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET};
+    use solar_clock_rs::{Coordinates, SolarClock};
+
+    use super::*;
+
+    fn clock(offset: FixedOffset) -> SolarClock {
+        SolarClock::new(
+            Coordinates::new(40.4168, -3.7038).unwrap(),
+            DEFAULT_TARGETS,
+            offset,
+        )
+    }
+
+    /// An empty directory of its own in the temporary directory.
+    fn empty_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("solar-clock-cache-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn files(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn fnv1a_matches_the_reference_values() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn keys_tell_configurations_apart() {
+        let key = key(&clock(SOLAR_TIMEZONE_OFFSET));
+        assert!(key.starts_with("+40.4168_-3.7038_"), "{key}");
+        assert_eq!(key, super::key(&clock(SOLAR_TIMEZONE_OFFSET)));
+        let other = super::key(&clock(FixedOffset::east_opt(7200).unwrap()));
+        assert_ne!(key, other);
+        // Only the hash differs
+        assert_eq!(key[..key.len() - 16], other[..other.len() - 16]);
+    }
+
+    #[test]
+    fn models_are_cached_for_one_date_at_a_time() {
+        let dir = empty_dir("dates");
+        let key = key(&clock(SOLAR_TIMEZONE_OFFSET));
+        let monday = Utc.with_ymd_and_hms(2026, 2, 2, 12, 0, 0).unwrap();
+        let tuesday = monday + chrono::Duration::days(1);
+
+        // Built and saved on the first run, loaded on the next ones
+        assert!(!prime_in(&dir, &clock(SOLAR_TIMEZONE_OFFSET), monday));
+        assert_eq!(files(&dir), [format!("2026-02-02_{key}.bin")]);
+        let cached = clock(SOLAR_TIMEZONE_OFFSET);
+        assert!(prime_in(&dir, &cached, monday));
+        assert_eq!(
+            cached.solar_time(monday),
+            clock(SOLAR_TIMEZONE_OFFSET).solar_time(monday)
+        );
+
+        // Another configuration keeps its own file
+        let other = clock(FixedOffset::east_opt(7200).unwrap());
+        assert!(!prime_in(&dir, &other, monday));
+        assert_eq!(files(&dir).len(), 2);
+
+        // The next date replaces the file of the previous one
+        assert!(!prime_in(&dir, &clock(SOLAR_TIMEZONE_OFFSET), tuesday));
+        let names = files(&dir);
+        assert!(
+            names.contains(&format!("2026-02-03_{key}.bin")),
+            "{names:?}"
+        );
+        assert!(
+            !names.contains(&format!("2026-02-02_{key}.bin")),
+            "{names:?}"
+        );
+        assert_eq!(names.len(), 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn damaged_or_unwritable_caches_only_cost_the_build() {
+        let dir = empty_dir("damaged");
+        let now = Utc.with_ymd_and_hms(2026, 2, 3, 12, 0, 0).unwrap();
+        let key = key(&clock(SOLAR_TIMEZONE_OFFSET));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("2026-02-03_{key}.bin"));
+
+        // A damaged file is rewritten
+        fs::write(&path, b"not a model").unwrap();
+        assert!(!prime_in(&dir, &clock(SOLAR_TIMEZONE_OFFSET), now));
+        assert!(prime_in(&dir, &clock(SOLAR_TIMEZONE_OFFSET), now));
+
+        // And a cache that cannot be created is skipped
+        let blocked = dir.join("blocked");
+        fs::write(&blocked, b"").unwrap();
+        let uncached = clock(SOLAR_TIMEZONE_OFFSET);
+        assert!(!prime_in(&blocked, &uncached, now));
+        assert_eq!(
+            uncached.solar_time(now),
+            clock(SOLAR_TIMEZONE_OFFSET).solar_time(now)
+        );
+        assert!(!files(&dir).iter().any(|name| name.ends_with(".tmp")));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,315 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Recommended colour temperature over the day, following the solar clock
+//! rather than the civil one, for circadian lighting.
+//!
+//! A [`CctCurve`] maps solar wall times to a correlated colour temperature:
+//! warm at night, cool during the day, with linear ramps in between.
+//! [`color_temperature`] evaluates it at an instant and [`sample_cct`] over
+//! a whole solar day.
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, Utc};
+
+use crate::error::{CctError, SolarClockError};
+use crate::solar_clock::SolarClock;
+
+/// Colour temperature curve on the solar clock: `night` kelvin until
+/// `morning.0`, rising linearly to `day` at `morning.1`, `day` until
+/// `evening.0` and falling linearly back to `night` at `evening.1`.
+///
+/// The curve is continuous, and monotone on each ramp.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde_utils::RawCctCurve"))]
+pub struct CctCurve {
+    night: f64,
+    day: f64,
+    morning: (NaiveTime, NaiveTime),
+    evening: (NaiveTime, NaiveTime),
+}
+
+impl Default for CctCurve {
+    /// 2500 K at night and 5500 K by day, ramping over solar 07:00–10:00 and
+    /// 18:00–21:00.
+    fn default() -> Self {
+        let hour = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap_or_default();
+        Self {
+            night: 2500.0,
+            day: 5500.0,
+            morning: (hour(7), hour(10)),
+            evening: (hour(18), hour(21)),
+        }
+    }
+}
+
+impl CctCurve {
+    /// Validates the curve: both temperatures positive and finite, and the
+    /// ramp bounds strictly increasing through the day.
+    pub fn new(
+        night: f64,
+        day: f64,
+        morning: (NaiveTime, NaiveTime),
+        evening: (NaiveTime, NaiveTime),
+    ) -> Result<Self, CctError> {
+        for kelvin in [night, day] {
+            if !kelvin.is_finite() || kelvin <= 0.0 {
+                return Err(CctError::InvalidKelvin(kelvin));
+            }
+        }
+        let bounds = [morning.0, morning.1, evening.0, evening.1];
+        for pair in bounds.windows(2) {
+            if pair[1] <= pair[0] {
+                return Err(CctError::Unordered {
+                    earlier: pair[0],
+                    later: pair[1],
+                });
+            }
+        }
+        Ok(Self {
+            night,
+            day,
+            morning,
+            evening,
+        })
+    }
+
+    pub fn night(&self) -> f64 {
+        self.night
+    }
+
+    pub fn day(&self) -> f64 {
+        self.day
+    }
+
+    pub fn morning(&self) -> (NaiveTime, NaiveTime) {
+        self.morning
+    }
+
+    pub fn evening(&self) -> (NaiveTime, NaiveTime) {
+        self.evening
+    }
+
+    /// Colour temperature in kelvin at the solar wall time `solar`.
+    pub fn kelvin(&self, solar: NaiveTime) -> f64 {
+        let ramp = |(start, end): (NaiveTime, NaiveTime), from: f64, to: f64| {
+            let t = seconds(solar - start) / seconds(end - start);
+            from + (to - from) * t
+        };
+        if solar < self.morning.0 || solar >= self.evening.1 {
+            self.night
+        } else if solar < self.morning.1 {
+            ramp(self.morning, self.night, self.day)
+        } else if solar < self.evening.0 {
+            self.day
+        } else {
+            ramp(self.evening, self.day, self.night)
+        }
+    }
+}
+
+/// Colour temperature of `curve` at the instant `dt`, read on the solar
+/// clock. Fails where the clock cannot be evaluated, as in polar day or
+/// night without [`polar fallback`](crate::ModelOptions::polar_fallback).
+pub fn color_temperature(
+    clock: &SolarClock,
+    dt: DateTime<Utc>,
+    curve: &CctCurve,
+) -> Result<f64, SolarClockError> {
+    let solar = clock.solar_time(dt)?.solar_time;
+    Ok(curve.kelvin(solar.time()))
+}
+
+/// Colour temperature at one instant of [`sample_cct`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CctSample {
+    pub civil: DateTime<Utc>,
+    pub solar: DateTime<FixedOffset>,
+    pub kelvin: f64,
+}
+
+/// Samples `curve` every `step` of civil time over the solar day `date`,
+/// from its solar midnight, included, to the next, excluded.
+///
+/// # Panics
+///
+/// If `step` is not positive.
+pub fn sample_cct(
+    clock: &SolarClock,
+    date: NaiveDate,
+    step: Duration,
+    curve: &CctCurve,
+) -> Result<Vec<CctSample>, SolarClockError> {
+    assert!(step > Duration::zero(), "the step must be positive");
+    // Civil noon is well inside the solar day of the same date
+    let noon = date.and_time(NaiveTime::MIN) + Duration::hours(12) - clock.offset();
+    let (start, end) = clock.solar_day_bounds(noon.and_utc())?;
+
+    let mut samples = Vec::new();
+    let mut civil = start;
+    while civil < end {
+        let solar = clock.solar_time(civil)?.solar_time;
+        samples.push(CctSample {
+            civil,
+            solar,
+            kelvin: curve.kelvin(solar.time()),
+        });
+        civil += step;
+    }
+    Ok(samples)
+}
+
+fn seconds(duration: Duration) -> f64 {
+    duration.num_nanoseconds().map_or(0.0, |ns| ns as f64 / 1e9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnchorEvent, AnchorSpec, SolarClockBuilder};
+
+    fn hm(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn the_curve_is_continuous_and_monotone_on_its_ramps() {
+        let curve = CctCurve::default();
+        assert_eq!(curve.kelvin(hm(12, 0)), curve.day());
+        assert_eq!(curve.kelvin(hm(0, 0)), curve.night());
+        assert_eq!(curve.kelvin(hm(7, 0)), curve.night());
+        assert_eq!(curve.kelvin(hm(8, 30)), 4000.0);
+        assert_eq!(curve.kelvin(hm(10, 0)), curve.day());
+        assert_eq!(curve.kelvin(hm(21, 0)), curve.night());
+
+        // 3000 K over three hours is under 0.3 K a second
+        let second = |s| NaiveTime::from_num_seconds_from_midnight_opt(s, 0).unwrap();
+        let kelvin: Vec<f64> = (0..86_400).map(|s| curve.kelvin(second(s))).collect();
+        for (s, pair) in (0..).zip(kelvin.windows(2)) {
+            let step = pair[1] - pair[0];
+            assert!(step.abs() < 0.3, "jump of {step} K at {}", second(s));
+            if second(s) < hm(12, 0) {
+                assert!(step >= 0.0, "falls at {}", second(s));
+            } else {
+                assert!(step <= 0.0, "rises at {}", second(s));
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_curves_are_rejected() {
+        let (morning, evening) = ((hm(7, 0), hm(10, 0)), (hm(18, 0), hm(21, 0)));
+        for kelvin in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                CctCurve::new(kelvin, 5500.0, morning, evening),
+                Err(CctError::InvalidKelvin(_))
+            ));
+            assert!(matches!(
+                CctCurve::new(2500.0, kelvin, morning, evening),
+                Err(CctError::InvalidKelvin(_))
+            ));
+        }
+        // Touching ramps are fine, overlapping or reversed ones are not
+        assert!(
+            CctCurve::new(
+                2500.0,
+                5500.0,
+                (hm(7, 0), hm(10, 0)),
+                (hm(10, 1), hm(21, 0))
+            )
+            .is_ok()
+        );
+        assert_eq!(
+            CctCurve::new(2500.0, 5500.0, (hm(7, 0), hm(10, 0)), (hm(9, 0), hm(21, 0))),
+            Err(CctError::Unordered {
+                earlier: hm(10, 0),
+                later: hm(9, 0)
+            })
+        );
+        assert_eq!(
+            CctCurve::new(2500.0, 5500.0, (hm(10, 0), hm(7, 0)), evening),
+            Err(CctError::Unordered {
+                earlier: hm(10, 0),
+                later: hm(7, 0)
+            })
+        );
+    }
+
+    #[test]
+    fn samples_cover_one_solar_day() {
+        let clock = SolarClockBuilder::new().build().unwrap();
+        let curve = CctCurve::default();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let step = Duration::minutes(10);
+        let samples = sample_cct(&clock, date, step, &curve).unwrap();
+
+        let noon = date.and_hms_opt(12, 0, 0).unwrap() - clock.offset();
+        let (start, end) = clock.solar_day_bounds(noon.and_utc()).unwrap();
+        assert_eq!(samples[0].civil, start);
+        assert!(samples.last().unwrap().civil < end);
+        assert!(samples.last().unwrap().civil + step >= end);
+        assert!(samples.windows(2).all(|w| w[1].civil - w[0].civil == step));
+        for sample in &samples {
+            assert_eq!(sample.solar.date_naive(), date);
+            assert_eq!(sample.kelvin, curve.kelvin(sample.solar.time()));
+            assert_eq!(
+                color_temperature(&clock, sample.civil, &curve).unwrap(),
+                sample.kelvin
+            );
+        }
+        // Solar noon is in the middle of the day plateau
+        let transit = samples
+            .iter()
+            .min_by_key(|sample| (sample.solar.time() - hm(12, 0)).abs())
+            .unwrap();
+        assert_eq!(transit.kelvin, curve.day());
+    }
+
+    #[test]
+    fn polar_nights_follow_the_fallback() {
+        // No sunrise or sunset anywhere near midwinter at 80° N
+        let date = NaiveDate::from_ymd_opt(2026, 12, 21).unwrap();
+        let curve = CctCurve::default();
+
+        let sun = SolarClockBuilder::new()
+            .coordinates(80.0, 15.0)
+            .anchors(vec![
+                AnchorSpec::new("sunrise", AnchorEvent::Sunrise, hm(7, 0)),
+                AnchorSpec::new("sunset", AnchorEvent::Sunset, hm(19, 0)),
+            ])
+            .build()
+            .unwrap();
+        assert!(sample_cct(&sun, date, Duration::hours(1), &curve).is_err());
+
+        // The fallback runs on the transits, and the curve with it
+        let fallback = SolarClockBuilder::new()
+            .coordinates(80.0, 15.0)
+            .polar_fallback(true)
+            .build()
+            .unwrap();
+        let samples = sample_cct(&fallback, date, Duration::minutes(30), &curve).unwrap();
+        assert!((47..=49).contains(&samples.len()), "{}", samples.len());
+        for sample in &samples {
+            assert_eq!(sample.kelvin, curve.kelvin(sample.solar.time()));
+        }
+        // Solar 10:00 to 18:00, give or take the sample on its edge
+        let plateau = samples.iter().filter(|sample| sample.kelvin == curve.day());
+        assert!((15..=17).contains(&plateau.count()));
+    }
+}
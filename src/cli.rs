@@ -0,0 +1,1431 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Command line parsing for the `solar-clock-rs` binary.
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime};
+use solar_clock_rs::{
+    CctCurve, CctError, CoordError, EventKind, InterpolationMethod, SolarTargets, TargetsError,
+};
+
+use crate::format::OutputFormat;
+use crate::mqtt::Broker;
+use crate::output::OutputMode;
+
+pub const USAGE: &str = "\
+Usage: solar-clock-rs [OPTIONS] [COMMAND]
+
+Commands:
+  events [--date YYYY-MM-DD] [--photo]
+                   Print twilight, sunrise, transit and sunset (default:
+                   today), and with --photo the golden and blue hours
+  range --from YYYY-MM-DD --to YYYY-MM-DD
+                   Print one row of solar events per day, both ends inclusive
+  ics --from YYYY-MM-DD --to YYYY-MM-DD
+                   Print the anchor events of each day as an iCalendar file,
+                   such as Sunset (solar 20:00)
+  next <HH:MM[:SS]> [--after RFC3339]
+                   Print when the solar clock next reads this wall time
+                   (default: after now)
+  sleep-until <sunrise|transit|sunset|HH:MM[:SS]>
+                   Block until the next such solar event or solar wall time
+  analemma --at <HH:MM[:SS]> [--year YEAR] [--solar]
+                   Print the sun's azimuth, elevation and the delta at this
+                   wall time every day of the year, on the solar offset or
+                   with --solar on the solar clock (default: this year)
+  plot [--year YEAR] [--hourly] [--width PX] [--height PX] --out <PATH>
+                   Write an SVG chart of the delta over the year, sampled
+                   daily at the transit target or hourly (default: this
+                   year, 800x400)
+  export-c [--year YEAR]
+                   Print a C header with each day's anchors and deltas, for
+                   microcontroller clocks (default: this year)
+  tzif --from YYYY-MM-DD --to YYYY-MM-DD [--granularity SECONDS] <PATH>
+                   Write a zoneinfo file whose offset follows the solar clock
+                   to within half the granularity (default 600), for use as
+                   TZ=:PATH
+  alarms-ics --at-solar <HH:MM[:SS]>... --from YYYY-MM-DD --to YYYY-MM-DD
+                   Print one calendar event per day at each given solar wall
+                   time, at the civil instant the solar clock reads it
+  run --at-solar <HH:MM[:SS]>... -- <COMMAND>...
+                   Run a command every day at each given solar wall time
+  repl             Read queries from standard input, keeping the clock in memory
+  cct [--date YYYY-MM-DD] [--step MINUTES] [--night K] [--day K]
+      [--morning HH:MM-HH:MM] [--evening HH:MM-HH:MM]
+                   Print the colour temperature now and over the solar day,
+                   ramping from night to day and back between these solar
+                   times (default: today, every 15 minutes, 2500 K and
+                   5500 K, 07:00-10:00 and 18:00-21:00)
+  night-light <gammastep|redshift|wlsunset> [-- <ARGS>...]
+                   Run the tool with today's --dawn and --dusk transitions
+  query [--socket PATH] <REQUEST>...
+                   Send NOW, AT <RFC3339>, NEXT <HH:MM> or EVENTS [DATE] to
+                   a --socket server and print its JSON answer (default
+                   $XDG_RUNTIME_DIR/solar-clock.sock)
+  dbus             Export the solar time and the next anchor as
+                   org.solarclock.Clock1 on the session bus (needs the
+                   dbus feature)
+  publish --broker mqtt[s]://HOST[:PORT] [--topic TOPIC] [--interval SECONDS]
+          [--ca-file PATH]
+                   Publish the solar time to TOPIC/state, retained, every
+                   interval (default 60), and each sunrise, transit and
+                   sunset to TOPIC/event (default topic solar/clock);
+                   mqtts:// needs the tls feature and checks the broker
+                   against --ca-file, a PEM bundle, or the Mozilla roots
+  serve [--listen IP:PORT] [--interval SECONDS]
+                   Answer GET /now, /at?t=, /events?date= and /next?solar=
+                   with the --json documents (default 127.0.0.1:8080), and
+                   push the solar time over a WebSocket at /ws (default 1)
+  watch [--interval SECONDS]
+                   Keep printing the solar time until interrupted (default 1)
+  save-model [--date YYYY-MM-DD] <PATH>
+                   Save the interpolation model of the solar date (default:
+                   today) for --load-model
+  seasons [YEAR]   Print the equinoxes and solstices (default: this year),
+                   and the transits with the sun overhead
+  validate [--date YYYY-MM-DD]
+                   Check that every solar minute of the day is shown exactly
+                   once (default: today)
+
+Options:
+  --lat <DEGREES>  Observer latitude, positive north [-90, 90]
+  --lon <DEGREES>  Observer longitude, positive east [-180, 180]
+  --at <RFC3339|@EPOCH>
+                   Evaluate this instant instead of the current time
+  --epoch <SECONDS>
+                   Same as --at @SECONDS (Unix seconds, fractions allowed)
+  --targets <SUNRISE,TRANSIT,SUNSET>
+                   Solar wall times as HH:MM[:SS] (default 08:00,14:00,20:00)
+  --midnight <HH:MM[:SS]|none>
+                   Solar wall time of solar midnight, before the sunrise
+                   target, e.g. 02:00 (default none, unanchored)
+  --solar-offset <±HH:MM>
+                   UTC offset of the solar wall time (default +01:00)
+  --window-days <N>
+                   Days of anchors taken on each side of the date (default 1)
+  --zenith <DEGREES>
+                   Zenith of the sun's centre at sunrise and sunset (default
+                   90.833; 90 for the geometric horizon, 108 for astronomical
+                   twilight)
+  --elevation <METRES>
+                   Observer height above the horizon, which makes sunrise
+                   earlier and sunset later (default 0)
+  --temperature <CELSIUS>, --pressure <HPA>
+                   Air temperature and pressure for the refraction at the
+                   horizon (default: the standard 10 °C and 1010 hPa)
+  --polar-fallback Run as mean solar time on days without sunrise or sunset,
+                   blending back to the targets over the following days
+  --interp <METHOD>
+                   Interpolation between anchors: pchip (default), linear or
+                   akima
+  --format <TEMPLATE>
+                   Print a single line: strftime codes for the solar time plus
+                   {delta_s} and {delta_hms} for the delta, {epoch} for the
+                   solar instant in Unix seconds, {eot} for the equation of
+                   time
+  -v, --verbose    Log to stderr: -v configuration, -vv anchors,
+                   -vvv interpolator evaluations
+  --output <MODE>  Machine-readable output: waybar (one JSON object per
+                   evaluation, also per tick in watch mode), json,
+                   ndjson (one line per tick in watch mode with a sequence
+                   number, the instant, the solar time and the delta),
+                   csv (the range table, and civil,solar,delta_s records
+                   for --stdin and watch mode) or home-assistant (a state
+                   and flat attributes for a command_line sensor, with
+                   value_template {{ value_json.state }} and
+                   json_attributes_path $.attributes) or redshift (the
+                   civil dawn-time= and dusk-time= of today's --dawn and
+                   --dusk, for gammastep and redshift)
+  --json           Same as --output json: one JSON document with the result,
+                   also for events, range and next (one per tick in watch
+                   mode)
+  -q, --quiet      Print only the solar time as HH:MM, or as --format
+  --sparkline      Also print the delta and the sun elevation over the day
+                   as one-line charts
+  --dawn <HH:MM[:SS]>, --dusk <HH:MM[:SS]>
+                   Solar wall times of the day and night transitions for
+                   --output redshift and night-light (default 08:00, 20:00)
+  --stdin          Read one RFC 3339 timestamp per line from standard input
+  --socket <PATH>  Answer query requests on this Unix socket, one JSON line
+                   per request line, until killed
+  --load-model <PATH>
+                   Use a model written by save-model for its date instead of
+                   computing it; refused when saved with other settings
+  --force          Use the --load-model file even if its settings differ
+  --cached         Keep the model of the day in $XDG_CACHE_HOME/solar-clock/
+                   and reuse it on later runs, for shell prompts
+  --config <PATH>  Configuration file (default
+                   $XDG_CONFIG_HOME/solar-clock/config.toml)
+  -h, --help       Print this help
+
+Environment (overridden by the options above, overrides the config file):
+  SOLAR_CLOCK_LAT, SOLAR_CLOCK_LON, SOLAR_CLOCK_TARGETS, SOLAR_CLOCK_MIDNIGHT,
+  SOLAR_CLOCK_OFFSET
+
+Exit status:
+  0 success, 1 I/O error, 2 invalid arguments or configuration,
+  3 no solar data (the events do not occur), 4 internal failure";
+
+/// Subcommand selected on the command line.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum Command {
+    /// Evaluate the solar clock at a single instant.
+    #[default]
+    Clock,
+    Events {
+        date: Option<NaiveDate>,
+        /// Also print the golden and blue hours.
+        photo: bool,
+    },
+    Range {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    Ics {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    Tzif {
+        from: NaiveDate,
+        to: NaiveDate,
+        /// Seconds the offset is rounded to.
+        granularity: Option<u32>,
+        path: PathBuf,
+    },
+    AlarmsIcs {
+        times: Vec<NaiveTime>,
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    Next {
+        time: NaiveTime,
+        after: Option<DateTime<FixedOffset>>,
+    },
+    Watch {
+        interval: Option<Duration>,
+    },
+    Repl,
+    Dbus,
+    NightLight {
+        tool: NightLightTool,
+        args: Vec<String>,
+    },
+    /// Send a request to a `--socket` server.
+    Query {
+        request: Vec<String>,
+    },
+    Publish {
+        broker: Broker,
+        topic: Option<String>,
+        interval: Option<Duration>,
+        /// PEM bundle of the certificates trusted for a TLS broker.
+        ca_file: Option<PathBuf>,
+    },
+    Serve {
+        listen: Option<SocketAddr>,
+        /// Period of the WebSocket messages.
+        interval: Option<Duration>,
+    },
+    SleepUntil {
+        target: SleepTarget,
+    },
+    Run {
+        times: Vec<NaiveTime>,
+        command: Vec<String>,
+    },
+    Validate {
+        date: Option<NaiveDate>,
+    },
+    /// Save the interpolation model of a solar date to a file.
+    SaveModel {
+        date: Option<NaiveDate>,
+        path: PathBuf,
+    },
+    Cct {
+        date: Option<NaiveDate>,
+        /// Minutes between samples.
+        step: Option<u32>,
+        curve: CctCurve,
+    },
+    Seasons {
+        year: Option<i32>,
+    },
+    Analemma {
+        time: NaiveTime,
+        year: Option<i32>,
+        /// Read `time` on the solar clock instead of the reference offset.
+        solar: bool,
+    },
+    ExportC {
+        year: Option<i32>,
+    },
+    Plot {
+        year: Option<i32>,
+        /// Sample every hour instead of once a day.
+        hourly: bool,
+        width: Option<u32>,
+        height: Option<u32>,
+        out: PathBuf,
+    },
+}
+
+/// Screen colour temperature tool started by `night-light`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NightLightTool {
+    Gammastep,
+    Redshift,
+    Wlsunset,
+}
+
+impl NightLightTool {
+    /// Name of the executable.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Gammastep => "gammastep",
+            Self::Redshift => "redshift",
+            Self::Wlsunset => "wlsunset",
+        }
+    }
+}
+
+/// What `sleep-until` waits for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SleepTarget {
+    Event(EventKind),
+    /// A wall time on the solar clock.
+    SolarTime(NaiveTime),
+}
+
+/// Options given on the command line. `None` means "use the configuration
+/// file or the default".
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub at: Option<DateTime<FixedOffset>>,
+    pub targets: Option<SolarTargets>,
+    /// `Some(None)` disables the solar midnight anchor.
+    pub midnight: Option<Option<NaiveTime>>,
+    pub solar_offset: Option<FixedOffset>,
+    pub interpolation: Option<InterpolationMethod>,
+    pub window_days: Option<u32>,
+    pub polar_fallback: bool,
+    pub zenith: Option<f64>,
+    pub elevation: Option<f64>,
+    pub temperature: Option<f64>,
+    pub pressure: Option<f64>,
+    pub format: Option<OutputFormat>,
+    pub output: Option<OutputMode>,
+    pub config: Option<PathBuf>,
+    /// Model file to use for its date instead of computing the model.
+    pub load_model: Option<PathBuf>,
+    /// Load the model file even if it was saved with other settings.
+    pub force: bool,
+    /// Reuse the model of the day from the cache directory.
+    pub cached: bool,
+    pub stdin: bool,
+    /// Serve queries on this Unix socket, or with `query`, send them to it.
+    pub socket: Option<PathBuf>,
+    pub quiet: bool,
+    /// Also print sparklines of the delta and the sun elevation over the day.
+    pub sparkline: bool,
+    /// Solar wall times of the day and night transitions.
+    pub dawn: Option<NaiveTime>,
+    pub dusk: Option<NaiveTime>,
+    pub verbose: u8,
+    pub help: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliError {
+    UnknownArgument(String),
+    UnknownCommand(String),
+    MissingValue(&'static str),
+    MissingFlag(&'static str),
+    /// `run` without a command after `--`.
+    MissingCommand,
+    InvalidNumber {
+        flag: &'static str,
+        value: String,
+    },
+    InvalidTimestamp {
+        value: String,
+        reason: chrono::ParseError,
+    },
+    InvalidTime {
+        flag: &'static str,
+        value: String,
+    },
+    InvalidOffset(String),
+    InvalidEpoch(String),
+    InvalidOutput(String),
+    InvalidInterpolation(String),
+    InvalidWindow(String),
+    InvalidZenith(String),
+    InvalidElevation(String),
+    InvalidTemperature(String),
+    InvalidPressure(String),
+    InvalidInterval(f64),
+    InvalidDate(String),
+    InvalidYear(String),
+    InvalidGranularity(String),
+    InvalidSize(String),
+    InvalidListen(String),
+    InvalidTool(String),
+    InvalidStep(String),
+    InvalidBroker {
+        value: String,
+        reason: String,
+    },
+    InvalidFormat {
+        value: String,
+        reason: String,
+    },
+    /// `--from` is after `--to`.
+    EmptyRange,
+    /// A flag that only applies to another subcommand.
+    MisplacedFlag(String),
+    Coordinates(CoordError),
+    Targets(TargetsError),
+    Cct(CctError),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownArgument(arg) => write!(f, "unknown argument '{arg}'"),
+            Self::UnknownCommand(command) => write!(f, "unknown command '{command}'"),
+            Self::MissingValue(flag) => write!(f, "missing value for '{flag}'"),
+            Self::MissingFlag(flag) => write!(f, "missing required flag '{flag}'"),
+            Self::MissingCommand => write!(f, "missing command after '--'"),
+            Self::InvalidNumber { flag, value } => {
+                write!(f, "invalid value '{value}' for '{flag}': expected a number")
+            }
+            Self::InvalidTimestamp { value, reason } => {
+                write!(f, "invalid RFC 3339 timestamp '{value}': {reason}")
+            }
+            Self::InvalidTime { flag, value } => {
+                write!(
+                    f,
+                    "invalid value '{value}' for '{flag}': expected HH:MM[:SS]"
+                )
+            }
+            Self::InvalidOffset(value) => {
+                write!(f, "invalid solar offset '{value}': expected ±HH:MM")
+            }
+            Self::InvalidEpoch(value) => {
+                write!(f, "invalid epoch '{value}': expected Unix seconds")
+            }
+            Self::InvalidOutput(value) => {
+                write!(
+                    f,
+                    "invalid output mode '{value}': expected waybar, json, ndjson, csv, home-assistant or redshift"
+                )
+            }
+            Self::InvalidInterpolation(value) => write!(
+                f,
+                "invalid interpolation method '{value}': expected pchip, linear or akima"
+            ),
+            Self::InvalidWindow(value) => write!(
+                f,
+                "invalid window '{value}': expected a whole number of days, at least 1"
+            ),
+            Self::InvalidZenith(value) => write!(
+                f,
+                "invalid zenith '{value}': expected degrees strictly between 0 and 180"
+            ),
+            Self::InvalidElevation(value) => write!(
+                f,
+                "invalid elevation '{value}': expected a non-negative number of metres"
+            ),
+            Self::InvalidTemperature(value) => write!(
+                f,
+                "invalid temperature '{value}': expected °C above absolute zero"
+            ),
+            Self::InvalidPressure(value) => write!(
+                f,
+                "invalid pressure '{value}': expected a positive number of hPa"
+            ),
+            Self::InvalidInterval(seconds) => {
+                write!(
+                    f,
+                    "invalid interval {seconds}: expected a positive number of seconds"
+                )
+            }
+            Self::InvalidDate(value) => {
+                write!(f, "invalid date '{value}': expected YYYY-MM-DD")
+            }
+            Self::InvalidYear(value) => write!(f, "invalid year '{value}'"),
+            Self::InvalidSize(value) => write!(
+                f,
+                "invalid size '{value}': expected a whole number of pixels, at least 200"
+            ),
+            Self::InvalidBroker { value, reason } => {
+                write!(f, "invalid broker '{value}': {reason}")
+            }
+            Self::InvalidTool(value) => write!(
+                f,
+                "invalid tool '{value}': expected gammastep, redshift or wlsunset"
+            ),
+            Self::InvalidStep(value) => write!(
+                f,
+                "invalid step '{value}': expected a whole number of minutes, at least 1"
+            ),
+            Self::InvalidListen(value) => {
+                write!(f, "invalid listen address '{value}': expected IP:PORT")
+            }
+            Self::InvalidGranularity(value) => write!(
+                f,
+                "invalid granularity '{value}': expected a whole number of seconds, at least 1"
+            ),
+            Self::InvalidFormat { value, reason } => {
+                write!(f, "invalid format '{value}': {reason}")
+            }
+            Self::EmptyRange => write!(f, "'--from' must not be after '--to'"),
+            Self::MisplacedFlag(flag) => {
+                write!(f, "'{flag}' is not valid for this command")
+            }
+            Self::Coordinates(e) => e.fmt(f),
+            Self::Targets(e) => e.fmt(f),
+            Self::Cct(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// [`Command`] while its arguments are being read: values the command
+/// requires are checked by [`Pending::finish`].
+#[derive(Debug, Default, Clone, PartialEq)]
+enum Pending {
+    /// Evaluate the solar clock at a single instant.
+    #[default]
+    Clock,
+    Events {
+        date: Option<NaiveDate>,
+        /// Also print the golden and blue hours.
+        photo: bool,
+    },
+    Range {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    Ics {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    Tzif {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+        /// Seconds the offset is rounded to.
+        granularity: Option<u32>,
+        path: Option<PathBuf>,
+    },
+    AlarmsIcs {
+        times: Vec<NaiveTime>,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
+    Next {
+        time: Option<NaiveTime>,
+        after: Option<DateTime<FixedOffset>>,
+    },
+    Watch {
+        interval: Option<Duration>,
+    },
+    SleepUntil {
+        target: Option<SleepTarget>,
+    },
+    Run {
+        times: Vec<NaiveTime>,
+        command: Vec<String>,
+    },
+    Repl,
+    Validate {
+        date: Option<NaiveDate>,
+    },
+    Seasons {
+        year: Option<i32>,
+    },
+    Plot {
+        year: Option<i32>,
+        /// Sample every hour instead of once a day.
+        hourly: bool,
+        width: Option<u32>,
+        height: Option<u32>,
+        out: Option<PathBuf>,
+    },
+    Analemma {
+        time: Option<NaiveTime>,
+        year: Option<i32>,
+        /// Read `time` on the solar clock instead of the reference offset.
+        solar: bool,
+    },
+    ExportC {
+        year: Option<i32>,
+    },
+    Serve {
+        listen: Option<SocketAddr>,
+        /// Period of the WebSocket messages.
+        interval: Option<Duration>,
+    },
+    Publish {
+        broker: Option<Broker>,
+        topic: Option<String>,
+        interval: Option<Duration>,
+        /// PEM bundle of the certificates trusted for a TLS broker.
+        ca_file: Option<PathBuf>,
+    },
+    Dbus,
+    Query {
+        request: Vec<String>,
+    },
+    NightLight {
+        tool: Option<NightLightTool>,
+        args: Vec<String>,
+    },
+    Cct {
+        date: Option<NaiveDate>,
+        /// Minutes between samples.
+        step: Option<u32>,
+        night: Option<f64>,
+        day: Option<f64>,
+        morning: Option<(NaiveTime, NaiveTime)>,
+        evening: Option<(NaiveTime, NaiveTime)>,
+    },
+    SaveModel {
+        date: Option<NaiveDate>,
+        path: Option<PathBuf>,
+    },
+}
+
+impl Pending {
+    /// Checks that the values the command requires were given.
+    fn finish(self) -> Result<Command, CliError> {
+        let range = |from: Option<NaiveDate>, to: Option<NaiveDate>| {
+            let from = from.ok_or(CliError::MissingFlag("--from"))?;
+            let to = to.ok_or(CliError::MissingFlag("--to"))?;
+            if from > to {
+                return Err(CliError::EmptyRange);
+            }
+            Ok((from, to))
+        };
+
+        Ok(match self {
+            Self::Clock => Command::Clock,
+            Self::Events { date, photo } => Command::Events { date, photo },
+            Self::Range { from, to } => {
+                let (from, to) = range(from, to)?;
+                Command::Range { from, to }
+            }
+            Self::Ics { from, to } => {
+                let (from, to) = range(from, to)?;
+                Command::Ics { from, to }
+            }
+            Self::Tzif {
+                from,
+                to,
+                granularity,
+                path,
+            } => {
+                let (from, to) = range(from, to)?;
+                Command::Tzif {
+                    from,
+                    to,
+                    granularity,
+                    path: path.ok_or(CliError::MissingValue("tzif"))?,
+                }
+            }
+            Self::AlarmsIcs { times, from, to } => {
+                let (from, to) = range(from, to)?;
+                if times.is_empty() {
+                    return Err(CliError::MissingFlag("--at-solar"));
+                }
+                Command::AlarmsIcs { times, from, to }
+            }
+            Self::Next { time, after } => Command::Next {
+                time: time.ok_or(CliError::MissingValue("next"))?,
+                after,
+            },
+            Self::Watch { interval } => Command::Watch { interval },
+            Self::SleepUntil { target } => Command::SleepUntil {
+                target: target.ok_or(CliError::MissingValue("sleep-until"))?,
+            },
+            Self::Run { times, command } => {
+                if times.is_empty() {
+                    return Err(CliError::MissingFlag("--at-solar"));
+                }
+                if command.is_empty() {
+                    return Err(CliError::MissingCommand);
+                }
+                Command::Run { times, command }
+            }
+            Self::Repl => Command::Repl,
+            Self::Validate { date } => Command::Validate { date },
+            Self::Seasons { year } => Command::Seasons { year },
+            Self::Plot {
+                year,
+                hourly,
+                width,
+                height,
+                out,
+            } => Command::Plot {
+                year,
+                hourly,
+                width,
+                height,
+                out: out.ok_or(CliError::MissingFlag("--out"))?,
+            },
+            Self::Analemma { time, year, solar } => Command::Analemma {
+                time: time.ok_or(CliError::MissingFlag("--at"))?,
+                year,
+                solar,
+            },
+            Self::ExportC { year } => Command::ExportC { year },
+            Self::Serve { listen, interval } => Command::Serve { listen, interval },
+            Self::Publish {
+                broker,
+                topic,
+                interval,
+                ca_file,
+            } => {
+                let broker = broker.ok_or(CliError::MissingFlag("--broker"))?;
+                if ca_file.is_some() && !broker.tls {
+                    return Err(CliError::InvalidBroker {
+                        value: broker.to_string(),
+                        reason: "--ca-file only applies to an mqtts:// broker".to_owned(),
+                    });
+                }
+                Command::Publish {
+                    broker,
+                    topic,
+                    interval,
+                    ca_file,
+                }
+            }
+            Self::Dbus => Command::Dbus,
+            Self::Query { request } => {
+                if request.is_empty() {
+                    return Err(CliError::MissingValue("query"));
+                }
+                Command::Query { request }
+            }
+            Self::NightLight { tool, args } => Command::NightLight {
+                tool: tool.ok_or(CliError::MissingValue("night-light"))?,
+                args,
+            },
+            Self::Cct {
+                date,
+                step,
+                night,
+                day,
+                morning,
+                evening,
+            } => Command::Cct {
+                date,
+                step,
+                curve: cct_curve(night, day, morning, evening)?,
+            },
+            Self::SaveModel { date, path } => Command::SaveModel {
+                date,
+                path: path.ok_or(CliError::MissingValue("save-model"))?,
+            },
+        })
+    }
+}
+
+/// Parses the arguments following the program name.
+///
+/// Flags take their value either as the next argument (`--lat 51.5`) or
+/// inline (`--lat=51.5`).
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, CliError> {
+    let mut parsed = Args::default();
+    let mut command = Pending::Clock;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_owned(), Some(value.into())),
+            _ => (arg, None),
+        };
+        let mut value = |name: &'static str| {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or(CliError::MissingValue(name))
+        };
+
+        match flag.as_str() {
+            "--lat" => parsed.latitude = Some(parse_number("--lat", value("--lat")?)?),
+            "--lon" => parsed.longitude = Some(parse_number("--lon", value("--lon")?)?),
+            // The analemma takes a daily wall time rather than an instant
+            "--at" if matches!(command, Pending::Analemma { .. }) => {
+                let value = parse_time("--at", &value("--at")?)?;
+                if let Pending::Analemma { time, .. } = &mut command {
+                    *time = Some(value);
+                }
+            }
+            "--at" => parsed.at = Some(parse_timestamp(value("--at")?)?),
+            "--epoch" => parsed.at = Some(parse_epoch(value("--epoch")?)?),
+            "--targets" => parsed.targets = Some(parse_targets(value("--targets")?)?),
+            "--midnight" => parsed.midnight = Some(parse_midnight(value("--midnight")?)?),
+            "--solar-offset" => parsed.solar_offset = Some(parse_offset(value("--solar-offset")?)?),
+            "--interp" => parsed.interpolation = Some(parse_interpolation(value("--interp")?)?),
+            "--window-days" => parsed.window_days = Some(parse_window(value("--window-days")?)?),
+            "--format" => parsed.format = Some(parse_format(value("--format")?)?),
+            "--output" => {
+                let mode = value("--output")?;
+                parsed.output =
+                    Some(OutputMode::parse(&mode).ok_or(CliError::InvalidOutput(mode))?);
+            }
+            "--json" => parsed.output = Some(OutputMode::Json),
+            "--config" => parsed.config = Some(value("--config")?.into()),
+            "--load-model" => parsed.load_model = Some(value("--load-model")?.into()),
+            "--force" => parsed.force = true,
+            "--cached" => parsed.cached = true,
+            "--zenith" => parsed.zenith = Some(parse_zenith(value("--zenith")?)?),
+            "--elevation" => parsed.elevation = Some(parse_elevation(value("--elevation")?)?),
+            "--temperature" => {
+                parsed.temperature = Some(parse_temperature(value("--temperature")?)?)
+            }
+            "--pressure" => parsed.pressure = Some(parse_pressure(value("--pressure")?)?),
+            "--polar-fallback" => parsed.polar_fallback = true,
+            "--stdin" => parsed.stdin = true,
+            "--socket" => parsed.socket = Some(value("--socket")?.into()),
+            "-q" | "--quiet" => parsed.quiet = true,
+            "--sparkline" => parsed.sparkline = true,
+            "--dawn" => parsed.dawn = Some(parse_time("--dawn", &value("--dawn")?)?),
+            "--dusk" => parsed.dusk = Some(parse_time("--dusk", &value("--dusk")?)?),
+            "--verbose" => parsed.verbose = parsed.verbose.saturating_add(1),
+            // -v, -vv, -vvv...
+            short
+                if short.len() > 1
+                    && short
+                        .strip_prefix('-')
+                        .is_some_and(|v| v.bytes().all(|b| b == b'v')) =>
+            {
+                parsed.verbose = parsed
+                    .verbose
+                    .saturating_add(u8::try_from(short.len() - 1).unwrap_or(u8::MAX))
+            }
+            "--date" => {
+                let date = parse_date(value("--date")?)?;
+                match &mut command {
+                    Pending::Events { date: slot, .. }
+                    | Pending::Validate { date: slot }
+                    | Pending::SaveModel { date: slot, .. }
+                    | Pending::Cct { date: slot, .. } => *slot = Some(date),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--photo" => match &mut command {
+                Pending::Events { photo, .. } => *photo = true,
+                _ => return Err(CliError::MisplacedFlag(flag)),
+            },
+            "--from" | "--to" => {
+                let name = if flag == "--from" { "--from" } else { "--to" };
+                let date = parse_date(value(name)?)?;
+                match &mut command {
+                    Pending::Range { from, .. }
+                    | Pending::Ics { from, .. }
+                    | Pending::Tzif { from, .. }
+                    | Pending::AlarmsIcs { from, .. }
+                        if flag == "--from" =>
+                    {
+                        *from = Some(date)
+                    }
+                    Pending::Range { to, .. }
+                    | Pending::Ics { to, .. }
+                    | Pending::Tzif { to, .. }
+                    | Pending::AlarmsIcs { to, .. } => *to = Some(date),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--year" => {
+                let value = value("--year")?;
+                match &mut command {
+                    Pending::Plot { year, .. }
+                    | Pending::Analemma { year, .. }
+                    | Pending::ExportC { year } => *year = Some(parse_year(&value)?),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--out" => {
+                let value = value("--out")?;
+                match &mut command {
+                    Pending::Plot { out, .. } => *out = Some(value.into()),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--width" | "--height" => {
+                let name = if flag == "--width" {
+                    "--width"
+                } else {
+                    "--height"
+                };
+                let pixels = parse_size(value(name)?)?;
+                match &mut command {
+                    Pending::Plot { width, .. } if flag == "--width" => *width = Some(pixels),
+                    Pending::Plot { height, .. } => *height = Some(pixels),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--broker" => {
+                let value = value("--broker")?;
+                let address = Broker::parse(&value)
+                    .map_err(|reason| CliError::InvalidBroker { value, reason })?;
+                match &mut command {
+                    Pending::Publish { broker, .. } => *broker = Some(address),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--topic" => {
+                let value = value("--topic")?;
+                match &mut command {
+                    Pending::Publish { topic, .. } => *topic = Some(value),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--ca-file" => {
+                let value = value("--ca-file")?;
+                match &mut command {
+                    Pending::Publish { ca_file, .. } => *ca_file = Some(value.into()),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--listen" => {
+                let value = value("--listen")?;
+                let address = value.parse().map_err(|_| CliError::InvalidListen(value))?;
+                match &mut command {
+                    Pending::Serve { listen, .. } => *listen = Some(address),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--solar" => match &mut command {
+                Pending::Analemma { solar, .. } => *solar = true,
+                _ => return Err(CliError::MisplacedFlag(flag)),
+            },
+            "--hourly" => match &mut command {
+                Pending::Plot { hourly, .. } => *hourly = true,
+                _ => return Err(CliError::MisplacedFlag(flag)),
+            },
+            "--granularity" => {
+                let seconds = parse_granularity(value("--granularity")?)?;
+                match &mut command {
+                    Pending::Tzif { granularity, .. } => *granularity = Some(seconds),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--after" => {
+                let instant = parse_timestamp(value("--after")?)?;
+                match &mut command {
+                    Pending::Next { after, .. } => *after = Some(instant),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--interval" => {
+                let seconds = parse_number("--interval", value("--interval")?)?;
+                let interval = Duration::try_from_secs_f64(seconds)
+                    .ok()
+                    .filter(|interval| !interval.is_zero())
+                    .ok_or(CliError::InvalidInterval(seconds))?;
+                match &mut command {
+                    Pending::Watch { interval: slot }
+                    | Pending::Serve { interval: slot, .. }
+                    | Pending::Publish { interval: slot, .. } => *slot = Some(interval),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--step" => {
+                let minutes = parse_step(value("--step")?)?;
+                match &mut command {
+                    Pending::Cct { step, .. } => *step = Some(minutes),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--night" | "--day" => {
+                let name = if flag == "--night" {
+                    "--night"
+                } else {
+                    "--day"
+                };
+                let kelvin = parse_number(name, value(name)?)?;
+                match &mut command {
+                    Pending::Cct { night, .. } if flag == "--night" => *night = Some(kelvin),
+                    Pending::Cct { day, .. } => *day = Some(kelvin),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--morning" | "--evening" => {
+                let name = if flag == "--morning" {
+                    "--morning"
+                } else {
+                    "--evening"
+                };
+                let ramp = parse_ramp(name, value(name)?)?;
+                match &mut command {
+                    Pending::Cct { morning, .. } if flag == "--morning" => *morning = Some(ramp),
+                    Pending::Cct { evening, .. } => *evening = Some(ramp),
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--at-solar" => {
+                let time = parse_time("--at-solar", &value("--at-solar")?)?;
+                match &mut command {
+                    Pending::Run { times, .. } | Pending::AlarmsIcs { times, .. } => {
+                        times.push(time)
+                    }
+                    _ => return Err(CliError::MisplacedFlag(flag)),
+                }
+            }
+            "--" => match &mut command {
+                Pending::Run { command, .. } | Pending::NightLight { args: command, .. } => {
+                    command.extend(args.by_ref())
+                }
+                _ => return Err(CliError::UnknownArgument(flag)),
+            },
+            "-h" | "--help" => parsed.help = true,
+            "events" if command == Pending::Clock => {
+                command = Pending::Events {
+                    date: None,
+                    photo: false,
+                }
+            }
+            "range" if command == Pending::Clock => {
+                command = Pending::Range {
+                    from: None,
+                    to: None,
+                }
+            }
+            "ics" if command == Pending::Clock => {
+                command = Pending::Ics {
+                    from: None,
+                    to: None,
+                }
+            }
+            "analemma" if command == Pending::Clock => {
+                command = Pending::Analemma {
+                    time: None,
+                    year: None,
+                    solar: false,
+                }
+            }
+            "export-c" if command == Pending::Clock => command = Pending::ExportC { year: None },
+            "plot" if command == Pending::Clock => {
+                command = Pending::Plot {
+                    year: None,
+                    hourly: false,
+                    width: None,
+                    height: None,
+                    out: None,
+                }
+            }
+            "tzif" if command == Pending::Clock => {
+                command = Pending::Tzif {
+                    from: None,
+                    to: None,
+                    granularity: None,
+                    path: None,
+                }
+            }
+            "alarms-ics" if command == Pending::Clock => {
+                command = Pending::AlarmsIcs {
+                    times: Vec::new(),
+                    from: None,
+                    to: None,
+                }
+            }
+            "next" if command == Pending::Clock => {
+                command = Pending::Next {
+                    time: None,
+                    after: None,
+                }
+            }
+            "run" if command == Pending::Clock => {
+                command = Pending::Run {
+                    times: Vec::new(),
+                    command: Vec::new(),
+                }
+            }
+            "repl" if command == Pending::Clock => command = Pending::Repl,
+            "dbus" if command == Pending::Clock => command = Pending::Dbus,
+            "night-light" if command == Pending::Clock => {
+                command = Pending::NightLight {
+                    tool: None,
+                    args: Vec::new(),
+                }
+            }
+            "query" if command == Pending::Clock => {
+                command = Pending::Query {
+                    request: Vec::new(),
+                }
+            }
+            "publish" if command == Pending::Clock => {
+                command = Pending::Publish {
+                    broker: None,
+                    topic: None,
+                    interval: None,
+                    ca_file: None,
+                }
+            }
+            "serve" if command == Pending::Clock => {
+                command = Pending::Serve {
+                    listen: None,
+                    interval: None,
+                }
+            }
+            "watch" if command == Pending::Clock => command = Pending::Watch { interval: None },
+            "validate" if command == Pending::Clock => command = Pending::Validate { date: None },
+            "save-model" if command == Pending::Clock => {
+                command = Pending::SaveModel {
+                    date: None,
+                    path: None,
+                }
+            }
+            "cct" if command == Pending::Clock => {
+                command = Pending::Cct {
+                    date: None,
+                    step: None,
+                    night: None,
+                    day: None,
+                    morning: None,
+                    evening: None,
+                }
+            }
+            "seasons" if command == Pending::Clock => command = Pending::Seasons { year: None },
+            "sleep-until" if command == Pending::Clock => {
+                command = Pending::SleepUntil { target: None }
+            }
+            positional if !positional.starts_with('-') => match &mut command {
+                Pending::Next {
+                    time: slot @ None, ..
+                } => *slot = Some(parse_time("next", positional)?),
+                Pending::SleepUntil {
+                    target: slot @ None,
+                } => *slot = Some(parse_sleep_target(positional)?),
+                Pending::Seasons { year: slot @ None } => *slot = Some(parse_year(positional)?),
+                Pending::Tzif {
+                    path: slot @ None, ..
+                }
+                | Pending::SaveModel {
+                    path: slot @ None, ..
+                } => *slot = Some(positional.into()),
+                Pending::Query { request } => request.push(positional.to_owned()),
+                Pending::NightLight {
+                    tool: slot @ None, ..
+                } => *slot = Some(parse_tool(positional)?),
+                _ => return Err(CliError::UnknownArgument(flag)),
+            },
+            _ => return Err(CliError::UnknownArgument(flag)),
+        }
+    }
+
+    parsed.command = command.finish()?;
+    if parsed.force && parsed.load_model.is_none() {
+        return Err(CliError::MissingFlag("--load-model"));
+    }
+    Ok(parsed)
+}
+
+pub fn parse_number(flag: &'static str, value: String) -> Result<f64, CliError> {
+    match value.parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(CliError::InvalidNumber { flag, value }),
+    }
+}
+
+/// Parses `pchip`, `linear` or `akima`.
+pub fn parse_interpolation(value: String) -> Result<InterpolationMethod, CliError> {
+    match value.as_str() {
+        "pchip" => Ok(InterpolationMethod::Pchip),
+        "linear" => Ok(InterpolationMethod::Linear),
+        "akima" => Ok(InterpolationMethod::Akima),
+        _ => Err(CliError::InvalidInterpolation(value)),
+    }
+}
+
+/// Parses a window of at least one day.
+pub fn parse_window(value: String) -> Result<u32, CliError> {
+    match value.parse::<u32>() {
+        Ok(days) if days >= 1 => Ok(days),
+        _ => Err(CliError::InvalidWindow(value)),
+    }
+}
+
+/// Parses a calendar year such as `2026`.
+pub fn parse_year(value: &str) -> Result<i32, CliError> {
+    value
+        .parse::<i32>()
+        .map_err(|_| CliError::InvalidYear(value.to_owned()))
+}
+
+/// Parses an image dimension of at least 200 pixels.
+pub fn parse_size(value: String) -> Result<u32, CliError> {
+    match value.parse::<u32>() {
+        Ok(pixels) if pixels >= 200 => Ok(pixels),
+        _ => Err(CliError::InvalidSize(value)),
+    }
+}
+
+/// Parses a sampling step of at least one minute.
+pub fn parse_step(value: String) -> Result<u32, CliError> {
+    match value.parse::<u32>() {
+        Ok(minutes) if minutes >= 1 => Ok(minutes),
+        _ => Err(CliError::InvalidStep(value)),
+    }
+}
+
+/// Parses a `HH:MM-HH:MM` ramp of the colour temperature curve.
+fn parse_ramp(flag: &'static str, value: String) -> Result<(NaiveTime, NaiveTime), CliError> {
+    let Some((start, end)) = value.split_once('-') else {
+        return Err(CliError::InvalidTime { flag, value });
+    };
+    Ok((parse_time(flag, start)?, parse_time(flag, end)?))
+}
+
+/// Colour temperature curve of `cct`, the defaults replaced by the values
+/// given.
+pub fn cct_curve(
+    night: Option<f64>,
+    day: Option<f64>,
+    morning: Option<(NaiveTime, NaiveTime)>,
+    evening: Option<(NaiveTime, NaiveTime)>,
+) -> Result<CctCurve, CliError> {
+    let default = CctCurve::default();
+    CctCurve::new(
+        night.unwrap_or(default.night()),
+        day.unwrap_or(default.day()),
+        morning.unwrap_or(default.morning()),
+        evening.unwrap_or(default.evening()),
+    )
+    .map_err(CliError::Cct)
+}
+
+/// Parses a granularity of at least one second.
+pub fn parse_granularity(value: String) -> Result<u32, CliError> {
+    match value.parse::<u32>() {
+        Ok(seconds) if seconds >= 1 => Ok(seconds),
+        _ => Err(CliError::InvalidGranularity(value)),
+    }
+}
+
+/// Parses a zenith angle strictly between 0 and 180 degrees.
+pub fn parse_zenith(value: String) -> Result<f64, CliError> {
+    match value.parse::<f64>() {
+        Ok(zenith) if zenith > 0.0 && zenith < 180.0 => Ok(zenith),
+        _ => Err(CliError::InvalidZenith(value)),
+    }
+}
+
+/// Parses a non-negative elevation in metres.
+pub fn parse_elevation(value: String) -> Result<f64, CliError> {
+    match value.parse::<f64>() {
+        Ok(metres) if metres.is_finite() && metres >= 0.0 => Ok(metres),
+        _ => Err(CliError::InvalidElevation(value)),
+    }
+}
+
+/// Parses a temperature in °C above absolute zero.
+pub fn parse_temperature(value: String) -> Result<f64, CliError> {
+    match value.parse::<f64>() {
+        Ok(celsius) if celsius.is_finite() && celsius > -273.15 => Ok(celsius),
+        _ => Err(CliError::InvalidTemperature(value)),
+    }
+}
+
+/// Parses a positive pressure in hPa.
+pub fn parse_pressure(value: String) -> Result<f64, CliError> {
+    match value.parse::<f64>() {
+        Ok(hpa) if hpa.is_finite() && hpa > 0.0 => Ok(hpa),
+        _ => Err(CliError::InvalidPressure(value)),
+    }
+}
+
+fn parse_tool(value: &str) -> Result<NightLightTool, CliError> {
+    match value {
+        "gammastep" => Ok(NightLightTool::Gammastep),
+        "redshift" => Ok(NightLightTool::Redshift),
+        "wlsunset" => Ok(NightLightTool::Wlsunset),
+        _ => Err(CliError::InvalidTool(value.to_owned())),
+    }
+}
+
+fn parse_sleep_target(value: &str) -> Result<SleepTarget, CliError> {
+    match value {
+        "sunrise" => Ok(SleepTarget::Event(EventKind::Sunrise)),
+        "transit" => Ok(SleepTarget::Event(EventKind::Transit)),
+        "sunset" => Ok(SleepTarget::Event(EventKind::Sunset)),
+        time => parse_time("sleep-until", time).map(SleepTarget::SolarTime),
+    }
+}
+
+/// Parses an RFC 3339 timestamp, with or without fractional seconds, or Unix
+/// seconds prefixed with `@`.
+pub fn parse_timestamp(value: String) -> Result<DateTime<FixedOffset>, CliError> {
+    if let Some(epoch) = value.strip_prefix('@') {
+        return parse_epoch(epoch.to_owned());
+    }
+    DateTime::parse_from_rfc3339(&value)
+        .map_err(|reason| CliError::InvalidTimestamp { value, reason })
+}
+
+/// Parses Unix seconds such as `1767225600` or `-0.25`, exactly to the
+/// nanosecond, as an instant in UTC.
+pub fn parse_epoch(value: String) -> Result<DateTime<FixedOffset>, CliError> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((&value, ""));
+    let negative = whole.starts_with('-');
+    let nanos = if fraction.len() <= 9 && fraction.bytes().all(|b| b.is_ascii_digit()) {
+        format!("{fraction:0<9}").parse::<i64>().ok()
+    } else {
+        None
+    };
+    let total = whole
+        .parse::<i64>()
+        .ok()
+        .zip(nanos)
+        .and_then(|(secs, nanos)| {
+            secs.checked_mul(1_000_000_000)?
+                .checked_add(if negative { -nanos } else { nanos })
+        });
+
+    match total {
+        Some(total) => Ok(DateTime::from_timestamp_nanos(total).fixed_offset()),
+        None => Err(CliError::InvalidEpoch(value)),
+    }
+}
+
+/// Parses `SUNRISE,TRANSIT,SUNSET` as validated targets.
+pub fn parse_targets(value: String) -> Result<SolarTargets, CliError> {
+    let times = value
+        .split(',')
+        .map(|part| parse_time("--targets", part.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let [sunrise, transit, sunset] = times[..] else {
+        return Err(CliError::InvalidTime {
+            flag: "--targets",
+            value,
+        });
+    };
+    SolarTargets::new(sunrise, transit, sunset).map_err(CliError::Targets)
+}
+
+/// Parses a midnight target, or `none` to not anchor solar midnight.
+pub fn parse_midnight(value: String) -> Result<Option<NaiveTime>, CliError> {
+    match value.as_str() {
+        "none" => Ok(None),
+        time => parse_time("--midnight", time).map(Some),
+    }
+}
+
+/// Parses `HH:MM` or `HH:MM:SS`.
+pub fn parse_time(flag: &'static str, value: &str) -> Result<NaiveTime, CliError> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .map_err(|_| CliError::InvalidTime {
+            flag,
+            value: value.to_owned(),
+        })
+}
+
+/// Parses a `±HH:MM` offset such as `+05:45`.
+pub fn parse_offset(value: String) -> Result<FixedOffset, CliError> {
+    value.parse().map_err(|_| CliError::InvalidOffset(value))
+}
+
+/// Parses and validates an output template.
+pub fn parse_format(value: String) -> Result<OutputFormat, CliError> {
+    OutputFormat::parse(&value).map_err(|reason| CliError::InvalidFormat { value, reason })
+}
+
+/// Parses a `YYYY-MM-DD` date.
+pub fn parse_date(value: String) -> Result<NaiveDate, CliError> {
+    NaiveDate::parse_from_str(&value, "%Y-%m-%d").map_err(|_| CliError::InvalidDate(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Args, CliError> {
+        parse(args.iter().map(|arg| (*arg).to_owned()))
+    }
+
+    #[test]
+    fn coordinates_take_separate_or_inline_values() {
+        let args = parse_args(&["--lat", "51.5", "--lon=-0.12"]).unwrap();
+        assert_eq!((args.latitude, args.longitude), (Some(51.5), Some(-0.12)));
+
+        assert_eq!(parse_args(&["--lat"]), Err(CliError::MissingValue("--lat")));
+        assert_eq!(
+            parse_args(&["--lon", "inf"]),
+            Err(CliError::InvalidNumber {
+                flag: "--lon",
+                value: "inf".to_owned()
+            })
+        );
+        assert_eq!(
+            parse_args(&["--latitude", "51.5"]),
+            Err(CliError::UnknownArgument("--latitude".to_owned()))
+        );
+    }
+
+    #[test]
+    fn subcommands_require_their_values() {
+        let date = |day| NaiveDate::from_ymd_opt(2026, 2, day).unwrap();
+        let args = parse_args(&["range", "--from", "2026-02-03", "--to", "2026-02-05"]).unwrap();
+        assert_eq!(
+            args.command,
+            Command::Range {
+                from: date(3),
+                to: date(5)
+            }
+        );
+        assert_eq!(
+            parse_args(&["range", "--from", "2026-02-03"]),
+            Err(CliError::MissingFlag("--to"))
+        );
+        assert_eq!(
+            parse_args(&["range", "--from", "2026-02-05", "--to", "2026-02-03"]),
+            Err(CliError::EmptyRange)
+        );
+
+        let args = parse_args(&["next", "14:00"]).unwrap();
+        assert_eq!(
+            args.command,
+            Command::Next {
+                time: NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+                after: None
+            }
+        );
+        assert_eq!(parse_args(&["next"]), Err(CliError::MissingValue("next")));
+        assert_eq!(
+            parse_args(&["plot", "--year", "2026"]),
+            Err(CliError::MissingFlag("--out"))
+        );
+        assert!(matches!(
+            parse_args(&["cct", "--morning", "10:00-07:00"]),
+            Err(CliError::Cct(_))
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `analemma`: the position of the sun at the same time every day of a year.
+
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+
+use chrono::{NaiveDate, NaiveTime, SecondsFormat};
+use solar_clock_rs::SolarClock;
+use solar_clock_rs::solar_clock::get_target_time;
+use solar_clock_rs::spa::solar_position;
+
+use crate::exit;
+use crate::json::{self, Object};
+use crate::output::{self, OutputMode};
+
+/// Prints, for every day of `year`, the azimuth and apparent elevation of
+/// the sun and the delta at wall time `time`.
+///
+/// The wall time is read on the fixed solar reference offset, which traces
+/// the classic figure-eight, or with `solar` on the solar clock, whose
+/// figure depends on the targets: at the transit target it collapses onto
+/// the meridian. Rows are `date instant
+/// azimuth elevation delta`; `--output csv` and `--json` print the same
+/// columns as a table or a `days` array.
+pub fn run(
+    time: NaiveTime,
+    year: i32,
+    solar: bool,
+    clock: &SolarClock,
+    output: Option<OutputMode>,
+) -> ExitCode {
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        eprintln!("error: year {year} is out of range");
+        return ExitCode::from(exit::USAGE);
+    };
+    let offset = clock.offset();
+
+    let mut rows = Vec::new();
+    for date in first.iter_days().take_while(|date| *date <= last) {
+        let wall = get_target_time(date, time, offset);
+        let instant = if solar {
+            match clock.solar_to_civil(wall.with_timezone(&offset)) {
+                Ok(instant) => instant,
+                Err(e) => {
+                    eprintln!("error: {date}: cannot invert the solar clock: {e}");
+                    return exit::from_error(&e);
+                }
+            }
+        } else {
+            wall
+        };
+        let delta = match clock.solar_time(instant) {
+            Ok(result) => result.delta,
+            Err(e) => {
+                eprintln!("error: {date}: solar clock unavailable: {e}");
+                return exit::from_error(&e);
+            }
+        };
+        let position = match solar_position(instant, clock.coordinates(), true) {
+            Ok(position) => position,
+            Err(e) => {
+                eprintln!("error: {date}: solar position algorithm failed: {e}");
+                return ExitCode::from(exit::INTERNAL);
+            }
+        };
+        rows.push((date, instant.with_timezone(&offset), position, delta));
+    }
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    let written = match output {
+        Some(OutputMode::Json) => {
+            let days = rows.iter().map(|(date, instant, position, delta)| {
+                Object::new()
+                    .string("date", &date.to_string())
+                    .time("instant", Some(*instant))
+                    .number("azimuth", position.azimuth)
+                    .number("elevation", position.elevation)
+                    .number("delta", *delta)
+                    .finish()
+            });
+            let document = Object::new()
+                .raw("coordinates", &output::coordinates_json(clock))
+                .string("time", &time.to_string())
+                .string("clock", if solar { "solar" } else { "civil" })
+                .raw("days", &json::array(days))
+                .finish();
+            writeln!(out, "{document}")
+        }
+        Some(OutputMode::Csv) => {
+            let header =
+                output::csv_record(&["date", "instant", "azimuth", "elevation", "delta_s"]);
+            writeln!(out, "{header}").and_then(|()| {
+                rows.iter()
+                    .try_for_each(|(date, instant, position, delta)| {
+                        let record = output::csv_record(&[
+                            date.to_string(),
+                            instant.to_rfc3339_opts(SecondsFormat::Secs, false),
+                            format!("{:.3}", position.azimuth),
+                            format!("{:.3}", position.elevation),
+                            format!("{delta:.3}"),
+                        ]);
+                        writeln!(out, "{record}")
+                    })
+            })
+        }
+        _ => rows
+            .iter()
+            .try_for_each(|(date, instant, position, delta)| {
+                writeln!(
+                    out,
+                    "{date} {} azimuth {:>6.2}° elevation {:>6.2}° delta {delta:+.1} s",
+                    instant.format("%H:%M:%S"),
+                    position.azimuth,
+                    position.elevation
+                )
+            }),
+    };
+    match written.and_then(|()| out.flush()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(exit::IO),
+    }
+}
@@ -0,0 +1,112 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `--stdin`: batch evaluation of timestamps read from standard input.
+
+use std::io::{self, BufRead, BufWriter, Write};
+use std::process::ExitCode;
+
+use chrono::{SecondsFormat, Utc};
+use solar_clock_rs::SolarClock;
+
+use crate::cli;
+use crate::exit;
+use crate::format::OutputFormat;
+use crate::output::{self, OutputMode};
+
+/// Evaluates one RFC 3339 timestamp per input line, printing the input, the
+/// solar time and the delta in seconds, or only the rendered `format` when
+/// one is given. Failed lines are reported on stderr and skipped; the exit
+/// status is that of the last failure.
+///
+/// With `--output csv`, prints a `civil,solar,delta_s` header and one record
+/// per line instead, with both instants in RFC 3339.
+pub fn run(
+    clock: &SolarClock,
+    format: Option<&OutputFormat>,
+    output: Option<OutputMode>,
+) -> ExitCode {
+    let csv = output == Some(OutputMode::Csv);
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut status = ExitCode::SUCCESS;
+    if csv && writeln!(out, "civil,solar,delta_s").is_err() {
+        return ExitCode::from(exit::IO);
+    }
+
+    for (index, line) in io::stdin().lock().lines().enumerate() {
+        let line_number = index + 1;
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("error: reading standard input: {e}");
+                return ExitCode::from(exit::IO);
+            }
+        };
+        let input = line.trim();
+        if input.is_empty() {
+            continue;
+        }
+
+        let result = cli::parse_timestamp(input.to_owned())
+            .map_err(|e| (exit::USAGE, e.to_string()))
+            .and_then(|dt| {
+                clock
+                    .solar_time(dt.with_timezone(&Utc))
+                    .map(|result| (dt, result))
+                    .map_err(|e| (exit::code(&e), e.to_string()))
+            });
+        match result {
+            Ok((dt, result)) => {
+                let written = match format {
+                    _ if csv => writeln!(
+                        out,
+                        "{}",
+                        output::csv_record(&[
+                            dt.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+                            result
+                                .solar_time
+                                .to_rfc3339_opts(SecondsFormat::AutoSi, false),
+                            format!("{:.3}", result.delta),
+                        ])
+                    ),
+                    Some(format) => writeln!(out, "{}", format.render(&result)),
+                    None => writeln!(
+                        out,
+                        "{} {} {:+.3}",
+                        input,
+                        result.solar_time.to_rfc3339(),
+                        result.delta
+                    ),
+                };
+                if written.is_err() {
+                    // Downstream closed the pipe
+                    return ExitCode::from(exit::IO);
+                }
+            }
+            Err((code, e)) => {
+                status = ExitCode::from(code);
+                eprintln!("line {line_number}: {e}");
+            }
+        }
+    }
+
+    match out.flush() {
+        Ok(()) => status,
+        Err(_) => ExitCode::from(exit::IO),
+    }
+}
@@ -0,0 +1,98 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `cct`: recommended colour temperature on the solar clock.
+
+use std::process::ExitCode;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use solar_clock_rs::{CctCurve, CctSample, SolarClock, color_temperature, sample_cct};
+
+use crate::exit;
+use crate::json::{self, Object};
+use crate::output::OutputMode;
+
+/// Prints the colour temperature at `now`, then one line per sample of the
+/// solar day `date` with the civil and solar times in the solar reference
+/// offset.
+///
+/// With `--json`, prints `{"now": SAMPLE, "curve": [SAMPLE...]}` instead,
+/// each sample an object with `civil`, `solar` and `kelvin`.
+pub fn run(
+    now: DateTime<Utc>,
+    date: NaiveDate,
+    step: Duration,
+    curve: &CctCurve,
+    clock: &SolarClock,
+    output: Option<OutputMode>,
+) -> ExitCode {
+    let sampled = color_temperature(clock, now, curve).and_then(|kelvin| {
+        let solar = clock.solar_time(now)?.solar_time;
+        let current = CctSample {
+            civil: now,
+            solar,
+            kelvin,
+        };
+        Ok((current, sample_cct(clock, date, step, curve)?))
+    });
+    let (current, samples) = match sampled {
+        Ok(sampled) => sampled,
+        Err(e) => {
+            eprintln!("error: solar clock unavailable: {e}");
+            return exit::from_error(&e);
+        }
+    };
+
+    if output == Some(OutputMode::Json) {
+        let document = Object::new()
+            .raw("now", &sample_json(&current, clock))
+            .raw(
+                "curve",
+                &json::array(samples.iter().map(|sample| sample_json(sample, clock))),
+            )
+            .finish();
+        println!("{document}");
+        return ExitCode::SUCCESS;
+    }
+
+    let offset = clock.offset();
+    println!(
+        "now    {}  solar {}  {:.0} K",
+        current.civil.with_timezone(&offset).format("%H:%M"),
+        current.solar.format("%H:%M"),
+        current.kelvin
+    );
+    println!("{date}");
+    for sample in &samples {
+        println!(
+            "  {}  solar {}  {:.0} K",
+            sample.civil.with_timezone(&offset).format("%H:%M"),
+            sample.solar.format("%H:%M"),
+            sample.kelvin
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+fn sample_json(sample: &CctSample, clock: &SolarClock) -> String {
+    Object::new()
+        .time("civil", Some(sample.civil.with_timezone(&clock.offset())))
+        .time("solar", Some(sample.solar))
+        .number("kelvin", sample.kelvin)
+        .finish()
+}
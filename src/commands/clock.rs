@@ -0,0 +1,298 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Default command: evaluate the solar clock at a single instant.
+
+use std::process::ExitCode;
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use solar_clock_rs::solar_clock::get_target_time;
+use solar_clock_rs::spa::{
+    calculate_solar_data_with_zenith, day_length_delta_with_zenith, solar_elevation,
+};
+use solar_clock_rs::{Anchor, AnchorMode, SolarClock};
+
+use crate::commands;
+use crate::exit;
+use crate::format::OutputFormat;
+use crate::output::{self, OutputMode};
+
+/// Characters of each sparkline: one per half hour of the day.
+const SPARKLINE_WIDTH: usize = 48;
+
+pub fn run(
+    dt: DateTime<FixedOffset>,
+    clock: &SolarClock,
+    format: Option<&OutputFormat>,
+    output: Option<OutputMode>,
+    verbose: bool,
+    sparkline: bool,
+) -> ExitCode {
+    if output == Some(OutputMode::Waybar) {
+        return match output::waybar(clock, dt.with_timezone(&Utc)) {
+            Ok(line) => {
+                println!("{line}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("error: solar clock unavailable: {e}");
+                exit::from_error(&e)
+            }
+        };
+    }
+    if output == Some(OutputMode::HomeAssistant) {
+        return match output::home_assistant(clock, dt.with_timezone(&Utc)) {
+            Ok(object) => {
+                println!("{object}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("error: solar clock unavailable: {e}");
+                exit::from_error(&e)
+            }
+        };
+    }
+    if output == Some(OutputMode::Ndjson) {
+        println!("{}", output::ndjson(clock, dt.with_timezone(&Utc), 0));
+        return ExitCode::SUCCESS;
+    }
+    if output == Some(OutputMode::Json) {
+        return match output::json(clock, dt) {
+            Ok(document) => {
+                println!("{document}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("error: solar clock unavailable: {e}");
+                exit::from_error(&e)
+            }
+        };
+    }
+    if let Some(format) = format {
+        return print_formatted(dt, clock, format);
+    }
+
+    let coords = clock.coordinates();
+    println!(
+        "Calculating solar data for coordinates ({}, {}) on date {}",
+        coords.latitude(),
+        coords.longitude(),
+        dt.format("%Y-%m-%d")
+    );
+
+    match calculate_solar_data_with_zenith(
+        dt.with_timezone(&Utc).date_naive(),
+        coords,
+        clock.options().horizon_zenith(),
+    ) {
+        Ok(data) => {
+            match (data.sunrise, data.sunset) {
+                (Some(sunrise), Some(sunset)) => {
+                    println!("Sunrise: {}", sunrise);
+                    println!("Sunset: {}", sunset);
+                }
+                _ => println!("Polar day or night (the sun does not rise or set)"),
+            }
+            println!("Solar noon: {}", data.transit);
+        }
+        Err(e) => {
+            eprintln!("error: solar position algorithm failed: {e}");
+            return ExitCode::from(exit::INTERNAL);
+        }
+    }
+    match day_length_delta_with_zenith(
+        dt.with_timezone(&Utc).date_naive(),
+        coords,
+        clock.options().horizon_zenith(),
+    ) {
+        Ok(Some(change)) => println!("Daylight: {}", day_change(change)),
+        Ok(None) => {}
+        Err(e) => tracing::warn!("day length change unavailable: {e}"),
+    }
+
+    process_solar_clock(dt, clock, verbose, sparkline)
+}
+
+fn process_solar_clock(
+    dt: DateTime<FixedOffset>,
+    clock: &SolarClock,
+    verbose: bool,
+    sparkline: bool,
+) -> ExitCode {
+    match clock.solar_time(dt.with_timezone(&Utc)) {
+        Ok(result) => {
+            println!("Civil time: {}", dt);
+            println!("Solar time: {}", result.solar_time);
+            println!("Delta:      {:+.3} s", result.delta);
+            if result.window_days > clock.options().window_days {
+                println!(
+                    "Window:     ±{} days, widened for missing events",
+                    result.window_days
+                );
+            }
+            if result.mode == AnchorMode::PolarFallback {
+                println!("Mode:       polar fallback (mean solar time near polar season)");
+            }
+            match clock.time_to_next_anchor(result.input) {
+                Ok(Some((anchor, civil, solar))) => println!(
+                    "Next:       {} in {} civil / {} solar",
+                    anchor.name,
+                    commands::format_hm(civil),
+                    commands::format_hm(solar)
+                ),
+                Ok(None) => {}
+                Err(e) => tracing::warn!("next anchor unavailable: {e}"),
+            }
+            match clock.rate_at(result.input) {
+                Ok(rate) => tracing::info!(rate, "solar seconds per civil second"),
+                Err(e) => tracing::warn!("rate unavailable: {e}"),
+            }
+            if sparkline {
+                print_sparklines(result.solar_time.date_naive(), clock);
+            }
+            if verbose {
+                if result.linear_fallback {
+                    println!(
+                        "Interpolation: {} (fallback, {} could not be used)",
+                        result.interpolation.name(),
+                        clock.options().interpolation.name()
+                    );
+                } else {
+                    println!("Interpolation: {}", result.interpolation.name());
+                }
+                print_anchors(&result.anchors);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: solar clock unavailable: {e}");
+            exit::from_error(&e)
+        }
+    }
+}
+
+/// Prints exactly one line on success; errors go to stderr only.
+fn print_formatted(
+    dt: DateTime<FixedOffset>,
+    clock: &SolarClock,
+    format: &OutputFormat,
+) -> ExitCode {
+    match clock.solar_time(dt.with_timezone(&Utc)) {
+        Ok(result) => {
+            println!("{}", format.render(&result));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: solar clock unavailable: {e}");
+            exit::from_error(&e)
+        }
+    }
+}
+
+/// Day length change as `2m31s longer than yesterday`, rounded to the second.
+fn day_change(change: Duration) -> String {
+    let seconds = change.as_seconds_f64();
+    let total = seconds.abs().round() as i64;
+    let amount = if total >= 60 {
+        format!("{}m{:02}s", total / 60, total % 60)
+    } else {
+        format!("{total}s")
+    };
+    match total {
+        0 => "as long as yesterday".to_owned(),
+        _ if seconds > 0.0 => format!("{amount} longer than yesterday"),
+        _ => format!("{amount} shorter than yesterday"),
+    }
+}
+
+/// Prints the delta and the sun elevation over the solar `date`, one
+/// character per half hour from solar midnight.
+fn print_sparklines(date: NaiveDate, clock: &SolarClock) {
+    let start = get_target_time(date, NaiveTime::MIN, clock.offset());
+    let end = start + Duration::minutes(30 * (SPARKLINE_WIDTH as i64 - 1));
+    let deltas: Vec<f64> = match clock.sample_delta(start, end, Duration::minutes(30)) {
+        Ok(samples) => samples.into_iter().map(|(_, delta)| delta).collect(),
+        Err(e) => {
+            tracing::warn!("delta sparkline unavailable: {e}");
+            return;
+        }
+    };
+    let elevations: Result<Vec<f64>, _> = (0..SPARKLINE_WIDTH)
+        .map(|i| {
+            solar_elevation(
+                start + Duration::minutes(30 * i as i64),
+                clock.coordinates(),
+            )
+        })
+        .collect();
+
+    let (line, min, max) = sparkline(&deltas);
+    println!(
+        "Delta today: {line} {} .. {}",
+        commands::format_signed_hms(min),
+        commands::format_signed_hms(max)
+    );
+    match elevations {
+        Ok(elevations) => {
+            let (line, min, max) = sparkline(&elevations);
+            println!("Elevation:   {line} {min:.1}° .. {max:.1}°");
+        }
+        Err(e) => tracing::warn!("elevation sparkline unavailable: {e}"),
+    }
+}
+
+/// One block character per value, scaled between the minimum and the
+/// maximum, which are returned along with it. Every character is one column
+/// wide.
+fn sparkline(values: &[f64]) -> (String, f64, f64) {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let span = max - min;
+    let line = values
+        .iter()
+        .map(|value| {
+            let level = if span > 0.0 {
+                ((value - min) / span * 7.0).round() as usize
+            } else {
+                0
+            };
+            BLOCKS[level.min(7)]
+        })
+        .collect();
+    (line, min, max)
+}
+
+/// Table of the anchors the model was built from.
+fn print_anchors(anchors: &[Anchor]) {
+    println!("Anchors:");
+    println!(
+        "  {:<8} {:<10} {:<20} {:<20} {:>12}",
+        "event", "date", "real (UTC)", "target (UTC)", "delta (s)"
+    );
+    for anchor in anchors {
+        println!(
+            "  {:<8} {:<10} {:<20} {:<20} {:>+12.3}",
+            anchor.name,
+            anchor.date,
+            anchor.real.to_rfc3339_opts(SecondsFormat::Secs, true),
+            anchor.target.to_rfc3339_opts(SecondsFormat::Secs, true),
+            anchor.delta()
+        );
+    }
+}
@@ -0,0 +1,484 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `dbus`: the solar clock as a service on the session bus, for desktop
+//! widgets and scripts.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::{DateTime, DurationRound, SecondsFormat, TimeDelta, Utc};
+use solar_clock_rs::{Anchor, SolarClock, SolarClockError};
+use zbus::blocking::connection;
+use zbus::fdo::{self, Properties, RequestNameFlags};
+use zbus::interface;
+use zbus::names::InterfaceName;
+use zbus::object_server::SignalEmitter;
+use zbus::zvariant::Value;
+
+use crate::config::Settings;
+use crate::exit;
+
+/// Well-known name, object path and interface of the service.
+const NAME: &str = "org.solarclock.Clock1";
+const PATH: &str = "/org/solarclock/Clock1";
+const INTERFACE: &str = NAME;
+const PROPERTIES: &str = "org.freedesktop.DBus.Properties";
+
+/// Longest sleep between checks, so that SIGINT and SIGTERM are honoured
+/// promptly.
+const POLL: Duration = Duration::from_millis(100);
+
+/// Exports the clock as `org.solarclock.Clock1` at `/org/solarclock/Clock1`
+/// until SIGINT or SIGTERM.
+///
+/// The read-only properties `SolarTime` and `NextEventTime` are RFC 3339
+/// instants in the solar offset, `DeltaSeconds` the delta and
+/// `NextEventKind` the name of the next anchor, such as `sunset`; the next
+/// anchor properties are empty strings when no anchor is coming, as in polar
+/// day or night. They are evaluated on every read, and `PropertiesChanged`
+/// is emitted at every civil minute. `AnchorReached(name, civil, solar)` is
+/// emitted at each anchor, and `Recalculate()` rebuilds the model from
+/// scratch.
+///
+/// Fails if another process owns the name.
+pub fn run(settings: &Settings) -> ExitCode {
+    let running = Arc::new(AtomicBool::new(true));
+    let handler = Arc::clone(&running);
+    if let Err(e) = ctrlc::set_handler(move || handler.store(false, Ordering::SeqCst)) {
+        eprintln!("error: cannot install the interrupt handler: {e}");
+        return ExitCode::from(exit::INTERNAL);
+    }
+
+    let connection = match connection::Builder::session()
+        .and_then(|builder| builder.serve_at(PATH, Service::new(settings.clone())))
+        .and_then(connection::Builder::build)
+    {
+        Ok(connection) => connection,
+        Err(e) => {
+            eprintln!("error: cannot connect to the session bus: {e}");
+            return ExitCode::from(exit::IO);
+        }
+    };
+    // Fail instead of queueing behind the current owner
+    match connection.request_name_with_flags(NAME, RequestNameFlags::DoNotQueue.into()) {
+        Ok(_) => {}
+        Err(zbus::Error::NameTaken) => {
+            eprintln!("error: {NAME} is already owned on the session bus");
+            return ExitCode::from(exit::IO);
+        }
+        Err(e) => {
+            eprintln!("error: cannot request {NAME}: {e}");
+            return ExitCode::from(exit::IO);
+        }
+    }
+    tracing::info!(
+        name = NAME,
+        unique_name = ?connection.unique_name(),
+        "exported"
+    );
+
+    let service = match connection.object_server().interface::<_, Service>(PATH) {
+        Ok(service) => service,
+        Err(e) => {
+            eprintln!("error: cannot export {PATH}: {e}");
+            return ExitCode::from(exit::INTERNAL);
+        }
+    };
+    let mut next_minute = next_minute(Utc::now());
+    let mut next_anchor = service.get().next_anchor(Utc::now());
+
+    while running.load(Ordering::SeqCst) {
+        let now = Utc::now();
+        let service = service.get();
+        if service.recalculated.swap(false, Ordering::SeqCst) {
+            next_anchor = service.next_anchor(now);
+        }
+        let mut sent = Ok(());
+        if let Some(anchor) = next_anchor.as_ref().filter(|anchor| anchor.real <= now) {
+            sent = sent.and(service.emit_anchor_reached(&connection, anchor));
+            next_anchor = service.next_anchor(now);
+        }
+        if next_minute <= now {
+            sent = sent.and(service.emit_properties_changed(&connection, now));
+            next_minute = self::next_minute(now);
+        }
+        if let Err(e) = sent {
+            eprintln!("error: lost the session bus: {e}");
+            return ExitCode::from(exit::IO);
+        }
+        drop(service);
+
+        let deadline = next_anchor
+            .as_ref()
+            .map_or(next_minute, |anchor| anchor.real.min(next_minute));
+        std::thread::sleep((deadline - now).to_std().unwrap_or_default().min(POLL));
+    }
+    tracing::info!("shutting down");
+    ExitCode::SUCCESS
+}
+
+/// The exported object. zbus answers on its own thread, hence the lock.
+struct Service {
+    clock: Mutex<SolarClock>,
+    settings: Settings,
+    /// Set by `Recalculate` so that the main loop looks up the next anchor
+    /// in the new model.
+    recalculated: AtomicBool,
+}
+
+/// The property values at one instant.
+struct Snapshot {
+    solar_time: String,
+    delta_seconds: f64,
+    next_event_kind: String,
+    next_event_time: String,
+}
+
+#[interface(name = "org.solarclock.Clock1")]
+impl Service {
+    #[zbus(property)]
+    fn solar_time(&self) -> fdo::Result<String> {
+        Ok(self.snapshot(Utc::now())?.solar_time)
+    }
+
+    #[zbus(property)]
+    fn delta_seconds(&self) -> fdo::Result<f64> {
+        Ok(self.snapshot(Utc::now())?.delta_seconds)
+    }
+
+    #[zbus(property)]
+    fn next_event_kind(&self) -> fdo::Result<String> {
+        Ok(self.snapshot(Utc::now())?.next_event_kind)
+    }
+
+    #[zbus(property)]
+    fn next_event_time(&self) -> fdo::Result<String> {
+        Ok(self.snapshot(Utc::now())?.next_event_time)
+    }
+
+    async fn recalculate(&self, #[zbus(signal_emitter)] emitter: SignalEmitter<'_>) {
+        *self.clock() = self.settings.clock();
+        self.recalculated.store(true, Ordering::SeqCst);
+        if let Some(changed) = self.changed(Utc::now())
+            && let Err(e) = Properties::properties_changed(
+                &emitter,
+                InterfaceName::from_static_str_unchecked(INTERFACE),
+                changed,
+                Cow::Borrowed(&[]),
+            )
+            .await
+        {
+            tracing::warn!("cannot emit PropertiesChanged: {e}");
+        }
+    }
+
+    #[zbus(signal)]
+    async fn anchor_reached(
+        emitter: &SignalEmitter<'_>,
+        name: &str,
+        civil: &str,
+        solar: &str,
+    ) -> zbus::Result<()>;
+}
+
+impl Service {
+    fn new(settings: Settings) -> Self {
+        Self {
+            clock: Mutex::new(settings.clock()),
+            settings,
+            recalculated: AtomicBool::new(false),
+        }
+    }
+
+    fn clock(&self) -> MutexGuard<'_, SolarClock> {
+        self.clock
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// The properties at `now`, or why the clock cannot be evaluated.
+    fn snapshot(&self, now: DateTime<Utc>) -> fdo::Result<Snapshot> {
+        let clock = self.clock();
+        let unavailable = |e: SolarClockError| fdo::Error::Failed(e.to_string());
+        let result = clock.solar_time(now).map_err(unavailable)?;
+        let next = clock.time_to_next_anchor(now).map_err(unavailable)?;
+        let (next_event_kind, next_event_time) = next.map_or_else(
+            || (String::new(), String::new()),
+            |(anchor, _, _)| (anchor.name, local(&clock, anchor.real)),
+        );
+        Ok(Snapshot {
+            solar_time: result
+                .solar_time
+                .to_rfc3339_opts(SecondsFormat::Secs, false),
+            delta_seconds: result.delta,
+            next_event_kind,
+            next_event_time,
+        })
+    }
+
+    /// All four properties at `now` for `PropertiesChanged`, or `None` when
+    /// the clock cannot be evaluated.
+    fn changed(&self, now: DateTime<Utc>) -> Option<HashMap<&'static str, Value<'static>>> {
+        match self.snapshot(now) {
+            Ok(snapshot) => Some(HashMap::from([
+                ("SolarTime", Value::from(snapshot.solar_time)),
+                ("DeltaSeconds", Value::from(snapshot.delta_seconds)),
+                ("NextEventKind", Value::from(snapshot.next_event_kind)),
+                ("NextEventTime", Value::from(snapshot.next_event_time)),
+            ])),
+            Err(e) => {
+                tracing::warn!("solar clock unavailable: {e}");
+                None
+            }
+        }
+    }
+
+    fn next_anchor(&self, now: DateTime<Utc>) -> Option<Anchor> {
+        match self.clock().time_to_next_anchor(now) {
+            Ok(next) => next.map(|(anchor, _, _)| anchor),
+            Err(e) => {
+                tracing::warn!("cannot find the next anchor: {e}");
+                None
+            }
+        }
+    }
+
+    fn emit_properties_changed(
+        &self,
+        connection: &zbus::blocking::Connection,
+        now: DateTime<Utc>,
+    ) -> zbus::Result<()> {
+        let Some(changed) = self.changed(now) else {
+            return Ok(());
+        };
+        connection.emit_signal(
+            None::<()>,
+            PATH,
+            PROPERTIES,
+            "PropertiesChanged",
+            &(INTERFACE, changed, Vec::<&str>::new()),
+        )
+    }
+
+    fn emit_anchor_reached(
+        &self,
+        connection: &zbus::blocking::Connection,
+        anchor: &Anchor,
+    ) -> zbus::Result<()> {
+        tracing::info!(name = anchor.name, "anchor reached");
+        let clock = self.clock();
+        connection.emit_signal(
+            None::<()>,
+            PATH,
+            INTERFACE,
+            "AnchorReached",
+            &(
+                anchor.name.as_str(),
+                local(&clock, anchor.real),
+                local(&clock, anchor.target),
+            ),
+        )
+    }
+}
+
+fn local(clock: &SolarClock, instant: DateTime<Utc>) -> String {
+    instant
+        .with_timezone(&clock.offset())
+        .to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+/// The first whole civil minute after `now`.
+fn next_minute(now: DateTime<Utc>) -> DateTime<Utc> {
+    let minute = TimeDelta::minutes(1);
+    now.duration_trunc(minute)
+        .map_or(now, |start| start + minute)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::net::UnixStream;
+
+    use chrono::{SubsecRound, TimeZone};
+    use zbus::Message;
+    use zbus::blocking::fdo::PropertiesProxy;
+    use zbus::blocking::{Connection, MessageIterator};
+    use zbus::message::Type;
+    use zbus::zvariant::OwnedValue;
+
+    use super::*;
+    use crate::cli::Args;
+    use crate::config::{FileConfig, resolve};
+
+    fn settings() -> Settings {
+        resolve(
+            &Args::default(),
+            &FileConfig::default(),
+            &FileConfig::default(),
+        )
+        .unwrap()
+    }
+
+    /// A service and a client talking peer to peer over a socket pair.
+    fn peers() -> (Connection, Connection) {
+        let (server, client) = UnixStream::pair().unwrap();
+        // Both ends must be handshaking at once
+        let server = std::thread::spawn(move || {
+            connection::Builder::async_io_unix_stream(server)
+                .server(zbus::Guid::generate())
+                .unwrap()
+                .p2p()
+                .serve_at(PATH, Service::new(settings()))
+                .unwrap()
+                .build()
+                .unwrap()
+        });
+        let client = connection::Builder::async_io_unix_stream(client)
+            .p2p()
+            .build()
+            .unwrap();
+        (server.join().unwrap(), client)
+    }
+
+    /// The next signal called `member`.
+    fn next_signal(messages: &mut MessageIterator, member: &str) -> Message {
+        messages
+            .map(Result::unwrap)
+            .find(|message| {
+                let header = message.header();
+                header.message_type() == Type::Signal
+                    && header.member().is_some_and(|name| name == member)
+            })
+            .unwrap()
+    }
+
+    type PropertiesChanged = (String, HashMap<String, OwnedValue>, Vec<String>);
+
+    #[test]
+    fn properties_are_read_over_the_bus() {
+        let (_server, client) = peers();
+        let properties = PropertiesProxy::builder(&client)
+            .destination(NAME)
+            .unwrap()
+            .path(PATH)
+            .unwrap()
+            .build()
+            .unwrap();
+        let interface = InterfaceName::from_static_str_unchecked(INTERFACE);
+        let clock = settings().clock();
+
+        let before = Utc::now();
+        let all = properties.get_all(interface.clone()).unwrap();
+        let after = Utc::now();
+        assert_eq!(all.len(), 4);
+
+        // SolarTime is the solar time of an instant during the call
+        let solar_time: String = all["SolarTime"].clone().try_into().unwrap();
+        let solar_time = DateTime::parse_from_rfc3339(&solar_time).unwrap();
+        let earliest = clock.solar_time(before).unwrap().solar_time;
+        let latest = clock.solar_time(after).unwrap().solar_time;
+        assert!(earliest.trunc_subsecs(0) <= solar_time && solar_time <= latest);
+        let delta: f64 = all["DeltaSeconds"].clone().try_into().unwrap();
+        assert!((delta - clock.solar_time(before).unwrap().delta).abs() < 1.0);
+
+        // The next anchor as the library reports it
+        let (anchor, _, _) = clock.time_to_next_anchor(after).unwrap().unwrap();
+        let kind: String = properties
+            .get(interface.clone(), "NextEventKind")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let time: String = properties
+            .get(interface.clone(), "NextEventTime")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(kind, anchor.name);
+        assert_eq!(time, local(&clock, anchor.real));
+
+        // The properties are read-only
+        let set = properties.set(interface, "DeltaSeconds", Value::from(0.0));
+        assert!(set.is_err());
+    }
+
+    #[test]
+    fn updates_are_signalled() {
+        let (server, client) = peers();
+        let mut messages = MessageIterator::from(&client);
+
+        // Recalculate answers and announces all the properties
+        client
+            .call_method(None::<()>, PATH, Some(INTERFACE), "Recalculate", &())
+            .unwrap();
+        let (interface, changed, invalidated): PropertiesChanged =
+            next_signal(&mut messages, "PropertiesChanged")
+                .body()
+                .deserialize()
+                .unwrap();
+        assert_eq!(interface, INTERFACE);
+        let mut names: Vec<_> = changed.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [
+                "DeltaSeconds",
+                "NextEventKind",
+                "NextEventTime",
+                "SolarTime"
+            ]
+        );
+        assert!(invalidated.is_empty());
+
+        let service = server
+            .object_server()
+            .interface::<_, Service>(PATH)
+            .unwrap();
+        let service = service.get();
+        assert!(service.recalculated.load(Ordering::SeqCst));
+
+        // The minute tick carries the values at that instant
+        let now = Utc.with_ymd_and_hms(2026, 2, 3, 12, 0, 0).unwrap();
+        service.emit_properties_changed(&server, now).unwrap();
+        let (_, changed, _): PropertiesChanged = next_signal(&mut messages, "PropertiesChanged")
+            .body()
+            .deserialize()
+            .unwrap();
+        let snapshot = service.snapshot(now).unwrap();
+        let solar_time: String = changed["SolarTime"].clone().try_into().unwrap();
+        let delta: f64 = changed["DeltaSeconds"].clone().try_into().unwrap();
+        assert_eq!(solar_time, snapshot.solar_time);
+        assert_eq!(delta, snapshot.delta_seconds);
+
+        // And each anchor is announced with both of its times
+        let anchor = service.next_anchor(now).unwrap();
+        service.emit_anchor_reached(&server, &anchor).unwrap();
+        let (name, civil, solar): (String, String, String) =
+            next_signal(&mut messages, "AnchorReached")
+                .body()
+                .deserialize()
+                .unwrap();
+        let clock = settings().clock();
+        assert_eq!(name, anchor.name);
+        assert_eq!(civil, local(&clock, anchor.real));
+        assert_eq!(solar, local(&clock, anchor.target));
+    }
+}
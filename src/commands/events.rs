@@ -0,0 +1,215 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `events`: the astronomical events the clock is anchored to on a date.
+
+use std::process::ExitCode;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, SecondsFormat, Utc};
+use solar_clock_rs::SolarClock;
+use solar_clock_rs::spa::{
+    Interval, PhotoHours, SolarData, SpaError, TwilightData, calculate_photo_hours,
+    calculate_solar_data_with_zenith, calculate_twilight_data, equation_of_time,
+};
+
+use crate::commands::format_signed_hms;
+use crate::exit;
+use crate::json::{self, Object};
+use crate::output::{self, OutputMode};
+
+/// Prints one line per event: its name, the UTC instant and the same instant
+/// in the solar reference offset, or `none` when the event does not occur.
+/// With `photo`, also the start and end of the golden and blue hours in the
+/// solar reference offset.
+///
+/// With `--json`, prints one JSON document instead, see [`events_json`].
+pub fn run(
+    date: NaiveDate,
+    photo: bool,
+    clock: &SolarClock,
+    output: Option<OutputMode>,
+) -> ExitCode {
+    let (data, twilight, hours, eot) = match compute(date, photo, clock) {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("error: solar position algorithm failed: {e}");
+            return ExitCode::from(exit::INTERNAL);
+        }
+    };
+    if output == Some(OutputMode::Json) {
+        println!(
+            "{}",
+            events_json(date, &data, &twilight, hours.as_ref(), eot, clock)
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    let offset = clock.offset();
+    println!("{:<17} {date}", "date");
+    print_event("astronomical dawn", twilight.astronomical_dawn, offset);
+    print_event("nautical dawn", twilight.nautical_dawn, offset);
+    print_event("civil dawn", twilight.civil_dawn, offset);
+    print_event("sunrise", data.sunrise, offset);
+    print_azimuth(data.sunrise_azimuth);
+    print_event("transit", Some(data.transit), offset);
+    println!("{:<17} {:.1}°", "  elevation", data.transit_elevation);
+    print_event("sunset", data.sunset, offset);
+    print_azimuth(data.sunset_azimuth);
+    print_event("civil dusk", twilight.civil_dusk, offset);
+    print_event("nautical dusk", twilight.nautical_dusk, offset);
+    print_event("astronomical dusk", twilight.astronomical_dusk, offset);
+    println!(
+        "{:<17} {}",
+        "equation of time",
+        format_signed_hms(eot * 60.0)
+    );
+    if let Some(hours) = hours {
+        print_interval("morning blue", hours.morning_blue, offset);
+        print_interval("morning golden", hours.morning_golden, offset);
+        print_interval("evening golden", hours.evening_golden, offset);
+        print_interval("evening blue", hours.evening_blue, offset);
+    }
+    if data.sunrise.is_none() || data.sunset.is_none() {
+        println!(
+            "{:<17} the sun does not rise or set on this date (polar day or night)",
+            "note"
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+/// The `--json` document of `events` for `date`, see [`events_json`].
+pub fn json(date: NaiveDate, photo: bool, clock: &SolarClock) -> Result<String, SpaError> {
+    let (data, twilight, hours, eot) = compute(date, photo, clock)?;
+    Ok(events_json(
+        date,
+        &data,
+        &twilight,
+        hours.as_ref(),
+        eot,
+        clock,
+    ))
+}
+
+/// The events, twilights, with `photo` the golden and blue hours, and the
+/// equation of time of `date`.
+fn compute(
+    date: NaiveDate,
+    photo: bool,
+    clock: &SolarClock,
+) -> Result<(SolarData, TwilightData, Option<PhotoHours>, f64), SpaError> {
+    let data = calculate_solar_data_with_zenith(
+        date,
+        clock.coordinates(),
+        clock.options().horizon_zenith(),
+    )?;
+    let twilight = calculate_twilight_data(date, clock.coordinates())?;
+    let hours = photo
+        .then(|| calculate_photo_hours(date, clock.coordinates()))
+        .transpose()?;
+    Ok((data, twilight, hours, equation_of_time(date)?))
+}
+
+/// JSON document for the date: `date`, `coordinates`, the fields of
+/// [`SolarData`] and `equation_of_time` in minutes, with a `twilight` object
+/// holding the fields of [`TwilightData`] and, with `--photo`, a `photo`
+/// object holding those of [`PhotoHours`], each a `[start, end]` pair or
+/// `null`. Instants are in the solar reference offset, `null` when the event
+/// does not occur.
+fn events_json(
+    date: NaiveDate,
+    data: &SolarData,
+    twilight: &TwilightData,
+    hours: Option<&PhotoHours>,
+    eot: f64,
+    clock: &SolarClock,
+) -> String {
+    let offset = clock.offset();
+    let local = |event: Option<DateTime<Utc>>| event.map(|t| t.with_timezone(&offset));
+    let interval = |interval: Option<Interval>| match interval {
+        Some((start, end)) => json::array([
+            json::time(&start.with_timezone(&offset)),
+            json::time(&end.with_timezone(&offset)),
+        ]),
+        None => "null".to_owned(),
+    };
+
+    let twilight = Object::new()
+        .time("civil_dawn", local(twilight.civil_dawn))
+        .time("civil_dusk", local(twilight.civil_dusk))
+        .time("nautical_dawn", local(twilight.nautical_dawn))
+        .time("nautical_dusk", local(twilight.nautical_dusk))
+        .time("astronomical_dawn", local(twilight.astronomical_dawn))
+        .time("astronomical_dusk", local(twilight.astronomical_dusk))
+        .finish();
+    let mut document = Object::new()
+        .string("date", &date.to_string())
+        .raw("coordinates", &output::coordinates_json(clock))
+        .time("sunrise", local(data.sunrise))
+        .time("transit", local(Some(data.transit)))
+        .number("transit_elevation", data.transit_elevation)
+        .time("sunset", local(data.sunset))
+        .optional_number("sunrise_azimuth", data.sunrise_azimuth)
+        .optional_number("sunset_azimuth", data.sunset_azimuth)
+        .time("midnight", local(Some(data.midnight)))
+        .number("equation_of_time", eot)
+        .raw("twilight", &twilight);
+    if let Some(hours) = hours {
+        let photo = Object::new()
+            .raw("morning_blue", &interval(hours.morning_blue))
+            .raw("morning_golden", &interval(hours.morning_golden))
+            .raw("evening_golden", &interval(hours.evening_golden))
+            .raw("evening_blue", &interval(hours.evening_blue))
+            .finish();
+        document = document.raw("photo", &photo);
+    }
+    document.finish()
+}
+
+fn print_interval(name: &str, interval: Option<Interval>, offset: FixedOffset) {
+    match interval {
+        Some((start, end)) => println!(
+            "{name:<17} {} {}",
+            start
+                .with_timezone(&offset)
+                .to_rfc3339_opts(SecondsFormat::Secs, false),
+            end.with_timezone(&offset)
+                .to_rfc3339_opts(SecondsFormat::Secs, false)
+        ),
+        None => println!("{name:<17} none"),
+    }
+}
+
+/// Prints the azimuth of the event above, if it occurs.
+fn print_azimuth(azimuth: Option<f64>) {
+    if let Some(azimuth) = azimuth {
+        println!("{:<17} {azimuth:.1}°", "  azimuth");
+    }
+}
+
+fn print_event(name: &str, event: Option<DateTime<Utc>>, offset: FixedOffset) {
+    match event {
+        Some(t) => println!(
+            "{name:<17} {} {}",
+            t.to_rfc3339_opts(SecondsFormat::Secs, true),
+            t.with_timezone(&offset)
+                .to_rfc3339_opts(SecondsFormat::Secs, false)
+        ),
+        None => println!("{name:<17} none"),
+    }
+}
@@ -0,0 +1,138 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `export-c`: the anchors of a year as a C header, for clocks that cannot
+//! run the model themselves.
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use chrono::{NaiveDate, NaiveTime};
+use solar_clock_rs::SolarClock;
+use solar_clock_rs::solar_clock::get_target_time;
+
+use crate::exit;
+
+/// Prints a C header with one row per day of `year` and one column per
+/// configured anchor: the instant of the event in seconds from 00:00 of
+/// that day in the solar reference offset, and its delta in whole seconds.
+///
+/// The anchors are those of the model the solar clock evaluates for that
+/// date, so both agree at every anchor to within the rounding of the delta.
+/// Events that do not occur are marked with `SOLAR_NO_EVENT`.
+pub fn run(year: i32, clock: &SolarClock) -> ExitCode {
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        eprintln!("error: year {year} is out of range");
+        return ExitCode::from(exit::USAGE);
+    };
+    let specs = &clock.options().anchors;
+    let offset = clock.offset();
+    let days = first.iter_days().take_while(|date| *date <= last).count();
+
+    let mut header = String::new();
+    let coords = clock.coordinates();
+    let names: Vec<&str> = specs.iter().map(|spec| spec.name.as_str()).collect();
+    let _ = write!(
+        header,
+        r#"/*
+ * Solar clock anchors for {year} at ({lat}, {lon}), generated by
+ * solar-clock-rs. Offset UTC{offset}; columns: {columns}.
+ *
+ * SOLAR_ANCHORS[d][a] is anchor a on day d (0 = January 1): `real` is the
+ * instant of the event in seconds from 00:00 of that day at UTC{offset},
+ * negative when it falls on the day before, and `delta` the seconds added
+ * to the civil time at that instant to get the solar time.
+ *
+ * Between two consecutive anchors, interpolate linearly:
+ *
+ *     // t: seconds from 00:00 of day d at UTC{offset}, a0 <= t < a1
+ *     int32_t span = a1.real - a0.real;
+ *     int32_t delta = a0.delta + (int32_t)((int64_t)(a1.delta - a0.delta)
+ *                                          * (t - a0.real) / span);
+ *     int32_t solar = t + delta;  // seconds from 00:00, may leave [0, 86400)
+ *
+ * taking a0 and a1 from the neighbouring days around midnight and skipping
+ * SOLAR_NO_EVENT. The Rust model interpolates with a monotone cubic, so
+ * between anchors the two differ by a few seconds.
+ */
+
+#ifndef SOLAR_CLOCK_ANCHORS_H
+#define SOLAR_CLOCK_ANCHORS_H
+
+#include <stdint.h>
+
+#define SOLAR_YEAR {year}
+#define SOLAR_UTC_OFFSET {utc_offset}
+#define SOLAR_DAYS {days}
+#define SOLAR_ANCHORS_PER_DAY {count}
+#define SOLAR_NO_EVENT INT32_MIN
+
+typedef struct {{
+    int32_t real;
+    int16_t delta;
+}} solar_anchor_t;
+
+static const solar_anchor_t SOLAR_ANCHORS[SOLAR_DAYS][SOLAR_ANCHORS_PER_DAY] = {{
+"#,
+        lat = coords.latitude(),
+        lon = coords.longitude(),
+        columns = names.join(", "),
+        utc_offset = offset.local_minus_utc(),
+        count = specs.len(),
+    );
+
+    for date in first.iter_days().take_while(|date| *date <= last) {
+        let anchors = match clock.model_anchors(date) {
+            Ok(anchors) => anchors,
+            Err(e) => {
+                eprintln!("error: {date}: solar clock unavailable: {e}");
+                return exit::from_error(&e);
+            }
+        };
+        let midnight = get_target_time(date, NaiveTime::MIN, offset);
+        let cells: Vec<String> = specs
+            .iter()
+            .map(|spec| {
+                anchors
+                    .iter()
+                    .find(|anchor| anchor.date == date && anchor.name == spec.name)
+                    .map_or_else(
+                        || "{SOLAR_NO_EVENT, 0}".to_owned(),
+                        |anchor| {
+                            format!(
+                                "{{{}, {}}}",
+                                (anchor.real - midnight).num_seconds(),
+                                anchor.delta().round() as i16
+                            )
+                        },
+                    )
+            })
+            .collect();
+        let _ = writeln!(header, "    {{{}}}, /* {date} */", cells.join(", "));
+    }
+    header.push_str("};\n\n#endif /* SOLAR_CLOCK_ANCHORS_H */\n");
+
+    match io::stdout().lock().write_all(header.as_bytes()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(exit::IO),
+    }
+}
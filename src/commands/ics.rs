@@ -0,0 +1,161 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `ics` and `alarms-ics`: the daily solar events, or fixed solar wall
+//! times, as an iCalendar file.
+
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use chrono::{NaiveDate, NaiveTime, SecondsFormat, SubsecRound, Timelike};
+use solar_clock_rs::solar_clock::get_target_time;
+use solar_clock_rs::spa::{calculate_solar_data_with_zenith, elevation_crossing};
+use solar_clock_rs::{AnchorEvent, SolarClock};
+
+use crate::commands::format_signed_hms;
+use crate::exit;
+use crate::ics::Calendar;
+use crate::output::capitalize;
+
+/// Prints a `VCALENDAR` with one `VEVENT` per configured anchor event and
+/// day from `from` to `to`, both inclusive, such as "Sunset (solar 20:00)".
+///
+/// Events that do not occur, like sunrise and sunset in polar day or
+/// night, are left out. The UID is derived from the date, the anchor name
+/// and the coordinates, so a regenerated export updates the same entries.
+pub fn run(from: NaiveDate, to: NaiveDate, clock: &SolarClock) -> ExitCode {
+    let coords = clock.coordinates();
+    let options = clock.options();
+    let offset = clock.offset();
+    let mut calendar = Calendar::new("Solar clock events");
+
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        let data = match calculate_solar_data_with_zenith(date, coords, options.horizon_zenith()) {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("error: {date}: solar position algorithm failed: {e}");
+                return ExitCode::from(exit::INTERNAL);
+            }
+        };
+        for spec in &options.anchors {
+            let event = match spec.event {
+                AnchorEvent::Midnight => Some(data.midnight),
+                AnchorEvent::Sunrise => data.sunrise,
+                AnchorEvent::Transit => Some(data.transit),
+                AnchorEvent::Sunset => data.sunset,
+                AnchorEvent::Elevation { degrees, rising } => {
+                    match elevation_crossing(data.transit, coords, degrees, rising) {
+                        Ok(event) => event,
+                        Err(e) => {
+                            eprintln!("error: {date}: solar position algorithm failed: {e}");
+                            return ExitCode::from(exit::INTERNAL);
+                        }
+                    }
+                }
+            };
+            let Some(real) = event else {
+                continue;
+            };
+
+            let target = get_target_time(date, spec.target, offset);
+            let uid = format!(
+                "{}-{}-{:.4}-{:.4}@solar-clock-rs",
+                date.format("%Y%m%d"),
+                spec.name,
+                coords.latitude(),
+                coords.longitude()
+            );
+            let summary = format!(
+                "{} (solar {})",
+                capitalize(&spec.name),
+                wall_time(spec.target)
+            );
+            let description = format!(
+                "The solar clock reads {} (UTC{offset}) here. Delta {}.",
+                wall_time(spec.target),
+                format_signed_hms((target - real).as_seconds_f64())
+            );
+            calendar.event(&uid, real, &summary, &description);
+        }
+    }
+
+    match io::stdout().lock().write_all(calendar.finish().as_bytes()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(exit::IO),
+    }
+}
+
+/// Prints a `VCALENDAR` with one `VEVENT` per solar wall time in `times` and
+/// day from `from` to `to`, both inclusive, starting at the civil instant the
+/// solar clock reads that time.
+///
+/// The civil instant drifts from day to day with the delta, which a
+/// recurrence rule cannot express, so every occurrence is written out. Days
+/// on which the clock cannot be inverted are left out with a warning.
+pub fn alarms(times: &[NaiveTime], from: NaiveDate, to: NaiveDate, clock: &SolarClock) -> ExitCode {
+    let coords = clock.coordinates();
+    let offset = clock.offset();
+    let mut calendar = Calendar::new("Solar clock alarms");
+
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        for &time in times {
+            let solar = get_target_time(date, time, offset).with_timezone(&offset);
+            let civil = match clock.solar_to_civil(solar) {
+                Ok(civil) => civil,
+                Err(e) => {
+                    tracing::warn!(%solar, "left out: {e}");
+                    continue;
+                }
+            };
+            // The root is only accurate to a microsecond, so 06:59:59.999999
+            // is 07:00
+            let civil = civil.round_subsecs(0);
+
+            let uid = format!(
+                "{}-solar-{}-{:.4}-{:.4}@solar-clock-rs",
+                date.format("%Y%m%d"),
+                time.format("%H%M%S"),
+                coords.latitude(),
+                coords.longitude()
+            );
+            let summary = format!("Solar {}", wall_time(time));
+            let description = format!(
+                "The solar clock reads {} (UTC{offset}) on {date}, at {} civil.",
+                wall_time(time),
+                civil
+                    .with_timezone(&offset)
+                    .to_rfc3339_opts(SecondsFormat::Secs, false)
+            );
+            calendar.event(&uid, civil, &summary, &description);
+        }
+    }
+
+    match io::stdout().lock().write_all(calendar.finish().as_bytes()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(exit::IO),
+    }
+}
+
+/// `20:00`, or `20:00:30` when the target has seconds.
+fn wall_time(time: NaiveTime) -> String {
+    if time.second() == 0 {
+        time.format("%H:%M").to_string()
+    } else {
+        time.format("%H:%M:%S").to_string()
+    }
+}
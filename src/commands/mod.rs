@@ -0,0 +1,78 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Subcommands of the `solar-clock-rs` binary.
+
+pub mod analemma;
+pub mod batch;
+pub mod cct;
+pub mod clock;
+#[cfg(feature = "dbus")]
+pub mod dbus;
+pub mod events;
+pub mod export_c;
+pub mod ics;
+pub mod next;
+pub mod night_light;
+pub mod plot;
+pub mod publish;
+pub mod range;
+pub mod repl;
+pub mod run;
+pub mod save_model;
+pub mod seasons;
+pub mod serve;
+pub mod sleep_until;
+#[cfg(unix)]
+pub mod socket;
+pub mod tzif;
+pub mod validate;
+pub mod watch;
+
+use std::thread;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Longest single sleep. Sleeping in slices and comparing against the system
+/// clock keeps long waits accurate when the clock is adjusted.
+const RECHECK: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Blocks until the system clock reaches `instant`.
+pub fn sleep_until(instant: DateTime<Utc>) {
+    while let Ok(remaining) = (instant - Utc::now()).to_std() {
+        thread::sleep(remaining.min(RECHECK));
+    }
+}
+
+/// Formats a duration as `14h43m`, rounded to the minute.
+pub fn format_hm(duration: Duration) -> String {
+    let minutes = (duration.as_seconds_f64() / 60.0).round() as i64;
+    format!("{}h{:02}m", minutes / 60, minutes % 60)
+}
+
+/// Formats signed seconds as `+44m12s` or `-1h02m03s`, rounded to the second.
+pub fn format_signed_hms(seconds: f64) -> String {
+    let sign = if seconds < 0.0 { '-' } else { '+' };
+    let total = seconds.abs().round() as i64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    if h > 0 {
+        format!("{sign}{h}h{m:02}m{s:02}s")
+    } else {
+        format!("{sign}{m}m{s:02}s")
+    }
+}
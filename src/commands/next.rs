@@ -0,0 +1,101 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `next`: when the solar clock will next read a given wall time.
+
+use std::process::ExitCode;
+
+use chrono::{DateTime, FixedOffset, Local, NaiveTime, SecondsFormat, SubsecRound, Utc};
+use solar_clock_rs::{SolarClock, SolarClockError};
+
+use crate::exit;
+use crate::json::Object;
+use crate::output::OutputMode;
+
+/// Prints the solar wall time being waited for and the civil instant at
+/// which it is reached, both in the solar offset and in local time.
+///
+/// With `--json`, prints `{"after": ..., "solar_time": ..., "civil": ...,
+/// "local": ...}` with the same instants, to the second.
+pub fn run(
+    time: NaiveTime,
+    after: DateTime<FixedOffset>,
+    clock: &SolarClock,
+    output: Option<OutputMode>,
+) -> ExitCode {
+    let (civil, solar) = match solve(time, after, clock) {
+        Ok(solution) => solution,
+        Err(e) => {
+            eprintln!("error: cannot invert the solar clock: {e}");
+            return exit::from_error(&e);
+        }
+    };
+
+    if output == Some(OutputMode::Json) {
+        println!("{}", json(after, civil, solar, clock));
+        return ExitCode::SUCCESS;
+    }
+
+    println!(
+        "solar   {}",
+        solar.to_rfc3339_opts(SecondsFormat::Secs, false)
+    );
+    println!(
+        "civil   {}",
+        civil
+            .with_timezone(&clock.offset())
+            .to_rfc3339_opts(SecondsFormat::Secs, false)
+    );
+    println!(
+        "local   {}",
+        civil
+            .with_timezone(&Local)
+            .to_rfc3339_opts(SecondsFormat::Secs, false)
+    );
+    ExitCode::SUCCESS
+}
+
+/// The first civil instant after `after` at which the solar clock reads
+/// `time`, and the solar time there.
+pub fn solve(
+    time: NaiveTime,
+    after: DateTime<FixedOffset>,
+    clock: &SolarClock,
+) -> Result<(DateTime<Utc>, DateTime<FixedOffset>), SolarClockError> {
+    let civil = clock.next_civil_time(after.with_timezone(&Utc), time)?;
+    // The root is only accurate to a microsecond, so 19:59:59.999999 is 20:00
+    let solar = clock.solar_time(civil)?.solar_time.round_subsecs(0);
+    Ok((civil, solar))
+}
+
+/// The `--json` document of `next`: `{"after": ..., "solar_time": ...,
+/// "civil": ..., "local": ...}`, to the second.
+pub fn json(
+    after: DateTime<FixedOffset>,
+    civil: DateTime<Utc>,
+    solar: DateTime<FixedOffset>,
+    clock: &SolarClock,
+) -> String {
+    let civil = civil.round_subsecs(0);
+    Object::new()
+        .time("after", Some(after))
+        .time("solar_time", Some(solar))
+        .time("civil", Some(civil.with_timezone(&clock.offset())))
+        .time("local", Some(civil.with_timezone(&Local).fixed_offset()))
+        .finish()
+}
@@ -0,0 +1,168 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Day and night transition times for screen colour temperature tools:
+//! `--output redshift` and `night-light`.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, ExitCode};
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, Utc};
+use solar_clock_rs::SolarClock;
+use solar_clock_rs::solar_clock::get_target_time;
+use solar_clock_rs::spa::{SpaError, calculate_solar_data_with_zenith, calculate_twilight_data};
+
+use crate::cli::NightLightTool;
+use crate::exit;
+
+/// Solar wall times of the day and night transitions by default.
+pub const DEFAULT_DAWN: NaiveTime = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
+pub const DEFAULT_DUSK: NaiveTime = NaiveTime::from_hms_opt(20, 0, 0).unwrap();
+
+/// Prints the transitions of the solar date `date` as `dawn-time=HH:MM` and
+/// `dusk-time=HH:MM` lines in local time, ready for the `[general]` section
+/// of a gammastep or redshift configuration file. See [`transitions`].
+pub fn print(date: NaiveDate, dawn: NaiveTime, dusk: NaiveTime, clock: &SolarClock) -> ExitCode {
+    match transitions(date, dawn, dusk, clock) {
+        Ok((dawn, dusk)) => {
+            println!("dawn-time={}", wall_time(dawn));
+            println!("dusk-time={}", wall_time(dusk));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: solar position algorithm failed: {e}");
+            ExitCode::from(exit::INTERNAL)
+        }
+    }
+}
+
+/// Runs `tool` with the transitions of the solar date `date`, followed by
+/// `args`. On Unix the process is replaced by the tool.
+///
+/// wlsunset gets them as `-S` and `-s`. gammastep and redshift only read
+/// them from a configuration file, so one is written to
+/// `$XDG_RUNTIME_DIR/solar-clock-TOOL.conf` and passed with `-c`, which
+/// replaces the user's file; other settings go in `args`. The times are
+/// those of one day, so the tool should be restarted daily, e.g. from a
+/// timer.
+pub fn run(
+    tool: NightLightTool,
+    args: &[String],
+    date: NaiveDate,
+    times: (NaiveTime, NaiveTime),
+    clock: &SolarClock,
+) -> ExitCode {
+    let (dawn, dusk) = match transitions(date, times.0, times.1, clock) {
+        Ok(transitions) => transitions,
+        Err(e) => {
+            eprintln!("error: solar position algorithm failed: {e}");
+            return ExitCode::from(exit::INTERNAL);
+        }
+    };
+
+    let mut command = Command::new(tool.name());
+    match tool {
+        NightLightTool::Wlsunset => {
+            command.args(["-S", &wall_time(dawn), "-s", &wall_time(dusk)]);
+        }
+        NightLightTool::Gammastep | NightLightTool::Redshift => {
+            let path = std::env::var_os("XDG_RUNTIME_DIR")
+                .map_or_else(std::env::temp_dir, PathBuf::from)
+                .join(format!("solar-clock-{}.conf", tool.name()));
+            let config = format!(
+                "[general]\ndawn-time={}\ndusk-time={}\n",
+                wall_time(dawn),
+                wall_time(dusk)
+            );
+            if let Err(e) = fs::write(&path, config) {
+                eprintln!("error: cannot write {}: {e}", path.display());
+                return ExitCode::from(exit::IO);
+            }
+            command.arg("-c").arg(path);
+        }
+    }
+    command.args(args);
+    tracing::info!(command = ?command, "starting");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let e = command.exec();
+        eprintln!("error: cannot run {}: {e}", tool.name());
+        ExitCode::from(exit::IO)
+    }
+    #[cfg(not(unix))]
+    match command.status() {
+        Ok(status) => ExitCode::from(status.code().map_or(exit::IO, |code| code as u8)),
+        Err(e) => {
+            eprintln!("error: cannot run {}: {e}", tool.name());
+            ExitCode::from(exit::IO)
+        }
+    }
+}
+
+/// Civil instants at which the solar clock reads `dawn` and `dusk` on the
+/// solar date `date`.
+///
+/// Where the clock cannot be evaluated, as in polar day or night without
+/// `--polar-fallback`, each transition falls back to civil twilight, or
+/// when there is none, to six hours before or after the transit.
+pub fn transitions(
+    date: NaiveDate,
+    dawn: NaiveTime,
+    dusk: NaiveTime,
+    clock: &SolarClock,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), SpaError> {
+    let offset = clock.offset();
+    let civil = |time: NaiveTime| {
+        clock
+            .solar_to_civil(get_target_time(date, time, offset).with_timezone(&offset))
+            .inspect_err(|e| tracing::warn!(%time, "falling back to twilight: {e}"))
+            .ok()
+    };
+    let (dawn, dusk) = (civil(dawn), civil(dusk));
+    if let (Some(dawn), Some(dusk)) = (dawn, dusk) {
+        return Ok((dawn, dusk));
+    }
+
+    let noon = get_target_time(
+        date,
+        NaiveTime::from_hms_opt(12, 0, 0).unwrap_or_default(),
+        offset,
+    );
+    let utc_date = noon.date_naive();
+    let transit = calculate_solar_data_with_zenith(
+        utc_date,
+        clock.coordinates(),
+        clock.options().horizon_zenith(),
+    )?
+    .transit;
+    let twilight = calculate_twilight_data(utc_date, clock.coordinates())?;
+    Ok((
+        dawn.or(twilight.civil_dawn)
+            .unwrap_or(transit - Duration::hours(6)),
+        dusk.or(twilight.civil_dusk)
+            .unwrap_or(transit + Duration::hours(6)),
+    ))
+}
+
+/// `HH:MM` in local time, as the tools expect.
+fn wall_time(instant: DateTime<Utc>) -> String {
+    instant.with_timezone(&Local).format("%H:%M").to_string()
+}
@@ -0,0 +1,283 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `plot`: an SVG line chart of the delta over a year.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use solar_clock_rs::solar_clock::get_target_time;
+use solar_clock_rs::{AnchorEvent, SolarClock, SolarClockError};
+
+use crate::commands::format_signed_hms;
+use crate::exit;
+
+/// Space around the plot area for the axes labels and the legend, in pixels.
+const MARGIN: (f64, f64, f64, f64) = (48.0, 16.0, 56.0, 64.0); // top, right, bottom, left
+
+/// Renders the delta of every day of `year`, or of every hour with `hourly`,
+/// as a `width` × `height` SVG chart written to `path`.
+///
+/// Daily samples are taken when the solar clock should read its transit
+/// target, or at solar noon of the reference offset when no transit is
+/// anchored. The chart marks the minimum and the maximum and lists the
+/// anchor targets in its title.
+pub fn run(
+    year: i32,
+    hourly: bool,
+    (width, height): (u32, u32),
+    path: &Path,
+    clock: &SolarClock,
+) -> ExitCode {
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        eprintln!("error: year {year} is out of range");
+        return ExitCode::from(exit::USAGE);
+    };
+    let samples = match sample(first, last, hourly, clock) {
+        Ok(samples) => samples,
+        Err(e) => {
+            eprintln!("error: solar clock unavailable: {e}");
+            return exit::from_error(&e);
+        }
+    };
+    let svg = render(
+        year,
+        &samples,
+        hourly,
+        (f64::from(width), f64::from(height)),
+        clock,
+    );
+    if let Err(e) = fs::write(path, svg) {
+        eprintln!("error: cannot write {}: {e}", path.display());
+        return ExitCode::from(exit::IO);
+    }
+    ExitCode::SUCCESS
+}
+
+/// `(instant, delta)` pairs from `first` to `last`.
+fn sample(
+    first: NaiveDate,
+    last: NaiveDate,
+    hourly: bool,
+    clock: &SolarClock,
+) -> Result<Vec<(DateTime<Utc>, f64)>, SolarClockError> {
+    let instants: Vec<DateTime<Utc>> = if hourly {
+        let start = get_target_time(first, NaiveTime::MIN, clock.offset());
+        let end = get_target_time(last + Duration::days(1), NaiveTime::MIN, clock.offset());
+        let hours = (end - start).num_hours();
+        (0..hours).map(|h| start + Duration::hours(h)).collect()
+    } else {
+        let noon = clock
+            .options()
+            .anchors
+            .iter()
+            .find(|anchor| anchor.event == AnchorEvent::Transit)
+            .map_or_else(
+                || NaiveTime::from_hms_opt(12, 0, 0).expect("noon is a valid time"),
+                |anchor| anchor.target,
+            );
+        first
+            .iter_days()
+            .take_while(|date| *date <= last)
+            .map(|date| get_target_time(date, noon, clock.offset()))
+            .collect()
+    };
+    instants
+        .into_iter()
+        .map(|t| clock.solar_time(t).map(|result| (t, result.delta)))
+        .collect()
+}
+
+fn render(
+    year: i32,
+    samples: &[(DateTime<Utc>, f64)],
+    hourly: bool,
+    (width, height): (f64, f64),
+    clock: &SolarClock,
+) -> String {
+    let (top, right, bottom, left) = MARGIN;
+    let (plot_w, plot_h) = (width - left - right, height - top - bottom);
+
+    // Whole minutes around the data, so the axis ticks are round
+    let minutes = |seconds: f64| seconds / 60.0;
+    let (min, max) = samples
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, d)| {
+            (lo.min(d), hi.max(d))
+        });
+    let tick = tick_step(minutes(max) - minutes(min));
+    let y_lo = (minutes(min) / tick).floor() * tick;
+    let y_hi = ((minutes(max) / tick).ceil() * tick).max(y_lo + tick);
+
+    let start = samples.first().map_or(0, |(t, _)| t.timestamp()) as f64;
+    let end = samples.last().map_or(1, |(t, _)| t.timestamp()) as f64;
+    let x =
+        |t: DateTime<Utc>| left + (t.timestamp() as f64 - start) / (end - start).max(1.0) * plot_w;
+    let y = |delta: f64| top + (y_hi - minutes(delta)) / (y_hi - y_lo) * plot_h;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+
+    // Title and legend
+    let coords = clock.coordinates();
+    let targets: Vec<String> = clock
+        .options()
+        .anchors
+        .iter()
+        .map(|anchor| format!("{} {}", anchor.name, anchor.target.format("%H:%M")))
+        .collect();
+    let _ = writeln!(
+        svg,
+        r#"<text x="{left}" y="18" font-size="13">Solar clock delta {year} at ({}, {})</text>"#,
+        coords.latitude(),
+        coords.longitude()
+    );
+    let _ = writeln!(
+        svg,
+        r##"<text x="{left}" y="34" fill="#555">Targets (UTC{}): {}</text>"##,
+        clock.offset(),
+        escape(&targets.join(", "))
+    );
+
+    // Horizontal grid and minute labels
+    let mut value = y_lo;
+    while value <= y_hi + tick / 2.0 {
+        let py = top + (y_hi - value) / (y_hi - y_lo) * plot_h;
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{left}" y1="{py:.1}" x2="{:.1}" y2="{py:.1}" stroke="#ddd"/>"##,
+            left + plot_w
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{value:+} min</text>"#,
+            left - 6.0,
+            py + 4.0
+        );
+        value += tick;
+    }
+
+    // Month ticks
+    for month in 1..=12 {
+        let Some(date) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            continue;
+        };
+        let t = get_target_time(date, NaiveTime::MIN, clock.offset());
+        let px = x(t).clamp(left, left + plot_w);
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{px:.1}" y1="{top}" x2="{px:.1}" y2="{:.1}" stroke="#eee"/>"##,
+            top + plot_h
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}">{}</text>"#,
+            px + 2.0,
+            top + plot_h + 16.0,
+            date.format("%b")
+        );
+    }
+
+    // Axes
+    let _ = writeln!(
+        svg,
+        r#"<path d="M{left} {top} V{:.1} H{:.1}" fill="none" stroke="black"/>"#,
+        top + plot_h,
+        left + plot_w
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+        left + plot_w / 2.0,
+        height - 12.0,
+        if hourly {
+            "hourly samples"
+        } else {
+            "daily samples at the transit target"
+        }
+    );
+
+    // The curve, one path point per sample
+    let mut d = String::new();
+    for (i, &(t, delta)) in samples.iter().enumerate() {
+        let _ = write!(
+            d,
+            "{}{:.1} {:.1}",
+            if i == 0 { "M" } else { " L" },
+            x(t),
+            y(delta)
+        );
+    }
+    let _ = writeln!(
+        svg,
+        r##"<path d="{d}" fill="none" stroke="#d95f02" stroke-width="1.5"/>"##
+    );
+
+    // Extremes
+    for (label, &(t, delta)) in [
+        ("min", samples.iter().min_by(|a, b| a.1.total_cmp(&b.1))),
+        ("max", samples.iter().max_by(|a, b| a.1.total_cmp(&b.1))),
+    ]
+    .into_iter()
+    .filter_map(|(label, sample)| sample.map(|sample| (label, sample)))
+    {
+        let (px, py) = (x(t), y(delta));
+        let anchor = if px > left + plot_w / 2.0 {
+            "end"
+        } else {
+            "start"
+        };
+        let dy = if label == "min" { 14.0 } else { -6.0 };
+        let _ = writeln!(svg, r#"<circle cx="{px:.1}" cy="{py:.1}" r="3"/>"#);
+        let _ = writeln!(
+            svg,
+            r#"<text x="{px:.1}" y="{:.1}" text-anchor="{anchor}">{label} {} on {}</text>"#,
+            py + dy,
+            format_signed_hms(delta),
+            t.with_timezone(&clock.offset()).format("%b %-d")
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Round step in minutes giving about six grid lines over `span` minutes.
+fn tick_step(span: f64) -> f64 {
+    [1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0]
+        .into_iter()
+        .find(|step| span / step <= 8.0)
+        .unwrap_or(120.0)
+}
+
+/// Escapes XML special characters in text content.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
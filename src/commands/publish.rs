@@ -0,0 +1,188 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `publish`: keep an MQTT broker up to date with the solar clock.
+
+use std::path::Path;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use solar_clock_rs::{SolarClock, SolarEvent, next_event};
+
+use crate::exit;
+use crate::json::Object;
+use crate::mqtt::{Broker, Client};
+
+/// Longest uninterrupted sleep, so that Ctrl-C is honoured promptly.
+const POLL: Duration = Duration::from_millis(100);
+/// Delay before the first reconnection attempt; it doubles on every failure
+/// up to [`MAX_BACKOFF`].
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Publishes to `topic` until SIGINT:
+///
+/// - `TOPIC/state`, retained, every `interval`: `{"ts", "solar", "delta_s",
+///   "next_event"}`, where `next_event` is `{"kind", "at"}` for the coming
+///   sunrise, transit or sunset. When the clock cannot be
+///   evaluated, `solar` and `delta_s` are replaced by `error`.
+/// - `TOPIC/event`, not retained, at the instant of each sunrise, transit
+///   and sunset: `{"kind", "at", "solar"}`.
+///
+/// Instants are RFC 3339, `at` in the solar offset. Messages are sent with
+/// QoS 1; when the broker cannot be reached or stops acknowledging, the
+/// client reconnects with exponential backoff and resumes with a fresh
+/// state message. A TLS broker is checked against `ca_file`, when given.
+pub fn run(
+    broker: &Broker,
+    topic: &str,
+    interval: Duration,
+    ca_file: Option<&Path>,
+    clock: &SolarClock,
+) -> ExitCode {
+    let running = Arc::new(AtomicBool::new(true));
+    let handler = Arc::clone(&running);
+    if let Err(e) = ctrlc::set_handler(move || handler.store(false, Ordering::SeqCst)) {
+        eprintln!("error: cannot install the interrupt handler: {e}");
+        return ExitCode::from(exit::INTERNAL);
+    }
+
+    let client_id = format!("solar-clock-rs-{}", std::process::id());
+    // The state messages keep the session alive
+    let keep_alive = (interval * 2).max(Duration::from_secs(60));
+    let mut backoff = MIN_BACKOFF;
+
+    while running.load(Ordering::SeqCst) {
+        let mut client = match Client::connect(broker, &client_id, keep_alive, ca_file) {
+            Ok(client) => {
+                tracing::info!(%broker, "connected");
+                backoff = MIN_BACKOFF;
+                client
+            }
+            Err(e) => {
+                tracing::warn!(%broker, "cannot connect, retrying in {backoff:?}: {e}");
+                wait_until(Utc::now() + backoff, &running);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                continue;
+            }
+        };
+
+        match session(&mut client, topic, interval, clock, &running) {
+            Ok(()) => {
+                let _ = client.disconnect();
+            }
+            Err(e) => tracing::warn!(%broker, "connection lost: {e}"),
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Publishes on `client` until SIGINT or a failed publication.
+fn session(
+    client: &mut Client,
+    topic: &str,
+    interval: Duration,
+    clock: &SolarClock,
+    running: &AtomicBool,
+) -> std::io::Result<()> {
+    let state_topic = format!("{topic}/state");
+    let event_topic = format!("{topic}/event");
+    let mut next_state = Utc::now();
+
+    while running.load(Ordering::SeqCst) {
+        let now = Utc::now();
+        let event = upcoming(now, clock);
+        match event {
+            Some(event) if event.time < next_state => {
+                if !wait_until(event.time, running) {
+                    break;
+                }
+                client.publish(&event_topic, &event_message(event, clock), false)?;
+            }
+            _ => {
+                if !wait_until(next_state, running) {
+                    break;
+                }
+                let now = Utc::now();
+                let event = upcoming(now, clock);
+                client.publish(&state_topic, &state_message(now, event, clock), true)?;
+                // Skip the ticks missed while blocked on the broker
+                while next_state <= now {
+                    next_state += interval;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The sunrise, transit or sunset following `now`.
+fn upcoming(now: DateTime<Utc>, clock: &SolarClock) -> Option<SolarEvent> {
+    next_event(now, clock.coordinates())
+        .inspect_err(|e| tracing::warn!("solar position algorithm failed: {e}"))
+        .ok()
+        .flatten()
+}
+
+fn state_message(now: DateTime<Utc>, event: Option<SolarEvent>, clock: &SolarClock) -> String {
+    let next = event.map_or_else(
+        || "null".to_owned(),
+        |event| {
+            Object::new()
+                .string("kind", event.kind.name())
+                .time("at", Some(event.time.with_timezone(&clock.offset())))
+                .finish()
+        },
+    );
+    let message = Object::new().time("ts", Some(now));
+    match clock.solar_time(now) {
+        Ok(result) => message
+            .time("solar", Some(result.solar_time))
+            .number("delta_s", result.delta),
+        Err(e) => message.string("error", &e.to_string()),
+    }
+    .raw("next_event", &next)
+    .finish()
+}
+
+fn event_message(event: SolarEvent, clock: &SolarClock) -> String {
+    let solar = clock
+        .solar_time(event.time)
+        .map(|result| result.solar_time)
+        .ok();
+    Object::new()
+        .string("kind", event.kind.name())
+        .time("at", Some(event.time.with_timezone(&clock.offset())))
+        .time("solar", solar)
+        .finish()
+}
+
+/// Sleeps until `instant`, returning `false` if interrupted first.
+fn wait_until(instant: DateTime<Utc>, running: &AtomicBool) -> bool {
+    while running.load(Ordering::SeqCst) {
+        match (instant - Utc::now()).to_std() {
+            Ok(remaining) => thread::sleep(remaining.min(POLL)),
+            Err(_) => return true,
+        }
+    }
+    false
+}
@@ -0,0 +1,206 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `range`: one row of daily solar events per day over a date span.
+
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, SecondsFormat, Utc};
+use solar_clock_rs::solar_clock::get_target_time;
+use solar_clock_rs::spa::{DayLength, calculate_solar_data_with_zenith, day_length_with_zenith};
+use solar_clock_rs::{AnchorEvent, SolarClock};
+
+use crate::commands::{format_hm, format_signed_hms};
+use crate::exit;
+use crate::json::{self, Object};
+use crate::output::{self, OutputMode};
+
+/// Prints a row per day from `from` to `to`, both inclusive.
+///
+/// Times are wall times in the solar reference offset; absent sunrise and
+/// sunset are shown as `--:--` and the day length as `polar-day` or
+/// `polar-night`. The change column is the day length minus the previous
+/// day's, positive while the days get longer, and `n/a` next to polar day or
+/// night. The last column is the delta that moves the real transit onto its
+/// target.
+///
+/// With `--json`, prints one document instead: `coordinates` and a `days`
+/// array of `{"date", "sunrise", "transit", "sunset", "day_length",
+/// "polar", "day_length_change", "transit_delta"}`. Instants are RFC 3339 in
+/// the solar offset and durations seconds; `polar` is `"day"`, `"night"` or
+/// `null`, and the change and the delta are `null` where the text shows
+/// `n/a`.
+///
+/// With `--output csv`, prints a header row and one record per day with the
+/// same columns, suffixed `_s` for seconds; absent values are empty fields.
+pub fn run(
+    from: NaiveDate,
+    to: NaiveDate,
+    clock: &SolarClock,
+    output: Option<OutputMode>,
+) -> ExitCode {
+    let json = output == Some(OutputMode::Json);
+    let offset = clock.offset();
+    let transit_target = clock
+        .options()
+        .anchors
+        .iter()
+        .find(|anchor| anchor.event == AnchorEvent::Transit)
+        .map(|anchor| anchor.target);
+
+    let zenith = clock.options().horizon_zenith();
+    let mut previous = match from
+        .pred_opt()
+        .map(|date| day_length_with_zenith(date, clock.coordinates(), zenith))
+        .transpose()
+    {
+        Ok(previous) => previous,
+        Err(e) => {
+            eprintln!("error: {from}: solar position algorithm failed: {e}");
+            return ExitCode::from(exit::INTERNAL);
+        }
+    };
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut days = Vec::new();
+    if output == Some(OutputMode::Csv) {
+        let header = output::csv_record(&[
+            "date",
+            "sunrise",
+            "transit",
+            "sunset",
+            "day_length_s",
+            "polar",
+            "day_length_change_s",
+            "transit_delta_s",
+        ]);
+        if writeln!(out, "{header}").is_err() {
+            return ExitCode::from(exit::IO);
+        }
+    }
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        let data = match calculate_solar_data_with_zenith(date, clock.coordinates(), zenith) {
+            Ok(data) => data,
+            Err(e) => {
+                eprintln!("error: {date}: solar position algorithm failed: {e}");
+                return ExitCode::from(exit::INTERNAL);
+            }
+        };
+
+        let length = DayLength::from_data(&data, zenith);
+        let change = previous
+            .and_then(|previous| length.change_since(previous))
+            .map(|change| change.as_seconds_f64());
+        previous = Some(length);
+        let transit_delta = transit_target
+            .map(|target| (get_target_time(date, target, offset) - data.transit).as_seconds_f64());
+
+        if json {
+            let local = |event: Option<DateTime<Utc>>| event.map(|t| t.with_timezone(&offset));
+            let polar = match length {
+                DayLength::Daylight(_) => "null".to_owned(),
+                DayLength::PolarDay => json::string("day"),
+                DayLength::PolarNight => json::string("night"),
+            };
+            days.push(
+                Object::new()
+                    .string("date", &date.to_string())
+                    .time("sunrise", local(data.sunrise))
+                    .time("transit", local(Some(data.transit)))
+                    .time("sunset", local(data.sunset))
+                    .number("day_length", length.duration().as_seconds_f64())
+                    .raw("polar", &polar)
+                    .optional_number("day_length_change", change)
+                    .optional_number("transit_delta", transit_delta)
+                    .finish(),
+            );
+            continue;
+        }
+        if output == Some(OutputMode::Csv) {
+            let time = |event: Option<DateTime<Utc>>| {
+                event.map_or_else(String::new, |t| {
+                    t.with_timezone(&offset)
+                        .to_rfc3339_opts(SecondsFormat::Secs, false)
+                })
+            };
+            let seconds =
+                |value: Option<f64>| value.map_or_else(String::new, |v| format!("{v:.3}"));
+            let polar = match length {
+                DayLength::Daylight(_) => "",
+                DayLength::PolarDay => "day",
+                DayLength::PolarNight => "night",
+            };
+            let record = output::csv_record(&[
+                date.to_string(),
+                time(data.sunrise),
+                time(Some(data.transit)),
+                time(data.sunset),
+                seconds(Some(length.duration().as_seconds_f64())),
+                polar.to_owned(),
+                seconds(change),
+                seconds(transit_delta),
+            ]);
+            if writeln!(out, "{record}").is_err() {
+                return ExitCode::from(exit::IO);
+            }
+            continue;
+        }
+
+        let day_length = match length {
+            DayLength::Daylight(duration) => format_hm(duration),
+            DayLength::PolarDay => "polar-day".to_owned(),
+            DayLength::PolarNight => "polar-night".to_owned(),
+        };
+        let change = change.map_or_else(|| "n/a".to_owned(), format_signed_hms);
+        let transit_delta = transit_delta.map_or_else(|| "n/a".to_owned(), format_signed_hms);
+
+        let row = writeln!(
+            out,
+            "{date} sunrise {} transit {} sunset {} daylength {day_length} change {change} delta {transit_delta}",
+            wall_time(data.sunrise, offset),
+            wall_time(Some(data.transit), offset),
+            wall_time(data.sunset, offset),
+        );
+        if row.is_err() {
+            // Downstream closed the pipe
+            return ExitCode::from(exit::IO);
+        }
+    }
+
+    if json {
+        let document = Object::new()
+            .raw("coordinates", &output::coordinates_json(clock))
+            .raw("days", &json::array(days))
+            .finish();
+        if writeln!(out, "{document}").is_err() {
+            return ExitCode::from(exit::IO);
+        }
+    }
+    match out.flush() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::from(exit::IO),
+    }
+}
+
+fn wall_time(event: Option<DateTime<Utc>>, offset: FixedOffset) -> String {
+    match event {
+        Some(t) => t.with_timezone(&offset).format("%H:%M").to_string(),
+        None => "--:--".to_owned(),
+    }
+}
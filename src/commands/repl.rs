@@ -0,0 +1,181 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `repl`: interactive queries against a clock kept in memory.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::ExitCode;
+
+use chrono::{Local, Utc};
+use solar_clock_rs::{Coordinates, SolarClock};
+
+use crate::cli::{self, CliError};
+use crate::commands;
+use crate::config::Settings;
+use crate::exit;
+use crate::format::OutputFormat;
+use crate::output::OutputMode;
+
+const HELP: &str = "\
+Commands:
+  at <RFC3339|@EPOCH>    Evaluate the solar clock at this instant
+  now                    Evaluate the solar clock at the current time
+  next <HH:MM[:SS]>      When the solar clock next reads this wall time
+  events [YYYY-MM-DD]    Twilight, sunrise, transit and sunset (default: today)
+  set lat|lon <DEGREES>  Change the observer coordinates
+  set targets <SUNRISE,TRANSIT,SUNSET>
+  set midnight <HH:MM|none>
+  set offset <±HH:MM>    Change the solar reference offset
+  help                   Print this help
+  quit                   Leave (so does end of input)";
+
+/// Configuration and clock shared by the commands of one session. The clock
+/// keeps its cached model until a `set` changes the configuration.
+struct Session {
+    settings: Settings,
+    format: Option<OutputFormat>,
+    output: Option<OutputMode>,
+    verbose: bool,
+    clock: SolarClock,
+}
+
+impl Session {
+    fn new(
+        settings: Settings,
+        format: Option<OutputFormat>,
+        output: Option<OutputMode>,
+        verbose: bool,
+    ) -> Self {
+        let clock = settings.clock();
+        Self {
+            settings,
+            format,
+            output,
+            verbose,
+            clock,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), CliError> {
+        let coords = self.settings.coords;
+        match key {
+            "lat" => {
+                let latitude = cli::parse_number("lat", value.to_owned())?;
+                self.settings.coords = Coordinates::new(latitude, coords.longitude())
+                    .map_err(CliError::Coordinates)?;
+            }
+            "lon" => {
+                let longitude = cli::parse_number("lon", value.to_owned())?;
+                self.settings.coords = Coordinates::new(coords.latitude(), longitude)
+                    .map_err(CliError::Coordinates)?;
+            }
+            "targets" => {
+                self.settings.targets = cli::parse_targets(value.to_owned())?
+                    .with_midnight(self.settings.targets.midnight())
+                    .map_err(CliError::Targets)?
+            }
+            "midnight" => {
+                self.settings.targets = self
+                    .settings
+                    .targets
+                    .with_midnight(cli::parse_midnight(value.to_owned())?)
+                    .map_err(CliError::Targets)?
+            }
+            "offset" => self.settings.solar_offset = cli::parse_offset(value.to_owned())?,
+            _ => return Err(CliError::UnknownArgument(key.to_owned())),
+        }
+        self.clock = self.settings.clock();
+        Ok(())
+    }
+
+    /// Runs one input line. Returns `false` when the session should end.
+    fn execute(&mut self, line: &str) -> Result<bool, CliError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let format = self.format.as_ref();
+        match words[..] {
+            [] => {}
+            ["quit" | "exit"] => return Ok(false),
+            ["help"] => println!("{HELP}"),
+            ["now"] => {
+                commands::clock::run(
+                    Local::now().fixed_offset(),
+                    &self.clock,
+                    format,
+                    self.output,
+                    self.verbose,
+                    false,
+                );
+            }
+            ["at", instant] => {
+                let dt = cli::parse_timestamp(instant.to_owned())?;
+                commands::clock::run(dt, &self.clock, format, self.output, self.verbose, false);
+            }
+            ["next", time] => {
+                let time = cli::parse_time("next", time)?;
+                commands::next::run(time, Local::now().fixed_offset(), &self.clock, self.output);
+            }
+            ["events"] => {
+                let today = Utc::now().with_timezone(&self.clock.offset()).date_naive();
+                commands::events::run(today, false, &self.clock, self.output);
+            }
+            ["events", date] => {
+                let date = cli::parse_date(date.to_owned())?;
+                commands::events::run(date, false, &self.clock, self.output);
+            }
+            ["set", key, value] => self.set(key, value)?,
+            _ => return Err(CliError::UnknownCommand(line.trim().to_owned())),
+        }
+        Ok(true)
+    }
+}
+
+/// Reads commands from standard input until `quit` or end of input. Errors
+/// are reported on stderr, with the help text for unknown commands, and do
+/// not end the session.
+pub fn run(
+    settings: Settings,
+    format: Option<OutputFormat>,
+    output: Option<OutputMode>,
+    verbose: bool,
+) -> ExitCode {
+    let mut session = Session::new(settings, format, output, verbose);
+    let interactive = io::stdin().is_terminal();
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        if interactive {
+            print!("> ");
+            let _ = io::stdout().flush();
+        }
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                eprintln!("error: reading standard input: {e}");
+                return ExitCode::from(exit::IO);
+            }
+            None => return ExitCode::SUCCESS,
+        };
+
+        match session.execute(&line) {
+            Ok(true) => {}
+            Ok(false) => return ExitCode::SUCCESS,
+            Err(e @ CliError::UnknownCommand(_)) => eprintln!("error: {e}\n\n{HELP}"),
+            Err(e) => eprintln!("error: {e}"),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `run`: execute a command every day at fixed solar wall times.
+
+use std::process::{self, ExitCode};
+
+use chrono::{NaiveTime, SecondsFormat, Utc};
+use solar_clock_rs::SolarClock;
+
+use crate::commands;
+use crate::exit;
+
+/// Runs `command` each time the solar clock reaches one of `times`, forever.
+///
+/// The next civil instant is recomputed after every execution, since the
+/// civil time of a fixed solar time drifts through the year. Failures of the
+/// child are logged and do not stop the scheduler.
+pub fn run(times: &[NaiveTime], command: &[String], clock: &SolarClock) -> ExitCode {
+    let Some((program, args)) = command.split_first() else {
+        unreachable!("the command is validated by cli::parse");
+    };
+    let offset = clock.offset();
+
+    loop {
+        let now = Utc::now();
+        let next = times
+            .iter()
+            .map(|&time| clock.next_civil_time(now, time).map(|civil| (time, civil)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|schedule| schedule.into_iter().min_by_key(|&(_, civil)| civil));
+        let (time, civil) = match next {
+            Ok(Some(next)) => next,
+            Ok(None) => unreachable!("at least one time is validated by cli::parse"),
+            Err(e) => {
+                eprintln!("error: cannot invert the solar clock: {e}");
+                return exit::from_error(&e);
+            }
+        };
+
+        let civil_text = civil
+            .with_timezone(&offset)
+            .to_rfc3339_opts(SecondsFormat::Secs, false);
+        eprintln!("run: next at solar {time} (civil {civil_text})");
+        commands::sleep_until(civil);
+
+        eprintln!("run: solar {time} civil {civil_text}: running {program}");
+        match process::Command::new(program).args(args).status() {
+            Ok(status) if status.success() => {}
+            Ok(status) => eprintln!("run: {program} failed: {status}"),
+            Err(e) => eprintln!("run: cannot start {program}: {e}"),
+        }
+    }
+}
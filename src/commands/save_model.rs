@@ -0,0 +1,38 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `save-model`: write the interpolation model of a date for `--load-model`.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use chrono::NaiveDate;
+use solar_clock_rs::SolarClock;
+
+use crate::exit;
+
+/// Saves the model used for instants on the solar date `date` to `path`.
+pub fn run(date: NaiveDate, path: &Path, clock: &SolarClock) -> ExitCode {
+    match clock.save_model(date, path) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}: {e}", path.display());
+            exit::from_model_error(&e)
+        }
+    }
+}
@@ -0,0 +1,65 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `seasons`: the equinoxes and solstices of a year, and the days the sun
+//! passes overhead.
+
+use std::process::ExitCode;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use solar_clock_rs::SolarClock;
+use solar_clock_rs::spa::{solstices_equinoxes, subsolar_transits};
+
+use crate::exit;
+
+/// Prints one line per equinox or solstice: its name, the UTC instant and the
+/// same instant in the solar reference offset. Between the tropics, also one
+/// line per transit with the sun overhead.
+pub fn run(year: i32, clock: &SolarClock) -> ExitCode {
+    let Some(markers) = solstices_equinoxes(year) else {
+        eprintln!("error: year {year} is out of range");
+        return ExitCode::from(exit::USAGE);
+    };
+
+    let overhead = match subsolar_transits(year, clock.coordinates()) {
+        Ok(overhead) => overhead,
+        Err(e) => {
+            eprintln!("error: solar position algorithm failed: {e}");
+            return ExitCode::from(exit::INTERNAL);
+        }
+    };
+
+    let offset = clock.offset();
+    let print = |name: &str, instant: DateTime<Utc>| {
+        println!(
+            "{name:<17} {} {}",
+            instant.to_rfc3339_opts(SecondsFormat::Secs, true),
+            instant
+                .with_timezone(&offset)
+                .to_rfc3339_opts(SecondsFormat::Secs, false)
+        )
+    };
+    print("march equinox", markers.march_equinox);
+    print("june solstice", markers.june_solstice);
+    print("september equinox", markers.september_equinox);
+    print("december solstice", markers.december_solstice);
+    for (_, transit) in overhead {
+        print("sun overhead", transit);
+    }
+    ExitCode::SUCCESS
+}
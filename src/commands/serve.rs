@@ -0,0 +1,337 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `serve`: answer queries for the solar clock over HTTP, so other devices
+//! on the network can read it.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use solar_clock_rs::SolarClock;
+
+use crate::cli::{self, CliError};
+use crate::commands::{self, events, next};
+use crate::config::Settings;
+use crate::exit;
+use crate::json::Object;
+use crate::output;
+use crate::ws;
+
+/// Longest wait for a client to send its request, so that a stalled
+/// connection cannot hold the server.
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// Longest request line plus headers read before giving up on a client.
+const MAX_REQUEST: u64 = 8 * 1024;
+
+/// Serves until killed:
+///
+/// - `GET /now`: the `--json` document of the clock at the current time.
+/// - `GET /at?t=RFC3339`: the same document at the given instant.
+/// - `GET /events?date=YYYY-MM-DD`: the document of `events --json`, by
+///   default for today.
+/// - `GET /next?solar=HH:MM[:SS]&after=RFC3339`: the document of
+///   `next --json`, by default after the current time.
+/// - `GET /ws`: a WebSocket pushing the NDJSON line of `watch --output
+///   ndjson` as a text message every `interval`, at the civil instants that
+///   are whole multiples of it. Each stream runs on its own thread with its
+///   own copy of the clock, and ends when a message cannot be delivered;
+///   messages from the client are ignored.
+///
+/// Parameters may be percent-encoded; `+` is kept as is, so offsets such as
+/// `+01:00` can be written literally. Bad parameters get a 400 response and
+/// failed evaluations a 422, both with an `{"error": "..."}` body.
+///
+/// Connections are served concurrently, each on its own thread, and share
+/// `clock`, whose model is cached and only rebuilt when the solar date
+/// changes; the WebSocket clocks are built from `settings` and cached the
+/// same way.
+pub fn run(
+    listen: SocketAddr,
+    interval: Duration,
+    settings: &Settings,
+    clock: SolarClock,
+) -> ExitCode {
+    let listener = match TcpListener::bind(listen) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("error: cannot listen on {listen}: {e}");
+            return ExitCode::from(exit::IO);
+        }
+    };
+    tracing::info!(address = %listen, "listening");
+
+    let clock = Mutex::new(clock);
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let clock = &clock;
+                    scope.spawn(move || {
+                        if let Err(e) = serve(stream, interval, settings, clock) {
+                            tracing::warn!("connection failed: {e}");
+                        }
+                    });
+                }
+                Err(e) => tracing::warn!("cannot accept a connection: {e}"),
+            }
+        }
+    });
+    ExitCode::SUCCESS
+}
+
+/// An HTTP status and its JSON body.
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        let body = Object::new().string("error", message).finish();
+        Self { status, body }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            422 => "Unprocessable Content",
+            426 => "Upgrade Required",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Reads one request from `stream` and answers it. The connection is closed
+/// unless it is upgraded to a WebSocket.
+fn serve(
+    stream: TcpStream,
+    interval: Duration,
+    settings: &Settings,
+    clock: &Mutex<SolarClock>,
+) -> io::Result<()> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut reader = BufReader::new((&stream).take(MAX_REQUEST));
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut headers = Vec::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 && line.trim_end() != "" {
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_owned()));
+        }
+        line.clear();
+    }
+
+    let response = match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["GET", "/ws", _] => return upgrade(stream, &headers, interval, settings),
+        ["GET", target, _] => {
+            let clock = clock
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            respond(target, &clock)
+        }
+        [_, _, _] => Response::error(405, "only GET is supported"),
+        _ => Response::error(400, "malformed request line"),
+    };
+    tracing::debug!(
+        request = request_line.trim_end(),
+        status = response.status,
+        "served"
+    );
+
+    reply(&stream, &response)
+}
+
+fn reply(mut stream: &TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Completes the WebSocket handshake requested with `headers` and hands the
+/// connection to a thread of its own, see [`push`].
+fn upgrade(
+    mut stream: TcpStream,
+    headers: &[(String, String)],
+    interval: Duration,
+    settings: &Settings,
+) -> io::Result<()> {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let websocket = header("upgrade").is_some_and(|value| value.eq_ignore_ascii_case("websocket"));
+    let Some(key) = header("sec-websocket-key").filter(|_| websocket) else {
+        return reply(
+            &stream,
+            &Response::error(426, "expected a WebSocket upgrade"),
+        );
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        ws::accept_key(key)
+    )?;
+    stream.flush()?;
+    // A client that stops reading ends its stream instead of blocking forever
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    tracing::info!(peer = ?stream.peer_addr().ok(), "websocket opened");
+
+    let settings = settings.clone();
+    thread::spawn(move || push(stream, interval, &settings.clock()));
+    Ok(())
+}
+
+/// Sends the NDJSON line of the clock at every whole multiple of `interval`
+/// of civil time, until the client goes away. `seq` counts the messages of
+/// this connection.
+fn push(mut stream: TcpStream, interval: Duration, clock: &SolarClock) {
+    let step = i64::try_from(interval.as_millis())
+        .unwrap_or(i64::MAX)
+        .max(1);
+    for seq in 0.. {
+        let next = (Utc::now().timestamp_millis() / step)
+            .saturating_add(1)
+            .saturating_mul(step);
+        let Some(tick) = DateTime::from_timestamp_millis(next) else {
+            return;
+        };
+        commands::sleep_until(tick);
+        let message = output::ndjson(clock, tick, seq);
+        if let Err(e) = stream.write_all(&ws::text_frame(&message)) {
+            tracing::info!("websocket closed: {e}");
+            return;
+        }
+    }
+}
+
+/// Answers the request for `target`, a path with an optional query string.
+fn respond(target: &str, clock: &SolarClock) -> Response {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = match parse_query(query) {
+        Some(query) => query,
+        None => return Response::error(400, "malformed query string"),
+    };
+    let param = |name: &str| {
+        query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+    };
+
+    match path {
+        "/now" => solar_time(clock, Local::now().fixed_offset()),
+        "/at" => match param("t").map(cli::parse_timestamp) {
+            Some(Ok(dt)) => solar_time(clock, dt),
+            Some(Err(e)) => bad_request(&e),
+            None => bad_request(&CliError::MissingValue("t")),
+        },
+        "/events" => {
+            let date = match param("date").map(cli::parse_date).transpose() {
+                Ok(date) => {
+                    date.unwrap_or_else(|| Local::now().with_timezone(&clock.offset()).date_naive())
+                }
+                Err(e) => return bad_request(&e),
+            };
+            match events::json(date, false, clock) {
+                Ok(document) => Response::ok(document),
+                Err(e) => Response::error(422, &format!("solar position algorithm failed: {e}")),
+            }
+        }
+        "/next" => {
+            let time = match param("solar").map(|value| cli::parse_time("solar", &value)) {
+                Some(Ok(time)) => time,
+                Some(Err(e)) => return bad_request(&e),
+                None => return bad_request(&CliError::MissingValue("solar")),
+            };
+            let after = match param("after").map(cli::parse_timestamp).transpose() {
+                Ok(after) => after.unwrap_or_else(|| Local::now().fixed_offset()),
+                Err(e) => return bad_request(&e),
+            };
+            match next::solve(time, after, clock) {
+                Ok((civil, solar)) => Response::ok(next::json(after, civil, solar, clock)),
+                Err(e) => Response::error(422, &format!("cannot invert the solar clock: {e}")),
+            }
+        }
+        _ => Response::error(404, &format!("no such endpoint '{path}'")),
+    }
+}
+
+fn solar_time(clock: &SolarClock, dt: DateTime<FixedOffset>) -> Response {
+    match output::json(clock, dt) {
+        Ok(document) => Response::ok(document),
+        Err(e) => Response {
+            status: 422,
+            body: output::json_error(&e),
+        },
+    }
+}
+
+fn bad_request(e: &CliError) -> Response {
+    Response::error(400, &e.to_string())
+}
+
+/// Splits a query string into percent-decoded `(key, value)` pairs, or
+/// `None` if an escape is malformed or decodes to invalid UTF-8.
+fn parse_query(query: &str) -> Option<Vec<(String, String)>> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Some((percent_decode(key)?, percent_decode(value)?))
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
@@ -0,0 +1,86 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `sleep-until`: block until a solar event or a solar wall time.
+
+use std::process::ExitCode;
+
+use chrono::{DateTime, Duration, Local, SecondsFormat, Utc};
+use solar_clock_rs::spa::SpaError;
+use solar_clock_rs::{EventKind, SolarClock, events_between};
+
+use crate::cli::SleepTarget;
+use crate::commands;
+use crate::exit;
+
+/// Prints the first target instant after `now`, then sleeps until it is
+/// reached, returning at once if it is already past.
+pub fn run(target: SleepTarget, now: DateTime<Utc>, clock: &SolarClock) -> ExitCode {
+    let (name, instant) = match target {
+        SleepTarget::SolarTime(time) => match clock.next_civil_time(now, time) {
+            Ok(instant) => (time.to_string(), instant),
+            Err(e) => {
+                eprintln!("error: cannot invert the solar clock: {e}");
+                return exit::from_error(&e);
+            }
+        },
+        SleepTarget::Event(kind) => match next_of_kind(kind, now, clock) {
+            Ok(Some(instant)) => (kind.name().to_owned(), instant),
+            Ok(None) => {
+                eprintln!(
+                    "error: {} does not occur in the next days at this location",
+                    kind.name()
+                );
+                return ExitCode::from(exit::NO_SOLAR_DATA);
+            }
+            Err(e) => {
+                eprintln!("error: solar position algorithm failed: {e}");
+                return ExitCode::from(exit::INTERNAL);
+            }
+        },
+    };
+
+    println!(
+        "sleeping until {name} at {} ({})",
+        instant
+            .with_timezone(&clock.offset())
+            .to_rfc3339_opts(SecondsFormat::Secs, false),
+        instant
+            .with_timezone(&Local)
+            .to_rfc3339_opts(SecondsFormat::Secs, false)
+    );
+
+    commands::sleep_until(instant);
+    ExitCode::SUCCESS
+}
+
+/// Next occurrence of `kind` after `now` within the coming two days.
+fn next_of_kind(
+    kind: EventKind,
+    now: DateTime<Utc>,
+    clock: &SolarClock,
+) -> Result<Option<DateTime<Utc>>, SpaError> {
+    let start = now + Duration::nanoseconds(1);
+    for event in events_between(start, now + Duration::days(2), clock.coordinates()) {
+        let event = event?;
+        if event.kind == kind {
+            return Ok(Some(event.time));
+        }
+    }
+    Ok(None)
+}
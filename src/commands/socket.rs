@@ -0,0 +1,195 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `--socket`: answer line-based queries on a Unix domain socket, and the
+//! `query` client for it.
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::thread;
+
+use chrono::Local;
+use solar_clock_rs::SolarClock;
+
+use crate::cli::{self, CliError};
+use crate::commands::{events, next};
+use crate::exit;
+use crate::json::{self, Object};
+use crate::output;
+
+/// Socket used when `--socket` is not given to `query`:
+/// `$XDG_RUNTIME_DIR/solar-clock.sock`, or the temporary directory.
+pub fn default_path() -> PathBuf {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .map_or_else(std::env::temp_dir, PathBuf::from)
+        .join("solar-clock.sock")
+}
+
+/// Listens on `path` until killed, answering each line received with one
+/// line of JSON:
+///
+/// - `NOW`: the `--json` document of the clock at the current time.
+/// - `AT <RFC3339|@EPOCH>`: the same document at that instant.
+/// - `NEXT <HH:MM[:SS]>`: the document of `next --json`.
+/// - `EVENTS [YYYY-MM-DD]`: the document of `events --json`, by default for
+///   today.
+///
+/// Commands are case-insensitive. Failures, including malformed requests,
+/// are answered with `{"error": "..."}` and leave the connection open.
+///
+/// Clients are served concurrently, each on its own thread, and share
+/// `clock`, so the model is built once per solar date. The socket is only
+/// accessible to the current user; a stale one left by a crash is replaced,
+/// but one with a live server behind it is not.
+pub fn run(path: &Path, clock: SolarClock) -> ExitCode {
+    if UnixStream::connect(path).is_ok() {
+        eprintln!(
+            "error: {} is already served by another process",
+            path.display()
+        );
+        return ExitCode::from(exit::IO);
+    }
+    let _ = fs::remove_file(path);
+    let listener = match UnixListener::bind(path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("error: cannot listen on {}: {e}", path.display());
+            return ExitCode::from(exit::IO);
+        }
+    };
+    if let Err(e) = fs::set_permissions(path, fs::Permissions::from_mode(0o600)) {
+        eprintln!("error: cannot restrict {}: {e}", path.display());
+        return ExitCode::from(exit::IO);
+    }
+    let socket = path.to_owned();
+    let cleanup = ctrlc::set_handler(move || {
+        let _ = fs::remove_file(&socket);
+        std::process::exit(0);
+    });
+    if let Err(e) = cleanup {
+        eprintln!("error: cannot install the interrupt handler: {e}");
+        return ExitCode::from(exit::INTERNAL);
+    }
+    tracing::info!(path = %path.display(), "listening");
+
+    let clock = Mutex::new(clock);
+    thread::scope(|scope| {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let clock = &clock;
+                    scope.spawn(move || {
+                        if let Err(e) = serve(stream, clock) {
+                            tracing::warn!("connection failed: {e}");
+                        }
+                    });
+                }
+                Err(e) => tracing::warn!("cannot accept a connection: {e}"),
+            }
+        }
+    });
+    ExitCode::SUCCESS
+}
+
+/// Sends `request` to the server at `path` and prints its answer. Exits
+/// with an error status if the answer is an error.
+pub fn query(path: &Path, request: &[String]) -> ExitCode {
+    let mut stream = match UnixStream::connect(path) {
+        Ok(stream) => stream,
+        Err(e) => {
+            eprintln!("error: cannot connect to {}: {e}", path.display());
+            return ExitCode::from(exit::IO);
+        }
+    };
+    let mut response = String::new();
+    let exchanged = writeln!(stream, "{}", request.join(" "))
+        .and_then(|()| BufReader::new(&stream).read_line(&mut response));
+    match exchanged {
+        Ok(0) | Err(_) => {
+            eprintln!("error: no answer from {}", path.display());
+            ExitCode::from(exit::IO)
+        }
+        Ok(_) => {
+            print!("{response}");
+            if json::member(&response, "error").is_some() {
+                ExitCode::from(exit::USAGE)
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+    }
+}
+
+/// Answers the requests of one client until it disconnects.
+fn serve(stream: UnixStream, clock: &Mutex<SolarClock>) -> io::Result<()> {
+    let mut writer = &stream;
+    for line in BufReader::new(&stream).lines() {
+        let line = line?;
+        let response = {
+            let clock = clock
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            respond(&line, &clock)
+        };
+        let response = response.unwrap_or_else(|e| Object::new().string("error", &e).finish());
+        writeln!(writer, "{response}")?;
+    }
+    Ok(())
+}
+
+fn respond(line: &str, clock: &SolarClock) -> Result<String, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let Some((command, args)) = words.split_first() else {
+        return Err("empty request".to_owned());
+    };
+    let usage = |e: CliError| e.to_string();
+
+    match (command.to_ascii_uppercase().as_str(), args) {
+        ("NOW", []) => output::json(clock, Local::now().fixed_offset()).map_err(|e| e.to_string()),
+        ("AT", [instant]) => {
+            let dt = cli::parse_timestamp((*instant).to_owned()).map_err(usage)?;
+            output::json(clock, dt).map_err(|e| e.to_string())
+        }
+        ("NEXT", [time]) => {
+            let time = cli::parse_time("NEXT", time).map_err(usage)?;
+            let after = Local::now().fixed_offset();
+            let (civil, solar) = next::solve(time, after, clock)
+                .map_err(|e| format!("cannot invert the solar clock: {e}"))?;
+            Ok(next::json(after, civil, solar, clock))
+        }
+        ("EVENTS", []) => {
+            let today = Local::now().with_timezone(&clock.offset()).date_naive();
+            events::json(today, false, clock)
+                .map_err(|e| format!("solar position algorithm failed: {e}"))
+        }
+        ("EVENTS", [date]) => {
+            let date = cli::parse_date((*date).to_owned()).map_err(usage)?;
+            events::json(date, false, clock)
+                .map_err(|e| format!("solar position algorithm failed: {e}"))
+        }
+        _ => Err(format!(
+            "unknown request '{}': expected NOW, AT <RFC3339>, NEXT <HH:MM> or EVENTS [YYYY-MM-DD]",
+            line.trim()
+        )),
+    }
+}
@@ -0,0 +1,140 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `tzif`: a zoneinfo file approximating the solar clock.
+
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use solar_clock_rs::solar_clock::get_target_time;
+use solar_clock_rs::{SolarClock, SolarClockError};
+
+use crate::exit;
+use crate::tzif::{EncodeError, Zone};
+
+/// Spacing of the samples between which offset changes are searched.
+const SCAN_STEP: Duration = Duration::minutes(10);
+
+/// Writes to `path` a TZif file whose UT offset follows the solar clock from
+/// solar midnight on `from` to solar midnight after `to`, in steps of
+/// `granularity` seconds.
+///
+/// The offset is the solar reference offset plus the delta rounded to the
+/// nearest multiple of `granularity`, and changes at the second the rounded
+/// value does, so within the span local time under `TZ=:path` differs from
+/// the solar clock by at most half the granularity. Before the span the
+/// first offset applies, and after it the last one. Offsets beyond ±24 h,
+/// which a TZif file cannot state, are an error.
+pub fn run(
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: u32,
+    path: &Path,
+    clock: &SolarClock,
+) -> ExitCode {
+    let zone = match approximate(from, to, granularity, clock) {
+        Ok(zone) => zone,
+        Err(e) => {
+            eprintln!("error: solar clock unavailable: {e}");
+            return exit::from_error(&e);
+        }
+    };
+    let bytes = match zone.encode() {
+        Ok(bytes) => bytes,
+        Err(e @ EncodeError::TooManyOffsets(_)) => {
+            eprintln!("error: {e}; use a coarser granularity");
+            return ExitCode::from(exit::USAGE);
+        }
+        Err(e @ EncodeError::OffsetOutOfRange(_)) => {
+            eprintln!("error: {e}; use a --solar-offset closer to UTC");
+            return ExitCode::from(exit::USAGE);
+        }
+    };
+    if let Err(e) = fs::write(path, bytes) {
+        eprintln!("error: cannot write {}: {e}", path.display());
+        return ExitCode::from(exit::IO);
+    }
+    tracing::info!(
+        transitions = zone.transitions.len(),
+        offsets = zone.offsets.len(),
+        "wrote {}",
+        path.display()
+    );
+    ExitCode::SUCCESS
+}
+
+fn approximate(
+    from: NaiveDate,
+    to: NaiveDate,
+    granularity: u32,
+    clock: &SolarClock,
+) -> Result<Zone, SolarClockError> {
+    let base = clock.offset().local_minus_utc();
+    let step = f64::from(granularity);
+    let offset_at = |t: DateTime<Utc>| -> Result<i32, SolarClockError> {
+        let delta = clock.solar_time(t)?.delta;
+        // Deltas are at most hours, far from overflowing
+        Ok(base + ((delta / step).round() * step) as i32)
+    };
+
+    let start = get_target_time(from, NaiveTime::MIN, clock.offset());
+    let end = get_target_time(to + Duration::days(1), NaiveTime::MIN, clock.offset());
+    let mut current = offset_at(start)?;
+    let mut zone = Zone {
+        offsets: vec![current],
+        transitions: Vec::new(),
+    };
+
+    let second = |t: i64| DateTime::from_timestamp(t, 0).expect("within the span");
+    let mut t = start;
+    while t < end {
+        let next = (t + SCAN_STEP).min(end);
+        if offset_at(next)? == current {
+            t = next;
+            continue;
+        }
+
+        // Bisect to the first second with another offset, and scan on from
+        // there in case it changes again before `next`
+        let (mut lo, mut hi) = (t.timestamp(), next.timestamp());
+        while hi - lo > 1 {
+            let mid = lo + (hi - lo) / 2;
+            if offset_at(second(mid))? == current {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let offset = offset_at(second(hi))?;
+        let index = match zone.offsets.iter().position(|&o| o == offset) {
+            Some(index) => index,
+            None => {
+                zone.offsets.push(offset);
+                zone.offsets.len() - 1
+            }
+        };
+        // Encoding rejects zones with more types than indices
+        zone.transitions
+            .push((hi, u8::try_from(index).unwrap_or(u8::MAX)));
+        current = offset;
+        t = second(hi);
+    }
+    Ok(zone)
+}
@@ -0,0 +1,47 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `validate`: check that the configured targets cover the solar day.
+
+use std::process::ExitCode;
+
+use chrono::NaiveDate;
+use solar_clock_rs::{SolarClock, check_coverage};
+
+use crate::exit;
+
+/// Prints `ok` or one line per run of skipped or repeated solar minutes, and
+/// fails with the usage status in the latter case.
+pub fn run(date: NaiveDate, clock: &SolarClock) -> ExitCode {
+    match check_coverage(clock, date) {
+        Ok(issues) if issues.is_empty() => {
+            println!("{date} ok: every solar minute is shown exactly once");
+            ExitCode::SUCCESS
+        }
+        Ok(issues) => {
+            for issue in &issues {
+                println!("{date} {issue}");
+            }
+            ExitCode::from(exit::USAGE)
+        }
+        Err(e) => {
+            eprintln!("error: solar clock unavailable: {e}");
+            exit::from_error(&e)
+        }
+    }
+}
@@ -0,0 +1,131 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `watch`: keep printing the solar time until interrupted.
+
+use std::io::{self, IsTerminal, Write};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{SecondsFormat, Utc};
+use solar_clock_rs::SolarClock;
+
+use crate::exit;
+use crate::format::OutputFormat;
+use crate::output::{self, OutputMode};
+
+/// Longest uninterrupted sleep, so that Ctrl-C is honoured promptly even
+/// with long intervals.
+const POLL: Duration = Duration::from_millis(100);
+
+/// Evaluates the clock every `interval` until SIGINT.
+///
+/// On a terminal the line is rewritten in place; otherwise, and always with
+/// `--output waybar`, `ndjson` or `--json`, one line is printed per tick and
+/// flushed at once. The model is cached by `clock` and only rebuilt when
+/// the solar date changes.
+pub fn run(
+    interval: Duration,
+    clock: &SolarClock,
+    format: Option<&OutputFormat>,
+    output: Option<OutputMode>,
+) -> ExitCode {
+    let running = Arc::new(AtomicBool::new(true));
+    let handler = Arc::clone(&running);
+    if let Err(e) = ctrlc::set_handler(move || handler.store(false, Ordering::SeqCst)) {
+        eprintln!("error: cannot install the interrupt handler: {e}");
+        return ExitCode::from(exit::INTERNAL);
+    }
+
+    let rewrite = io::stdout().is_terminal() && output.is_none();
+    let mut out = io::stdout().lock();
+    if output == Some(OutputMode::Csv) && writeln!(out, "civil,solar,delta_s").is_err() {
+        return ExitCode::from(exit::IO);
+    }
+    let start = Instant::now();
+    let mut tick: u32 = 0;
+
+    while running.load(Ordering::SeqCst) {
+        let now = Utc::now();
+        let line = match (output, clock.solar_time(now)) {
+            (Some(OutputMode::Waybar), _) => {
+                output::waybar(clock, now).unwrap_or_else(|e| output::waybar_error(&e))
+            }
+            (Some(OutputMode::HomeAssistant), _) => output::home_assistant(clock, now)
+                .unwrap_or_else(|e| output::home_assistant_error(&e)),
+            (Some(OutputMode::Ndjson), _) => output::ndjson(clock, now, u64::from(tick)),
+            (Some(OutputMode::Csv), Ok(result)) => output::csv_record(&[
+                now.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+                result
+                    .solar_time
+                    .to_rfc3339_opts(SecondsFormat::AutoSi, false),
+                format!("{:.3}", result.delta),
+            ]),
+            (Some(OutputMode::Csv), Err(e)) => {
+                // Keep the table rectangular: empty fields, reason on stderr
+                tracing::warn!("solar clock unavailable: {e}");
+                output::csv_record(&[
+                    now.to_rfc3339_opts(SecondsFormat::AutoSi, false),
+                    String::new(),
+                    String::new(),
+                ])
+            }
+            (Some(OutputMode::Json), _) => {
+                output::json(clock, now.fixed_offset()).unwrap_or_else(|e| output::json_error(&e))
+            }
+            // Transition times are a one-shot output; keep the plain line
+            (None | Some(OutputMode::Redshift), Ok(result)) => match format {
+                Some(format) => format.render(&result),
+                None => format!(
+                    "{} {:+.3} s",
+                    result.solar_time.format("%Y-%m-%d %H:%M:%S"),
+                    result.delta
+                ),
+            },
+            (None | Some(OutputMode::Redshift), Err(e)) => format!("solar clock unavailable: {e}"),
+        };
+        let written = if rewrite {
+            write!(out, "\r\x1b[2K{line}").and_then(|()| out.flush())
+        } else {
+            writeln!(out, "{line}").and_then(|()| out.flush())
+        };
+        if written.is_err() {
+            // Downstream closed the pipe
+            return ExitCode::from(exit::IO);
+        }
+
+        // Schedule against the start so ticks do not drift
+        tick += 1;
+        let next = start + interval * tick;
+        while running.load(Ordering::SeqCst) {
+            let now = Instant::now();
+            if now >= next {
+                break;
+            }
+            thread::sleep((next - now).min(POLL));
+        }
+    }
+
+    if rewrite {
+        let _ = writeln!(out);
+    }
+    ExitCode::SUCCESS
+}
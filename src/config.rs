@@ -0,0 +1,578 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Configuration file support for the `solar-clock-rs` binary.
+//!
+//! The file is looked up at `$XDG_CONFIG_HOME/solar-clock/config.toml`
+//! (falling back to `~/.config/solar-clock/config.toml`). It is TOML with
+//! top-level keys only:
+//!
+//! ```toml
+//! latitude = 40.4168
+//! longitude = -3.7038
+//! targets = ["08:00", "14:00", "20:00"]
+//! midnight = "02:00"   # default "none"
+//! solar_offset = "+01:00"
+//! format = "%H:%M {delta_hms}"
+//! ```
+//!
+//! The environment variables `SOLAR_CLOCK_LAT`, `SOLAR_CLOCK_LON`,
+//! `SOLAR_CLOCK_TARGETS` (`08:00,14:00,20:00`), `SOLAR_CLOCK_MIDNIGHT` and
+//! `SOLAR_CLOCK_OFFSET` sit
+//! between the two: command line flags override the environment, which
+//! overrides the file, which overrides the built-in defaults.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use chrono::{FixedOffset, NaiveDate, NaiveTime};
+use solar_clock_rs::solar_clock::{
+    DEFAULT_COORDINATES, DEFAULT_TARGETS, DEFAULT_WINDOW_DAYS, SOLAR_TIMEZONE_OFFSET,
+};
+use solar_clock_rs::spa::STANDARD_ZENITH;
+use solar_clock_rs::{
+    Coordinates, InterpolationMethod, SolarClock, SolarClockBuilder, SolarTargets, check_coverage,
+};
+use toml::de::{DeTable, DeValue};
+
+use crate::cli::{self, Args, CliError};
+use crate::format::OutputFormat;
+
+/// Values read from the configuration file or the environment. `None` means
+/// "not set".
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FileConfig {
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub targets: Option<SolarTargets>,
+    /// `Some(None)` disables the solar midnight anchor.
+    pub midnight: Option<Option<NaiveTime>>,
+    pub solar_offset: Option<FixedOffset>,
+    pub format: Option<OutputFormat>,
+}
+
+/// Fully resolved configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub coords: Coordinates,
+    pub targets: SolarTargets,
+    pub solar_offset: FixedOffset,
+    pub interpolation: InterpolationMethod,
+    pub window_days: u32,
+    pub polar_fallback: bool,
+    pub zenith: f64,
+    pub elevation: f64,
+    pub temperature: Option<f64>,
+    pub pressure: Option<f64>,
+    pub format: Option<OutputFormat>,
+}
+
+impl Settings {
+    /// Solar clock configured with these settings.
+    pub fn clock(&self) -> SolarClock {
+        let mut builder = SolarClockBuilder::new()
+            .coordinates(self.coords.latitude(), self.coords.longitude())
+            .targets(
+                self.targets.sunrise(),
+                self.targets.transit(),
+                self.targets.sunset(),
+            )
+            .solar_offset(self.solar_offset)
+            .midnight(self.targets.midnight())
+            .interpolation(self.interpolation)
+            .window_days(self.window_days)
+            .polar_fallback(self.polar_fallback)
+            .zenith(self.zenith)
+            .elevation(self.elevation);
+        if let Some(celsius) = self.temperature {
+            builder = builder.temperature(celsius);
+        }
+        if let Some(hpa) = self.pressure {
+            builder = builder.pressure(hpa);
+        }
+        builder.build().expect("settings are validated by resolve")
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Syntax {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+    InvalidValue {
+        path: PathBuf,
+        line: usize,
+        key: String,
+        message: String,
+    },
+    Env {
+        variable: &'static str,
+        message: String,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::Syntax {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{line}: {message}", path.display()),
+            Self::InvalidValue {
+                path,
+                line,
+                key,
+                message,
+            } => write!(f, "{}:{line}: invalid '{key}': {message}", path.display()),
+            Self::Env { variable, message } => {
+                write!(f, "environment variable {variable}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Default location of the configuration file, if a home can be determined.
+pub fn default_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(base.join("solar-clock").join("config.toml"))
+}
+
+/// Loads the configuration from `explicit`, or from the default location.
+///
+/// A missing file at the default location is not an error; a missing file
+/// given explicitly is.
+pub fn load(explicit: Option<&Path>) -> Result<FileConfig, ConfigError> {
+    let path = match explicit {
+        Some(path) => path.to_path_buf(),
+        None => match default_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(FileConfig::default()),
+        },
+    };
+    let text = std::fs::read_to_string(&path).map_err(|source| ConfigError::Io {
+        path: path.clone(),
+        source,
+    })?;
+    parse(&path, &text)
+}
+
+/// Reads the `SOLAR_CLOCK_*` environment variables. Empty variables count as
+/// unset.
+pub fn load_env() -> Result<FileConfig, ConfigError> {
+    from_vars(|name| std::env::var(name).ok().filter(|value| !value.is_empty()))
+}
+
+/// Builds a configuration layer from variables provided by `lookup`.
+pub fn from_vars(lookup: impl Fn(&str) -> Option<String>) -> Result<FileConfig, ConfigError> {
+    fn read<T>(
+        lookup: &impl Fn(&str) -> Option<String>,
+        variable: &'static str,
+        parse: impl FnOnce(String) -> Result<T, String>,
+    ) -> Result<Option<T>, ConfigError> {
+        lookup(variable)
+            .map(parse)
+            .transpose()
+            .map_err(|message| ConfigError::Env { variable, message })
+    }
+    let number = |value: String| match value.trim().parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(format!("invalid value '{value}': expected a number")),
+    };
+    // Checked here rather than by resolve, so that the error names the
+    // variable
+    let latitude = |value| {
+        let latitude = number(value)?;
+        Coordinates::new(latitude, 0.0)
+            .map(|_| latitude)
+            .map_err(|e| e.to_string())
+    };
+    let longitude = |value| {
+        let longitude = number(value)?;
+        Coordinates::new(0.0, longitude)
+            .map(|_| longitude)
+            .map_err(|e| e.to_string())
+    };
+
+    Ok(FileConfig {
+        latitude: read(&lookup, "SOLAR_CLOCK_LAT", latitude)?,
+        longitude: read(&lookup, "SOLAR_CLOCK_LON", longitude)?,
+        targets: read(&lookup, "SOLAR_CLOCK_TARGETS", |value| {
+            cli::parse_targets(value).map_err(|e| e.to_string())
+        })?,
+        midnight: read(&lookup, "SOLAR_CLOCK_MIDNIGHT", |value| {
+            cli::parse_midnight(value).map_err(|e| e.to_string())
+        })?,
+        solar_offset: read(&lookup, "SOLAR_CLOCK_OFFSET", |value| {
+            cli::parse_offset(value).map_err(|e| e.to_string())
+        })?,
+        format: None,
+    })
+}
+
+/// Parses the contents of the configuration file at `path`.
+pub fn parse(path: &Path, text: &str) -> Result<FileConfig, ConfigError> {
+    let line = |offset: usize| text[..offset].matches('\n').count() + 1;
+    let table = DeTable::parse(text).map_err(|error| ConfigError::Syntax {
+        path: path.to_path_buf(),
+        line: line(error.span().map_or(0, |span| span.start)),
+        message: error.message().trim_end().to_owned(),
+    })?;
+
+    // Report the first bad key in file order, not in key order
+    let mut entries: Vec<_> = table.get_ref().iter().collect();
+    entries.sort_by_key(|(key, _)| key.span().start);
+
+    let mut config = FileConfig::default();
+    for (key, value) in entries {
+        let line = line(key.span().start);
+        let key = key.get_ref().as_ref();
+        let value = value.get_ref();
+        let invalid = |message: String| ConfigError::InvalidValue {
+            path: path.to_path_buf(),
+            line,
+            key: key.to_owned(),
+            message,
+        };
+        if value.is_table() {
+            return Err(ConfigError::Syntax {
+                path: path.to_path_buf(),
+                line,
+                message: "tables are not supported, use top-level keys".to_owned(),
+            });
+        }
+        match key {
+            "latitude" => config.latitude = Some(number(value).map_err(invalid)?),
+            "longitude" => config.longitude = Some(number(value).map_err(invalid)?),
+            "targets" => {
+                let joined = strings(value).map_err(invalid)?.join(",");
+                config.targets =
+                    Some(cli::parse_targets(joined).map_err(|e| invalid(e.to_string()))?);
+            }
+            "midnight" => {
+                let midnight = string(value).map_err(invalid)?;
+                config.midnight =
+                    Some(cli::parse_midnight(midnight).map_err(|e| invalid(e.to_string()))?);
+            }
+            "solar_offset" => {
+                let offset = string(value).map_err(invalid)?;
+                config.solar_offset =
+                    Some(cli::parse_offset(offset).map_err(|e| invalid(e.to_string()))?);
+            }
+            "format" => {
+                let format = string(value).map_err(invalid)?;
+                config.format =
+                    Some(cli::parse_format(format).map_err(|e| invalid(e.to_string()))?);
+            }
+            _ => return Err(invalid("unknown key".to_owned())),
+        }
+    }
+
+    Ok(config)
+}
+
+/// Merges the command line over the environment over the file over the
+/// built-in defaults.
+pub fn resolve(args: &Args, env: &FileConfig, file: &FileConfig) -> Result<Settings, CliError> {
+    let coords = Coordinates::new(
+        args.latitude
+            .or(env.latitude)
+            .or(file.latitude)
+            .unwrap_or(DEFAULT_COORDINATES.latitude()),
+        args.longitude
+            .or(env.longitude)
+            .or(file.longitude)
+            .unwrap_or(DEFAULT_COORDINATES.longitude()),
+    )
+    .map_err(CliError::Coordinates)?;
+    let targets = args
+        .targets
+        .or(env.targets)
+        .or(file.targets)
+        .unwrap_or(DEFAULT_TARGETS)
+        .with_midnight(args.midnight.or(env.midnight).or(file.midnight).flatten())
+        .map_err(CliError::Targets)?;
+
+    Ok(Settings {
+        coords,
+        targets,
+        solar_offset: args
+            .solar_offset
+            .or(env.solar_offset)
+            .or(file.solar_offset)
+            .unwrap_or(SOLAR_TIMEZONE_OFFSET),
+        interpolation: args.interpolation.unwrap_or_default(),
+        window_days: args.window_days.unwrap_or(DEFAULT_WINDOW_DAYS),
+        polar_fallback: args.polar_fallback,
+        zenith: args.zenith.unwrap_or(STANDARD_ZENITH),
+        elevation: args.elevation.unwrap_or(0.0),
+        temperature: args.temperature,
+        pressure: args.pressure,
+        format: args.format.clone().or_else(|| file.format.clone()),
+    })
+}
+
+/// Warns about solar minutes of `date` that the clock skips or shows twice
+/// with the resolved targets. Evaluation failures are left to the command.
+pub fn warn_coverage(clock: &SolarClock, date: NaiveDate) {
+    match check_coverage(clock, date) {
+        Ok(issues) => {
+            for issue in issues {
+                tracing::warn!("targets do not cover the solar day {date}: {issue}");
+            }
+        }
+        Err(e) => tracing::debug!("coverage of {date} not checked: {e}"),
+    }
+}
+
+fn number(value: &DeValue<'_>) -> Result<f64, String> {
+    let number = match value {
+        DeValue::Integer(integer) => i64::from_str_radix(integer.as_str(), integer.radix())
+            .ok()
+            .map(|number| number as f64),
+        DeValue::Float(float) => float.as_str().replace('_', "").parse::<f64>().ok(),
+        _ => return Err("expected a number".to_owned()),
+    };
+    number
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| "expected a finite number".to_owned())
+}
+
+fn string(value: &DeValue<'_>) -> Result<String, String> {
+    value
+        .as_str()
+        .map(str::to_owned)
+        .ok_or_else(|| "expected a string".to_owned())
+}
+
+/// Accepts either an array of strings or a single comma-separated string.
+fn strings(value: &DeValue<'_>) -> Result<Vec<String>, String> {
+    match value {
+        DeValue::Array(items) => items.iter().map(|item| string(item.get_ref())).collect(),
+        DeValue::String(string) => Ok(vec![string.to_string()]),
+        _ => Err("expected an array of strings".to_owned()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+
+    use super::*;
+
+    fn fixture() -> FileConfig {
+        load(Some(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/data/config.toml"),
+        ))
+        .unwrap()
+    }
+
+    fn time(hour: u32, minute: u32, second: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, second).unwrap()
+    }
+
+    fn args(flags: &[&str]) -> Args {
+        cli::parse(flags.iter().map(|flag| flag.to_string())).unwrap()
+    }
+
+    #[test]
+    fn loads_the_fixture() {
+        let targets = SolarTargets::new(time(7, 30, 0), time(13, 0, 0), time(21, 0, 30)).unwrap();
+        assert_eq!(
+            fixture(),
+            FileConfig {
+                latitude: Some(59.9139),
+                longitude: Some(10.7522),
+                targets: Some(targets),
+                midnight: Some(Some(time(1, 0, 0))),
+                solar_offset: FixedOffset::east_opt(2 * 3600),
+                format: Some(OutputFormat::parse("%H:%M {delta_hms}").unwrap()),
+            }
+        );
+    }
+
+    #[test]
+    fn accepts_multi_line_arrays_and_literal_strings() {
+        let text = "targets = [\n    '07:30',  # sunrise\n    \"13:00\",\n    '21:00:30',\n]\nformat = '%H:%M {delta_hms}'\nlatitude = 5_991.39e-2\nlongitude = 10\n";
+        let config = parse(Path::new("config.toml"), text).unwrap();
+        assert_eq!(config.targets, fixture().targets);
+        assert_eq!(config.format, fixture().format);
+        assert_eq!(config.latitude, Some(59.9139));
+        assert_eq!(config.longitude, Some(10.0));
+    }
+
+    #[test]
+    fn the_file_overrides_the_defaults() {
+        let settings = resolve(&Args::default(), &FileConfig::default(), &fixture()).unwrap();
+        assert_eq!(settings.coords, Coordinates::new(59.9139, 10.7522).unwrap());
+        assert_eq!(settings.targets.sunset(), time(21, 0, 30));
+        assert_eq!(settings.targets.midnight(), Some(time(1, 0, 0)));
+        assert_eq!(
+            settings.solar_offset,
+            FixedOffset::east_opt(2 * 3600).unwrap()
+        );
+        assert!(settings.format.is_some());
+
+        let defaults = resolve(
+            &Args::default(),
+            &FileConfig::default(),
+            &FileConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(defaults.coords, DEFAULT_COORDINATES);
+        assert_eq!(defaults.targets, DEFAULT_TARGETS);
+        assert_eq!(defaults.solar_offset, SOLAR_TIMEZONE_OFFSET);
+        assert_eq!(defaults.format, None);
+    }
+
+    #[test]
+    fn flags_override_the_environment_and_the_file() {
+        let env = from_vars(|name| match name {
+            "SOLAR_CLOCK_LAT" => Some("48.8566".to_owned()),
+            "SOLAR_CLOCK_OFFSET" => Some("+01:00".to_owned()),
+            "SOLAR_CLOCK_MIDNIGHT" => Some("none".to_owned()),
+            _ => None,
+        })
+        .unwrap();
+        let file = fixture();
+
+        // The environment wins over the file, key by key
+        let settings = resolve(&Args::default(), &env, &file).unwrap();
+        assert_eq!(settings.coords, Coordinates::new(48.8566, 10.7522).unwrap());
+        assert_eq!(settings.solar_offset, SOLAR_TIMEZONE_OFFSET);
+        assert_eq!(settings.targets.midnight(), None);
+        assert_eq!(settings.targets.sunrise(), time(7, 30, 0));
+
+        let flags = args(&[
+            "--lat",
+            "-33.8688",
+            "--targets",
+            "08:00,14:00,20:00",
+            "--format",
+            "%T",
+        ]);
+        let settings = resolve(&flags, &env, &file).unwrap();
+        assert_eq!(
+            settings.coords,
+            Coordinates::new(-33.8688, 10.7522).unwrap()
+        );
+        assert_eq!(settings.targets, DEFAULT_TARGETS);
+        assert_eq!(settings.format, Some(OutputFormat::parse("%T").unwrap()));
+        assert_eq!(settings.solar_offset, SOLAR_TIMEZONE_OFFSET);
+    }
+
+    #[test]
+    fn errors_name_the_key_and_line() {
+        let path = Path::new("config.toml");
+        let error = parse(path, "latitude = 40\n\n# note\nlongitude = \"east\"\n").unwrap_err();
+        assert!(
+            matches!(&error, ConfigError::InvalidValue { line: 4, key, .. } if key == "longitude"),
+            "{error:?}"
+        );
+        assert!(
+            error
+                .to_string()
+                .starts_with("config.toml:4: invalid 'longitude': ")
+        );
+
+        let error = parse(path, "targets = [\"20:00\", \"14:00\", \"08:00\"]").unwrap_err();
+        assert!(
+            matches!(&error, ConfigError::InvalidValue { line: 1, key, .. } if key == "targets")
+        );
+        let error = parse(path, "colour = \"red\"").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "config.toml:1: invalid 'colour': unknown key"
+        );
+        let error = parse(path, "latitude = 40\nlongitude 40").unwrap_err();
+        assert!(
+            matches!(error, ConfigError::Syntax { line: 2, .. }),
+            "{error:?}"
+        );
+        let error = parse(path, "latitude = 40\n\n[location]\nlongitude = 10").unwrap_err();
+        assert!(
+            matches!(error, ConfigError::Syntax { line: 3, .. }),
+            "{error:?}"
+        );
+        let error = parse(path, "latitude = inf").unwrap_err();
+        assert!(matches!(error, ConfigError::InvalidValue { line: 1, .. }));
+    }
+
+    #[test]
+    fn environment_errors_name_the_variable() {
+        let env = |variable: &'static str, value: &'static str| {
+            from_vars(|name| (name == variable).then(|| value.to_owned())).unwrap_err()
+        };
+        let error = env("SOLAR_CLOCK_LAT", "95");
+        assert!(
+            matches!(
+                error,
+                ConfigError::Env {
+                    variable: "SOLAR_CLOCK_LAT",
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+        assert_eq!(
+            error.to_string(),
+            "environment variable SOLAR_CLOCK_LAT: latitude 95 is outside the range [-90, 90]"
+        );
+        let error = env("SOLAR_CLOCK_LON", "-181");
+        assert!(
+            matches!(
+                error,
+                ConfigError::Env {
+                    variable: "SOLAR_CLOCK_LON",
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+        let error = env("SOLAR_CLOCK_LON", "east");
+        assert!(
+            matches!(
+                error,
+                ConfigError::Env {
+                    variable: "SOLAR_CLOCK_LON",
+                    ..
+                }
+            ),
+            "{error:?}"
+        );
+    }
+}
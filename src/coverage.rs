@@ -0,0 +1,246 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Diagnostic checking that every solar wall time is shown exactly once per
+//! solar day.
+//!
+//! Targets far from the events they are pinned to can make the solar clock
+//! jump over some wall times, typically where the model for one civil date
+//! hands over to the next, or show them twice. [`check_coverage`] scans the
+//! civil instants around a solar day and counts how often each solar minute
+//! is reached.
+
+use std::fmt;
+
+use chrono::{Duration, NaiveDate, NaiveTime};
+
+use crate::error::SolarClockError;
+use crate::solar_clock::{SolarClock, get_target_time, to_seconds};
+
+const MINUTES_PER_DAY: usize = 24 * 60;
+
+/// Spacing of the civil samples. Folds shorter than this go unnoticed.
+const STEP: Duration = Duration::minutes(1);
+
+/// How a run of solar minutes fails to be covered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CoverageIssueKind {
+    /// The solar clock never shows these minutes.
+    Gap,
+    /// The solar clock shows these minutes more than once.
+    Overlap,
+}
+
+/// Run of consecutive solar minutes with the same coverage problem.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoverageIssue {
+    pub kind: CoverageIssueKind,
+    /// First affected solar minute.
+    pub first: NaiveTime,
+    /// Last affected solar minute, included.
+    pub last: NaiveTime,
+    /// Names of the anchors whose targets enclose the run, when the model of
+    /// the solar date has anchors on both sides of it.
+    pub segment: Option<(String, String)>,
+}
+
+impl fmt::Display for CoverageIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            CoverageIssueKind::Gap => "gap",
+            CoverageIssueKind::Overlap => "overlap",
+        };
+        write!(
+            f,
+            "{kind} {}–{}",
+            self.first.format("%H:%M"),
+            self.last.format("%H:%M")
+        )?;
+        if let Some((from, to)) = &self.segment {
+            write!(f, " (between {from} and {to})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that each minute of the solar day `date` has exactly one civil
+/// instant at which the solar clock shows it.
+///
+/// An empty result means full coverage. Jumps where the model of one civil
+/// date hands over to the next count as gaps, not as instants showing the
+/// skipped minutes.
+pub fn check_coverage(
+    clock: &SolarClock,
+    date: NaiveDate,
+) -> Result<Vec<CoverageIssue>, SolarClockError> {
+    let offset = clock.offset();
+    let first_minute = to_seconds(get_target_time(date, NaiveTime::MIN, offset));
+
+    // Civil samples over the civil dates around `date`, ending each date on
+    // its last nanosecond so that model handovers fall between two samples
+    let mut samples = Vec::new();
+    for day in [date - Duration::days(1), date, date + Duration::days(1)] {
+        let midnight = get_target_time(day, NaiveTime::MIN, offset);
+        let mut civil = midnight;
+        while civil < midnight + Duration::days(1) {
+            samples.push((
+                to_seconds(clock.solar_time(civil)?.solar_time.to_utc()),
+                false,
+            ));
+            civil += STEP;
+        }
+        let last = midnight + Duration::days(1) - Duration::nanoseconds(1);
+        samples.push((
+            to_seconds(clock.solar_time(last)?.solar_time.to_utc()),
+            true,
+        ));
+    }
+
+    // Index of the first solar minute at or after `seconds`, and strictly after
+    let at_or_after = |seconds: f64| ((seconds - first_minute) / 60.0).ceil();
+    let after = |seconds: f64| ((seconds - first_minute) / 60.0).floor() + 1.0;
+    let mut preimages = [0u32; MINUTES_PER_DAY];
+    for pair in samples.windows(2) {
+        let [(from, handover), (to, _)] = [pair[0], pair[1]];
+        if handover {
+            continue;
+        }
+        // Minutes `m` with `from <= m < to`, or `to < m <= from` on a fold
+        let (start, end) = if from <= to {
+            (at_or_after(from), at_or_after(to))
+        } else {
+            (after(to), after(from))
+        };
+        let start = start.clamp(0.0, MINUTES_PER_DAY as f64) as usize;
+        let end = end.clamp(0.0, MINUTES_PER_DAY as f64) as usize;
+        for count in &mut preimages[start..end] {
+            *count += 1;
+        }
+    }
+
+    let anchors = clock.model_anchors(date)?;
+    let mut issues: Vec<CoverageIssue> = Vec::new();
+    for (minute, &count) in preimages.iter().enumerate() {
+        let kind = match count {
+            1 => continue,
+            0 => CoverageIssueKind::Gap,
+            _ => CoverageIssueKind::Overlap,
+        };
+        let time = NaiveTime::MIN + Duration::minutes(minute as i64);
+        if let Some(issue) = issues.last_mut()
+            && issue.kind == kind
+            && issue.last + Duration::minutes(1) == time
+        {
+            issue.last = time;
+            continue;
+        }
+        let target = get_target_time(date, time, offset);
+        let previous = anchors.iter().rev().find(|anchor| anchor.target <= target);
+        let next = anchors.iter().find(|anchor| anchor.target > target);
+        issues.push(CoverageIssue {
+            kind,
+            first: time,
+            last: time,
+            segment: previous
+                .zip(next)
+                .map(|(a, b)| (a.name.clone(), b.name.clone())),
+        });
+    }
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::FixedOffset;
+
+    use super::*;
+    use crate::solar_clock::{DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET};
+    use crate::{Coordinates, SolarTargets};
+
+    fn madrid() -> Coordinates {
+        Coordinates::new(40.4168, -3.7038).unwrap()
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn segment(from: &str, to: &str) -> Option<(String, String)> {
+        Some((from.to_owned(), to.to_owned()))
+    }
+
+    #[test]
+    fn default_targets_cover_the_day() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        for date in [(2026, 2, 3), (2026, 6, 21), (2026, 12, 21)] {
+            let date = NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap();
+            assert_eq!(check_coverage(&clock, date).unwrap(), []);
+        }
+    }
+
+    #[test]
+    fn far_targets_skip_or_repeat_minutes() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+
+        // A wall time 14 hours ahead of the sun moves the handover between
+        // the models of two dates into the morning, where they disagree
+        let ahead = SolarClock::new(
+            madrid(),
+            DEFAULT_TARGETS,
+            FixedOffset::east_opt(14 * 3600).unwrap(),
+        );
+        let issues = check_coverage(&ahead, date).unwrap();
+        let gap = issues
+            .iter()
+            .find(|issue| issue.kind == CoverageIssueKind::Gap)
+            .unwrap_or_else(|| panic!("{issues:?}"));
+        assert!(gap.first <= gap.last, "{gap}");
+        assert!(time(8, 0) < gap.first && gap.last < time(14, 0), "{gap}");
+        assert_eq!(gap.segment, segment("sunrise", "transit"));
+        assert!(gap.to_string().starts_with("gap "), "{gap}");
+
+        // 14 hours behind in June, the models disagree the other way in the
+        // afternoon and the minutes come round twice
+        let behind = SolarClock::new(
+            madrid(),
+            DEFAULT_TARGETS,
+            FixedOffset::west_opt(14 * 3600).unwrap(),
+        );
+        let june = NaiveDate::from_ymd_opt(2026, 6, 21).unwrap();
+        let issues = check_coverage(&behind, june).unwrap();
+        let overlap = issues
+            .iter()
+            .find(|issue| issue.kind == CoverageIssueKind::Overlap)
+            .unwrap_or_else(|| panic!("{issues:?}"));
+        assert!(
+            time(12, 0) < overlap.first && overlap.last < time(18, 0),
+            "{overlap}"
+        );
+        assert_eq!(overlap.segment, segment("transit", "sunset"));
+
+        // Sunset pinned to 23:30 and sunrise to 00:30 would run a cubic
+        // backwards around midnight, so the clock falls back to straight
+        // segments and still shows every minute once
+        let late = SolarTargets::new(time(0, 30), time(12, 0), time(23, 30)).unwrap();
+        let clock = SolarClock::new(madrid(), late, SOLAR_TIMEZONE_OFFSET);
+        assert_eq!(check_coverage(&clock, date).unwrap(), []);
+    }
+}
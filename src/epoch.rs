@@ -0,0 +1,561 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Delta evaluation on plain Unix seconds, using only `core`.
+//!
+//! This is the part of the solar clock that runs without `std`, allocation or
+//! chrono, for instance on a microcontroller: the interpolation kernels shared
+//! with [`Interpolator`](crate::interp::Interpolator), and [`EpochModel`],
+//! which evaluates the delta from a fixed-size array of anchors. The anchors
+//! themselves need the solar position and are computed with `std`, e.g. on a
+//! host from [`Anchor::point`](crate::Anchor::point).
+//!
+//! Instants are Unix seconds as `f64`, exact to about a microsecond for
+//! current dates.
+
+use crate::error::InterpError;
+
+/// Interpolation between anchors, which shapes how fast the solar clock runs
+/// in between. All methods pass exactly through every anchor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum InterpolationMethod {
+    /// Monotone piecewise cubic (Fritsch–Carlson): no overshoot between
+    /// anchors and a continuous rate.
+    #[default]
+    Pchip,
+    /// Straight segments: a constant rate between anchors that jumps at each
+    /// of them.
+    Linear,
+    /// Akima cubic: a continuous rate that follows the anchors more closely
+    /// than PCHIP but may overshoot.
+    Akima,
+}
+
+impl InterpolationMethod {
+    /// Lowercase name, as used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Pchip => "pchip",
+            Self::Linear => "linear",
+            Self::Akima => "akima",
+        }
+    }
+}
+
+/// Interpolation anchor: `x` is the real event instant as Unix seconds and `y`
+/// the delta in seconds that moves it onto its target wall time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Interpolated delta over at most `N` anchors, stored inline.
+///
+/// Mirrors the model of [`SolarClock`](crate::SolarClock) for the same
+/// anchors: a cubic whose slopes overflow, or whose solar time would run
+/// backwards between the anchors, degrades to linear interpolation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpochModel<const N: usize> {
+    method: InterpolationMethod,
+    len: usize,
+    xs: [f64; N],
+    ys: [f64; N],
+    slopes: [f64; N],
+}
+
+impl<const N: usize> EpochModel<N> {
+    /// Model through `points`, with the requirements of [`check_points`] and
+    /// at most `N` of them.
+    pub fn new(method: InterpolationMethod, points: &[Point]) -> Result<Self, InterpError> {
+        let len = points.len();
+        if len > N {
+            return Err(InterpError::TooManyPoints {
+                count: len,
+                capacity: N,
+            });
+        }
+        let (mut xs, mut ys) = ([0.0; N], [0.0; N]);
+        for (i, point) in points.iter().enumerate() {
+            xs[i] = point.x;
+            ys[i] = point.y;
+        }
+        check_points(&xs[..len], &ys[..len])?;
+
+        let mut slopes = [0.0; N];
+        let method = match compute_slopes(method, &xs[..len], &ys[..len], &mut slopes[..len]) {
+            Ok(()) if min_derivative(method, &xs[..len], &ys[..len], &slopes[..len]) > -1.0 => {
+                method
+            }
+            Ok(()) | Err(InterpError::NonFiniteSlope(_)) => InterpolationMethod::Linear,
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            method,
+            len,
+            xs,
+            ys,
+            slopes,
+        })
+    }
+
+    /// Interpolation actually used, see [`new`](Self::new).
+    pub fn method(&self) -> InterpolationMethod {
+        self.method
+    }
+
+    /// Real instants of the first and last anchors, the range the model
+    /// covers.
+    pub fn range(&self) -> (f64, f64) {
+        (self.xs[0], self.xs[self.len - 1])
+    }
+
+    /// Seconds added to the civil instant `epoch` to obtain the solar time,
+    /// or `None` outside [`range`](Self::range).
+    pub fn delta(&self, epoch: f64) -> Option<f64> {
+        let (xs, ys, slopes) = self.parts();
+        evaluate(self.method, xs, ys, slopes, epoch)
+    }
+
+    /// Solar instant of the civil instant `epoch`, in Unix seconds.
+    pub fn solar_time(&self, epoch: f64) -> Option<f64> {
+        Some(epoch + self.delta(epoch)?)
+    }
+
+    /// Solar seconds per civil second at `epoch`.
+    pub fn rate(&self, epoch: f64) -> Option<f64> {
+        let (xs, ys, slopes) = self.parts();
+        Some(1.0 + derivative(self.method, xs, ys, slopes, epoch)?)
+    }
+
+    fn parts(&self) -> (&[f64], &[f64], &[f64]) {
+        let len = self.len;
+        (&self.xs[..len], &self.ys[..len], &self.slopes[..len])
+    }
+}
+
+/// Checks the points `(xs[i], ys[i])` of an interpolant: at least two, as
+/// many `ys` as `xs`, all finite, and `xs` strictly increasing.
+pub fn check_points(xs: &[f64], ys: &[f64]) -> Result<(), InterpError> {
+    let n = xs.len();
+    if n < 2 {
+        return Err(InterpError::TooFewPoints(n));
+    }
+    if ys.len() != n {
+        return Err(InterpError::LengthMismatch {
+            xs: n,
+            ys: ys.len(),
+        });
+    }
+    if let Some(index) = xs.iter().chain(ys).position(|v| !v.is_finite()) {
+        return Err(InterpError::NotFinite(index % n));
+    }
+    if let Some(index) = xs.windows(2).position(|w| w[0] >= w[1]) {
+        return Err(InterpError::NotIncreasing(index + 1));
+    }
+    Ok(())
+}
+
+/// Writes the slopes of the cubic interpolant at each point into `slopes`,
+/// which must be as long as `xs`. Linear interpolation has no slopes and
+/// leaves it untouched.
+///
+/// Points extremely close together relative to their spread overflow the
+/// secants, which fails with [`InterpError::NonFiniteSlope`].
+pub fn compute_slopes(
+    method: InterpolationMethod,
+    xs: &[f64],
+    ys: &[f64],
+    slopes: &mut [f64],
+) -> Result<(), InterpError> {
+    match method {
+        InterpolationMethod::Linear => return Ok(()),
+        InterpolationMethod::Pchip => pchip_slopes(xs, ys, slopes),
+        InterpolationMethod::Akima => akima_slopes(xs, ys, slopes),
+    }
+    match slopes.iter().position(|slope| !slope.is_finite()) {
+        Some(index) => Err(InterpError::NonFiniteSlope(index)),
+        None => Ok(()),
+    }
+}
+
+/// Index of the segment containing `x`, or `None` outside the point range.
+pub fn segment(xs: &[f64], x: f64) -> Option<usize> {
+    let n = xs.len();
+    if !(xs[0]..=xs[n - 1]).contains(&x) {
+        return None;
+    }
+    Some(xs.partition_point(|&xi| xi <= x).clamp(1, n - 1) - 1)
+}
+
+/// Evaluates the interpolant, returning `None` outside the point range.
+pub fn evaluate(
+    method: InterpolationMethod,
+    xs: &[f64],
+    ys: &[f64],
+    slopes: &[f64],
+    x: f64,
+) -> Option<f64> {
+    let k = segment(xs, x)?;
+    let h = xs[k + 1] - xs[k];
+    let t = (x - xs[k]) / h;
+    if method == InterpolationMethod::Linear {
+        return Some(ys[k] + t * (ys[k + 1] - ys[k]));
+    }
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + t;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+
+    Some(h00 * ys[k] + h10 * h * slopes[k] + h01 * ys[k + 1] + h11 * h * slopes[k + 1])
+}
+
+/// Derivative of the interpolant, returning `None` outside the point range.
+/// At an interior point it is the derivative of the segment on its right,
+/// which only matters for linear interpolation.
+pub fn derivative(
+    method: InterpolationMethod,
+    xs: &[f64],
+    ys: &[f64],
+    slopes: &[f64],
+    x: f64,
+) -> Option<f64> {
+    let k = segment(xs, x)?;
+    let h = xs[k + 1] - xs[k];
+    if method == InterpolationMethod::Linear {
+        return Some((ys[k + 1] - ys[k]) / h);
+    }
+    let t = (x - xs[k]) / h;
+    let t2 = t * t;
+
+    let d00 = 6.0 * t2 - 6.0 * t;
+    let d10 = 3.0 * t2 - 4.0 * t + 1.0;
+    let d01 = -6.0 * t2 + 6.0 * t;
+    let d11 = 3.0 * t2 - 2.0 * t;
+
+    Some((d00 * ys[k] + d01 * ys[k + 1]) / h + d10 * slopes[k] + d11 * slopes[k + 1])
+}
+
+/// Smallest derivative of the interpolant over the whole point range.
+///
+/// On each segment the derivative of the cubic is a quadratic in the
+/// normalised position, so its minimum is at an end or at the vertex.
+pub fn min_derivative(method: InterpolationMethod, xs: &[f64], ys: &[f64], slopes: &[f64]) -> f64 {
+    let mut min = f64::INFINITY;
+    for k in 0..xs.len() - 1 {
+        let secant = (ys[k + 1] - ys[k]) / (xs[k + 1] - xs[k]);
+        if method == InterpolationMethod::Linear {
+            min = min.min(secant);
+            continue;
+        }
+        let (m0, m1) = (slopes[k], slopes[k + 1]);
+        min = min.min(m0).min(m1);
+        // a t² + b t + m0, with a vertex inside the segment when a > 0
+        let a = 3.0 * (m0 + m1 - 2.0 * secant);
+        let b = 6.0 * secant - 4.0 * m0 - 2.0 * m1;
+        if a > 0.0 && (0.0..1.0).contains(&(-b / (2.0 * a))) {
+            min = min.min(m0 - b * b / (4.0 * a));
+        }
+    }
+    min
+}
+
+/// Fritsch–Carlson slopes, which preserve the monotonicity of the data.
+fn pchip_slopes(xs: &[f64], ys: &[f64], slopes: &mut [f64]) {
+    let n = xs.len();
+    let h = |k: usize| xs[k + 1] - xs[k];
+    let secant = |k: usize| (ys[k + 1] - ys[k]) / h(k);
+    if n == 2 {
+        slopes.fill(secant(0));
+        return;
+    }
+    for (k, slope) in slopes.iter_mut().enumerate().take(n - 1).skip(1) {
+        // Flat at local extrema, weighted harmonic mean otherwise
+        *slope = if secant(k - 1) * secant(k) > 0.0 {
+            let w1 = 2.0 * h(k) + h(k - 1);
+            let w2 = h(k) + 2.0 * h(k - 1);
+            (w1 + w2) / (w1 / secant(k - 1) + w2 / secant(k))
+        } else {
+            0.0
+        };
+    }
+    slopes[0] = end_slope(h(0), h(1), secant(0), secant(1));
+    slopes[n - 1] = end_slope(h(n - 2), h(n - 3), secant(n - 2), secant(n - 3));
+}
+
+/// Akima slopes: each is a mean of the neighbouring secants weighted by how
+/// much the secants change on the far side, which limits the influence of
+/// outlying anchors. Two secants are extrapolated linearly beyond each end.
+fn akima_slopes(xs: &[f64], ys: &[f64], slopes: &mut [f64]) {
+    let n = xs.len();
+    let secant = |k: usize| (ys[k + 1] - ys[k]) / (xs[k + 1] - xs[k]);
+    if n == 2 {
+        slopes.fill(secant(0));
+        return;
+    }
+    // Secant `j - 2`, extended by two on each side
+    let m = |j: usize| match j {
+        0 => 3.0 * secant(0) - 2.0 * secant(1),
+        1 => 2.0 * secant(0) - secant(1),
+        j if j == n + 1 => 2.0 * secant(n - 2) - secant(n - 3),
+        j if j == n + 2 => 3.0 * secant(n - 2) - 2.0 * secant(n - 3),
+        j => secant(j - 2),
+    };
+
+    // Slope `k` lies between the extended secants `k + 1` and `k + 2`
+    for (k, slope) in slopes.iter_mut().enumerate() {
+        let w1 = (m(k + 3) - m(k + 2)).abs();
+        let w2 = (m(k + 1) - m(k)).abs();
+        *slope = if w1 + w2 == 0.0 {
+            0.5 * (m(k + 1) + m(k + 2))
+        } else {
+            (w1 * m(k + 1) + w2 * m(k + 2)) / (w1 + w2)
+        };
+    }
+}
+
+/// One-sided three-point slope estimate for the end points, clamped so the
+/// interpolant does not overshoot.
+fn end_slope(h0: f64, h1: f64, s0: f64, s1: f64) -> f64 {
+    let d = ((2.0 * h0 + h1) * s0 - h0 * s1) / (h0 + h1);
+    if d.signum() != s0.signum() {
+        0.0
+    } else if s0.signum() != s1.signum() && d.abs() > 3.0 * s0.abs() {
+        3.0 * s0
+    } else {
+        d
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METHODS: [InterpolationMethod; 3] = [
+        InterpolationMethod::Pchip,
+        InterpolationMethod::Linear,
+        InterpolationMethod::Akima,
+    ];
+
+    /// Sunrise, transit and sunset anchors of two February days, about as
+    /// at Madrid.
+    const ANCHORS: [Point; 6] = [
+        Point {
+            x: 1_770_103_980.0,
+            y: -4_380.0,
+        },
+        Point {
+            x: 1_770_120_960.0,
+            y: -3_360.0,
+        },
+        Point {
+            x: 1_770_137_940.0,
+            y: -2_340.0,
+        },
+        Point {
+            x: 1_770_190_320.0,
+            y: -4_320.0,
+        },
+        Point {
+            x: 1_770_207_360.0,
+            y: -3_360.0,
+        },
+        Point {
+            x: 1_770_224_460.0,
+            y: -2_460.0,
+        },
+    ];
+
+    #[test]
+    fn epoch_model_passes_through_its_anchors() {
+        for method in METHODS {
+            let model = EpochModel::<8>::new(method, &ANCHORS).unwrap();
+            assert_eq!(model.method(), method);
+            assert_eq!(model.range(), (ANCHORS[0].x, ANCHORS[5].x));
+            for point in ANCHORS {
+                let delta = model.delta(point.x).unwrap();
+                assert!((delta - point.y).abs() < 1e-6, "{method:?}: {delta}");
+                assert_eq!(model.solar_time(point.x), Some(point.x + delta));
+            }
+            // The solar clock runs forwards, and not wildly off the civil one
+            let (start, end) = model.range();
+            let mut x = start;
+            while x < end {
+                let rate = model.rate(x).unwrap();
+                assert!((0.5..1.5).contains(&rate), "{method:?} at {x}: {rate}");
+                x += 600.0;
+            }
+            assert_eq!(model.delta(start - 1.0), None);
+            assert_eq!(model.delta(end + 1.0), None);
+            assert_eq!(model.rate(end + 1.0), None);
+        }
+    }
+
+    #[test]
+    fn epoch_model_rejects_bad_points() {
+        let pchip = InterpolationMethod::Pchip;
+        assert_eq!(
+            EpochModel::<4>::new(pchip, &ANCHORS),
+            Err(InterpError::TooManyPoints {
+                count: 6,
+                capacity: 4
+            })
+        );
+        assert_eq!(
+            EpochModel::<4>::new(pchip, &ANCHORS[..1]),
+            Err(InterpError::TooFewPoints(1))
+        );
+        let mut points = ANCHORS;
+        points[3].x = points[2].x;
+        assert_eq!(
+            EpochModel::<8>::new(pchip, &points),
+            Err(InterpError::NotIncreasing(3))
+        );
+        points[3].y = f64::NAN;
+        assert_eq!(
+            EpochModel::<8>::new(pchip, &points),
+            Err(InterpError::NotFinite(3))
+        );
+    }
+
+    #[test]
+    fn epoch_model_falls_back_to_linear() {
+        // Solar time would run backwards between the second and third
+        // anchors, which are ten minutes apart with the delta falling by
+        // almost as much
+        let backwards = [
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 600.0, y: 0.0 },
+            Point {
+                x: 1_200.0,
+                y: -590.0,
+            },
+            Point {
+                x: 43_200.0,
+                y: -590.0,
+            },
+        ];
+        // And two anchors a subnormal apart overflow the secant
+        let overflow = [
+            Point { x: 0.0, y: 0.0 },
+            Point { x: 1e-320, y: 1.0 },
+            Point { x: 1.0, y: 2.0 },
+        ];
+        for method in [InterpolationMethod::Pchip, InterpolationMethod::Akima] {
+            for points in [&backwards[..], &overflow[..]] {
+                let model = EpochModel::<4>::new(method, points).unwrap();
+                assert_eq!(model.method(), InterpolationMethod::Linear);
+            }
+        }
+        let model = EpochModel::<4>::new(InterpolationMethod::Pchip, &backwards).unwrap();
+        assert_eq!(model.delta(900.0), Some(-295.0));
+        assert_eq!(model.rate(900.0), Some(1.0 - 590.0 / 600.0));
+    }
+
+    #[test]
+    fn pchip_slopes_are_flat_at_extrema_and_keep_monotone_data_monotone() {
+        let xs = [0.0, 1.0, 2.0, 4.0, 5.0];
+        let mut slopes = [f64::NAN; 5];
+
+        // Interior extrema get flat slopes
+        let zigzag = [0.0, 1.0, 0.0, 1.0, 0.0];
+        pchip_slopes(&xs, &zigzag, &mut slopes);
+        assert_eq!(slopes[1..4], [0.0; 3]);
+
+        // Otherwise, the weighted harmonic mean of the secants: 1 and 2 on
+        // equal steps give 4/3
+        let rising = [0.0, 1.0, 3.0, 4.0, 10.0];
+        pchip_slopes(&xs, &rising, &mut slopes);
+        assert!((slopes[1] - 4.0 / 3.0).abs() < 1e-12);
+        assert!(slopes.iter().all(|&slope| slope >= 0.0));
+        let min = min_derivative(InterpolationMethod::Pchip, &xs, &rising, &slopes);
+        assert!(min >= 0.0, "{min}");
+        let mut x = 0.0;
+        while x <= 5.0 {
+            let derivative = derivative(InterpolationMethod::Pchip, &xs, &rising, &slopes, x);
+            assert!(derivative.unwrap() >= min);
+            x += 0.01;
+        }
+
+        // Two points share one straight line
+        let mut two = [f64::NAN; 2];
+        pchip_slopes(&[0.0, 2.0], &[1.0, 2.0], &mut two);
+        assert_eq!(two, [0.5; 2]);
+    }
+
+    #[test]
+    fn end_slopes_do_not_overshoot() {
+        // The three-point estimate when it agrees with the first secant
+        assert_eq!(end_slope(1.0, 1.0, 1.0, 2.0), 0.5);
+        // Flat when it points the other way
+        assert_eq!(end_slope(1.0, 1.0, 1.0, 4.0), 0.0);
+        assert_eq!(end_slope(1.0, 1.0, -1.0, -4.0), 0.0);
+        // Capped at three secants past an extremum
+        assert_eq!(end_slope(1.0, 1.0, 1.0, -1.0), 2.0);
+        assert_eq!(end_slope(1.0, 1.0, 1.0, -5.0), 3.0);
+        assert_eq!(end_slope(1.0, 1.0, -1.0, 5.0), -3.0);
+        // Unequal steps weight the nearer secant
+        assert_eq!(end_slope(1.0, 3.0, 2.0, 1.0), 2.25);
+    }
+
+    #[test]
+    fn min_derivative_finds_the_dip_between_anchors() {
+        // Steep at both ends of a unit secant, so the cubic flattens to 0 in
+        // the middle
+        let (xs, ys, slopes) = ([0.0, 1.0], [0.0, 1.0], [3.0, 3.0]);
+        let dip = derivative(InterpolationMethod::Pchip, &xs, &ys, &slopes, 0.5);
+        assert_eq!(dip, Some(0.0));
+        assert_eq!(
+            min_derivative(InterpolationMethod::Pchip, &xs, &ys, &slopes),
+            0.0
+        );
+        // Flat at both ends, the middle is a maximum
+        assert_eq!(
+            min_derivative(InterpolationMethod::Pchip, &xs, &ys, &[0.0, 0.0]),
+            0.0
+        );
+        // Linear interpolation has only its secants
+        let (xs, ys) = ([0.0, 1.0, 3.0], [0.0, 2.0, 1.0]);
+        assert_eq!(
+            min_derivative(InterpolationMethod::Linear, &xs, &ys, &[]),
+            -0.5
+        );
+
+        // Akima on the Madrid anchors, against a dense sampling
+        let (mut xs, mut ys, mut slopes) = ([0.0; 6], [0.0; 6], [0.0; 6]);
+        for (i, point) in ANCHORS.iter().enumerate() {
+            (xs[i], ys[i]) = (point.x, point.y);
+        }
+        let akima = InterpolationMethod::Akima;
+        compute_slopes(akima, &xs, &ys, &mut slopes).unwrap();
+        let min = min_derivative(akima, &xs, &ys, &slopes);
+        let mut sampled = f64::INFINITY;
+        let mut x = xs[0];
+        while x < xs[5] {
+            sampled = sampled.min(derivative(akima, &xs, &ys, &slopes, x).unwrap());
+            x += 60.0;
+        }
+        assert!(min <= sampled && sampled - min < 1e-6, "{min} {sampled}");
+    }
+}
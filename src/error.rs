@@ -0,0 +1,414 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Error types of the solar clock.
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use chrono::{DateTime, NaiveTime, Utc};
+
+/// Reasons why the solar clock cannot be evaluated.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolarClockError {
+    /// The solar position algorithm rejected the inputs (e.g. coordinates out
+    /// of range).
+    SolarPositionFailed,
+    /// Fewer than two anchors were found in the window, which happens when the
+    /// solar events cannot be computed for the surrounding days.
+    InsufficientAnchors { count: usize },
+    /// The anchors include a sunrise or a sunset, but the sun neither rises
+    /// nor sets within `window_days` of the date, the widest window, as in
+    /// polar day or night.
+    /// [`ModelOptions::polar_fallback`](crate::ModelOptions::polar_fallback)
+    /// anchors on the transits instead.
+    NoSunriseOrSunset { window_days: u32 },
+    /// The numerical inversion of the solar clock did not converge.
+    InversionDidNotConverge,
+    /// The anchors were rejected by the interpolator, e.g. because two of
+    /// them share a real time.
+    Interpolation(InterpError),
+    /// Consecutive anchors have decreasing targets, so the solar clock would
+    /// run backwards. `earlier` is the target of the anchor that happens
+    /// first and `later` the target of the one after it.
+    NonMonotonic {
+        earlier: DateTime<Utc>,
+        later: DateTime<Utc>,
+    },
+    /// The interpolant produced a non-finite value at `input`, although it
+    /// lies within the anchors.
+    EvaluationFailed { input: DateTime<Utc> },
+    /// The input lies outside the range covered by the anchors, from the
+    /// real time of the first anchor to that of the last. Models are widened
+    /// until they enclose the whole date evaluated, so this only happens when
+    /// [`ModelOptions::max_window_days`](crate::ModelOptions::max_window_days)
+    /// is reached or for ranges beyond the anchors in
+    /// [`SolarClock::sample_delta`](crate::SolarClock::sample_delta).
+    OutOfRange {
+        earliest: DateTime<Utc>,
+        latest: DateTime<Utc>,
+    },
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for SolarClockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SolarPositionFailed => {
+                write!(f, "solar position algorithm rejected the input")
+            }
+            Self::InsufficientAnchors { count } => write!(
+                f,
+                "insufficient solar data: {count} anchor(s) found, at least 2 required"
+            ),
+            Self::NoSunriseOrSunset { window_days } => write!(
+                f,
+                "no sunrise or sunset within {window_days} day(s) of the date (polar day or night)"
+            ),
+            Self::InversionDidNotConverge => {
+                write!(f, "numerical inversion of the solar clock did not converge")
+            }
+            Self::Interpolation(e) => write!(f, "invalid interpolation anchors: {e}"),
+            Self::NonMonotonic { earlier, later } => write!(
+                f,
+                "solar time would run backwards: target {later} follows target {earlier}"
+            ),
+            Self::EvaluationFailed { input } => {
+                write!(f, "interpolation produced no finite delta at {input}")
+            }
+            Self::OutOfRange { earliest, latest } => write!(
+                f,
+                "instant outside the interpolation window ({earliest} to {latest})"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SolarClockError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Interpolation(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Coordinates rejected by [`Coordinates::new`](crate::Coordinates::new).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoordError {
+    /// Latitude outside `[-90, 90]` degrees.
+    InvalidLatitude(f64),
+    /// Longitude outside `[-180, 180]` degrees.
+    InvalidLongitude(f64),
+}
+
+impl fmt::Display for CoordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLatitude(lat) => {
+                write!(f, "latitude {lat} is outside the range [-90, 90]")
+            }
+            Self::InvalidLongitude(lon) => {
+                write!(f, "longitude {lon} is outside the range [-180, 180]")
+            }
+        }
+    }
+}
+
+impl core::error::Error for CoordError {}
+
+/// Targets rejected by [`SolarTargets::new`](crate::SolarTargets::new).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetsError {
+    /// `later` is not strictly after `earlier`, although its event happens
+    /// later in the day.
+    Unordered {
+        earlier: NaiveTime,
+        later: NaiveTime,
+    },
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for TargetsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unordered { earlier, later } => write!(
+                f,
+                "target {later} must be after {earlier} (midnight < sunrise < transit < sunset)"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TargetsError {}
+
+/// Failure of an entry point for another date-time type, such as
+/// [`SolarClock::solar_time_from_system`](crate::SolarClock::solar_time_from_system).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InteropError {
+    SolarClock(SolarClockError),
+    /// The instant or offset cannot be represented on the other side.
+    OutOfRange,
+    /// `SystemTime` instants before 1970 are not supported.
+    BeforeEpoch,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for InteropError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SolarClock(e) => e.fmt(f),
+            Self::OutOfRange => write!(f, "instant or offset outside the supported range"),
+            Self::BeforeEpoch => write!(f, "instant before the Unix epoch"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InteropError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::SolarClock(e) => Some(e),
+            Self::OutOfRange | Self::BeforeEpoch => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<SolarClockError> for InteropError {
+    fn from(e: SolarClockError) -> Self {
+        Self::SolarClock(e)
+    }
+}
+
+/// Failure of [`SolarClock::save_model`](crate::SolarClock::save_model) or
+/// [`SolarClock::load_model`](crate::SolarClock::load_model).
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ModelFileError {
+    Io(std::io::Error),
+    /// Not a model file, or a truncated or corrupted one.
+    Malformed,
+    /// Written in a format version that this build cannot read.
+    UnsupportedVersion(u16),
+    /// Saved for another configuration: the setting that differs.
+    Mismatch(&'static str),
+    SolarClock(SolarClockError),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ModelFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::Malformed => write!(f, "not a valid model file"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "model file format version {version} is not supported (expected {})",
+                crate::model_file::FORMAT_VERSION
+            ),
+            Self::Mismatch(setting) => {
+                write!(f, "model file was saved with different {setting}")
+            }
+            Self::SolarClock(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ModelFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::SolarClock(e) => Some(e),
+            Self::Malformed | Self::UnsupportedVersion(_) | Self::Mismatch(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ModelFileError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<SolarClockError> for ModelFileError {
+    fn from(e: SolarClockError) -> Self {
+        Self::SolarClock(e)
+    }
+}
+
+/// Curve rejected by [`CctCurve::new`](crate::CctCurve::new).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CctError {
+    /// Colour temperature that is not a positive number of kelvin.
+    InvalidKelvin(f64),
+    /// Ramp bound `later` is not strictly after `earlier`.
+    Unordered {
+        earlier: NaiveTime,
+        later: NaiveTime,
+    },
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for CctError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidKelvin(kelvin) => {
+                write!(f, "colour temperature {kelvin} K is not positive")
+            }
+            Self::Unordered { earlier, later } => write!(
+                f,
+                "ramp bound {later} must be after {earlier} (morning start < morning end < evening start < evening end)"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CctError {}
+
+/// Invalid configuration reported by [`SolarClockBuilder::build`](crate::SolarClockBuilder::build).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BuildError {
+    Coordinates(CoordError),
+    Targets(TargetsError),
+    /// The window must include at least one day on each side.
+    InvalidWindow(u32),
+    /// The zenith of sunrise and sunset must be strictly between 0 and 180
+    /// degrees.
+    InvalidZenith(f64),
+    /// The observer elevation must be a finite, non-negative number of
+    /// metres.
+    InvalidElevation(f64),
+    /// The air temperature must be a finite number of °C above absolute
+    /// zero.
+    InvalidTemperature(f64),
+    /// The air pressure must be a finite, positive number of hPa.
+    InvalidPressure(f64),
+    /// ΔT must be a finite number of seconds.
+    InvalidDeltaT(f64),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Coordinates(e) => e.fmt(f),
+            Self::Targets(e) => e.fmt(f),
+            Self::InvalidWindow(days) => {
+                write!(
+                    f,
+                    "window of {days} day(s) is too small, at least 1 required"
+                )
+            }
+            Self::InvalidZenith(zenith) => {
+                write!(f, "zenith {zenith} is outside the range (0, 180)")
+            }
+            Self::InvalidElevation(metres) => {
+                write!(f, "elevation {metres} m is not a non-negative number")
+            }
+            Self::InvalidTemperature(celsius) => {
+                write!(f, "temperature {celsius} °C is not above absolute zero")
+            }
+            Self::InvalidPressure(hpa) => write!(f, "pressure {hpa} hPa is not positive"),
+            Self::InvalidDeltaT(seconds) => write!(f, "ΔT {seconds} s is not a finite number"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Coordinates(e) => Some(e),
+            Self::Targets(e) => Some(e),
+            Self::InvalidWindow(_)
+            | Self::InvalidZenith(_)
+            | Self::InvalidElevation(_)
+            | Self::InvalidTemperature(_)
+            | Self::InvalidPressure(_)
+            | Self::InvalidDeltaT(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<CoordError> for BuildError {
+    fn from(e: CoordError) -> Self {
+        Self::Coordinates(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<TargetsError> for BuildError {
+    fn from(e: TargetsError) -> Self {
+        Self::Targets(e)
+    }
+}
+
+/// Points rejected by [`Interpolator::new`](crate::interp::Interpolator::new)
+/// and [`EpochModel::new`](crate::epoch::EpochModel::new).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterpError {
+    /// Fewer than two points.
+    TooFewPoints(usize),
+    /// More points than an [`EpochModel`](crate::epoch::EpochModel) holds.
+    TooManyPoints { count: usize, capacity: usize },
+    /// `xs` and `ys` have different lengths.
+    LengthMismatch { xs: usize, ys: usize },
+    /// The point at this index has a NaN or infinite coordinate.
+    NotFinite(usize),
+    /// `xs` does not increase strictly at this index.
+    NotIncreasing(usize),
+    /// The slope of a cubic interpolant at this point overflowed. Linear
+    /// interpolation of the same points does not fail.
+    NonFiniteSlope(usize),
+}
+
+impl fmt::Display for InterpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewPoints(count) => {
+                write!(f, "{count} point(s) given, at least 2 required")
+            }
+            Self::TooManyPoints { count, capacity } => {
+                write!(f, "{count} points given, at most {capacity} held")
+            }
+            Self::LengthMismatch { xs, ys } => {
+                write!(f, "{xs} abscissae but {ys} ordinates")
+            }
+            Self::NotFinite(index) => write!(f, "point {index} is not finite"),
+            Self::NotIncreasing(index) => {
+                write!(f, "abscissa {index} does not increase strictly")
+            }
+            Self::NonFiniteSlope(index) => write!(f, "slope at point {index} is not finite"),
+        }
+    }
+}
+
+impl core::error::Error for InterpError {}
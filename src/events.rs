@@ -0,0 +1,191 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Enumeration of the daily solar events over arbitrary time ranges.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::solar_clock::Coordinates;
+use crate::spa::{self, SpaError};
+
+/// Span searched by [`next_event`] and [`previous_event`]. Transits occur
+/// every day, so an event lies within it.
+const SEARCH: Duration = Duration::days(2);
+
+/// Kind of a daily solar event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum EventKind {
+    Sunrise,
+    Transit,
+    Sunset,
+}
+
+impl EventKind {
+    /// Lowercase name, as used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sunrise => "sunrise",
+            Self::Transit => "transit",
+            Self::Sunset => "sunset",
+        }
+    }
+}
+
+/// A solar event and the instant it occurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarEvent {
+    pub kind: EventKind,
+    pub time: DateTime<Utc>,
+}
+
+/// Sunrises, transits and sunsets between `start` and `end`, both inclusive,
+/// in chronological order.
+///
+/// Events that do not occur, such as sunrise and sunset during polar night,
+/// are skipped; transits always occur. When a day cannot be computed its
+/// error is yielded alone.
+pub fn events_between(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    coords: &Coordinates,
+) -> impl Iterator<Item = Result<SolarEvent, SpaError>> {
+    let events = collect_between(start, end, coords);
+    let (events, error) = match events {
+        Ok(events) => (events, None),
+        Err(e) => (Vec::new(), Some(e)),
+    };
+    events.into_iter().map(Ok).chain(error.map(Err))
+}
+
+/// Events of [`events_between`], sorted and without duplicates.
+fn collect_between(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    coords: &Coordinates,
+) -> Result<Vec<SolarEvent>, SpaError> {
+    if start > end {
+        return Ok(Vec::new());
+    }
+    // Events of a UTC day can spill into the neighbouring days at far
+    // longitudes, so look one day beyond each end
+    let first = start.date_naive() - Duration::days(1);
+    let last = end.date_naive() + Duration::days(1);
+
+    let mut events = Vec::new();
+    for date in first.iter_days().take_while(|date| *date <= last) {
+        events.extend(
+            day_events(date, coords)?
+                .into_iter()
+                .filter(|event| event.time >= start && event.time <= end),
+        );
+    }
+    // Neighbouring days may yield their events out of order, or the same
+    // event twice
+    events.sort_by_key(|event| event.time);
+    events.dedup();
+    Ok(events)
+}
+
+/// First event strictly after `after`, or `None` if there is none within
+/// two days.
+pub fn next_event(
+    after: DateTime<Utc>,
+    coords: &Coordinates,
+) -> Result<Option<SolarEvent>, SpaError> {
+    let start = after + Duration::nanoseconds(1);
+    events_between(start, after + SEARCH, coords)
+        .next()
+        .transpose()
+}
+
+/// Last event strictly before `before`, or `None` if there is none within
+/// two days.
+pub fn previous_event(
+    before: DateTime<Utc>,
+    coords: &Coordinates,
+) -> Result<Option<SolarEvent>, SpaError> {
+    let end = before - Duration::nanoseconds(1);
+    events_between(before - SEARCH, end, coords)
+        .last()
+        .transpose()
+}
+
+/// Events computed for the UTC day `date`, sorted by time.
+fn day_events(date: NaiveDate, coords: &Coordinates) -> Result<Vec<SolarEvent>, SpaError> {
+    let data = spa::calculate_solar_data(date, coords)?;
+    let mut events: Vec<SolarEvent> = [
+        (EventKind::Sunrise, data.sunrise),
+        (EventKind::Transit, Some(data.transit)),
+        (EventKind::Sunset, data.sunset),
+    ]
+    .into_iter()
+    .filter_map(|(kind, time)| time.map(|time| SolarEvent { kind, time }))
+    .collect();
+    events.sort_by_key(|event| event.time);
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_between_keeps_every_event_in_order() {
+        let start: DateTime<Utc> = "2026-05-01T00:00:00Z".parse().unwrap();
+        let end: DateTime<Utc> = "2026-06-15T00:00:00Z".parse().unwrap();
+        // Tromsø around the start of the midnight sun, and both sides of
+        // the antimeridian, where a day's events spill into the next
+        for (latitude, longitude) in [(69.65, 18.96), (-14.0, 179.9), (64.7, -179.9)] {
+            let coords = Coordinates::new(latitude, longitude).unwrap();
+            let events: Vec<SolarEvent> = events_between(start, end, &coords)
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+            let mut expected: Vec<SolarEvent> = (start.date_naive() - Duration::days(1))
+                .iter_days()
+                .take_while(|date| *date <= end.date_naive() + Duration::days(1))
+                .flat_map(|date| day_events(date, &coords).unwrap())
+                .filter(|event| event.time >= start && event.time <= end)
+                .collect();
+            expected.sort_by_key(|event| event.time);
+            expected.dedup();
+
+            assert_eq!(events, expected, "at {latitude}, {longitude}");
+            assert!(events.windows(2).all(|w| w[0].time < w[1].time));
+        }
+    }
+
+    #[test]
+    fn next_and_previous_events_are_neighbours() {
+        let coords = Coordinates::new(40.4168, -3.7038).unwrap();
+        let now: DateTime<Utc> = "2026-02-03T12:00:00Z".parse().unwrap();
+
+        let next = next_event(now, &coords).unwrap().unwrap();
+        let previous = previous_event(now, &coords).unwrap().unwrap();
+        assert_eq!(next.kind, EventKind::Transit);
+        assert_eq!(previous.kind, EventKind::Sunrise);
+        assert!(previous.time < now && now < next.time);
+
+        // An event's neighbours are found strictly before and after it
+        assert_eq!(previous_event(next.time, &coords).unwrap(), Some(previous));
+        assert_eq!(next_event(previous.time, &coords).unwrap(), Some(next));
+    }
+}
@@ -0,0 +1,68 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Process exit statuses of the `solar-clock-rs` binary.
+//!
+//! | Code | Meaning                                                  |
+//! |------|----------------------------------------------------------|
+//! | 0    | Success                                                  |
+//! | 1    | I/O failure, such as a closed output pipe                |
+//! | 2    | Invalid arguments, environment or configuration file     |
+//! | 3    | No solar data: the events needed do not occur (polar)   |
+//! | 4    | Internal failure of the solar position or interpolation |
+
+use std::process::ExitCode;
+
+use solar_clock_rs::{ModelFileError, SolarClockError};
+
+pub const IO: u8 = 1;
+pub const USAGE: u8 = 2;
+pub const NO_SOLAR_DATA: u8 = 3;
+pub const INTERNAL: u8 = 4;
+
+/// Exit status for a failed evaluation of the solar clock.
+pub fn code(e: &SolarClockError) -> u8 {
+    match e {
+        SolarClockError::InsufficientAnchors { .. } | SolarClockError::NoSunriseOrSunset { .. } => {
+            NO_SOLAR_DATA
+        }
+        // Only custom anchor sets can run backwards
+        SolarClockError::NonMonotonic { .. } => USAGE,
+        SolarClockError::SolarPositionFailed
+        | SolarClockError::InversionDidNotConverge
+        | SolarClockError::Interpolation(_)
+        | SolarClockError::EvaluationFailed { .. }
+        | SolarClockError::OutOfRange { .. } => INTERNAL,
+    }
+}
+
+/// [`code`] as an [`ExitCode`].
+pub fn from_error(e: &SolarClockError) -> ExitCode {
+    ExitCode::from(code(e))
+}
+
+/// Exit status for a model file that cannot be saved or loaded.
+pub fn from_model_error(e: &ModelFileError) -> ExitCode {
+    ExitCode::from(match e {
+        ModelFileError::Io(_) => IO,
+        ModelFileError::Malformed
+        | ModelFileError::UnsupportedVersion(_)
+        | ModelFileError::Mismatch(_) => USAGE,
+        ModelFileError::SolarClock(e) => code(e),
+    })
+}
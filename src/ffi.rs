@@ -0,0 +1,185 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! C interface, behind the `ffi` feature.
+//!
+//! Build `libsolar_clock.so` and `libsolar_clock.a` with
+//! `cargo build --release -p solar-clock`.
+//! `include/solar_clock.h` declares these functions and is generated with
+//! `cbindgen --config cbindgen.toml --output include/solar_clock.h src/ffi.rs`;
+//! the tests of the `solar-clock` package check that it is current and build
+//! a C program against it.
+//! Instants are Unix seconds as `double`, fractions allowed. No panic crosses
+//! the boundary: one is reported as [`ScStatus::Panic`], or a null handle.
+
+use std::ffi::c_int;
+use std::panic::{self, AssertUnwindSafe};
+
+use chrono::{DateTime, FixedOffset, NaiveTime, Utc};
+
+use crate::builder::SolarClockBuilder;
+use crate::error::SolarClockError;
+use crate::solar_clock::SolarClock;
+
+/// Status returned as `int` by [`sc_solar_time`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScStatus {
+    Ok = 0,
+    /// A pointer argument was null.
+    NullPointer = 1,
+    /// An argument was out of range or not finite.
+    InvalidArgument = 2,
+    /// The events needed do not occur, as in polar day or night.
+    NoSolarData = 3,
+    /// The solar position or the interpolation failed.
+    Internal = 4,
+    /// The library panicked; the handle should be freed.
+    Panic = 5,
+}
+
+impl From<SolarClockError> for ScStatus {
+    fn from(e: SolarClockError) -> Self {
+        match e {
+            SolarClockError::InsufficientAnchors { .. }
+            | SolarClockError::NoSunriseOrSunset { .. } => Self::NoSolarData,
+            SolarClockError::NonMonotonic { .. } => Self::InvalidArgument,
+            SolarClockError::SolarPositionFailed
+            | SolarClockError::InversionDidNotConverge
+            | SolarClockError::Interpolation(_)
+            | SolarClockError::EvaluationFailed { .. }
+            | SolarClockError::OutOfRange { .. } => Self::Internal,
+        }
+    }
+}
+
+/// Opaque solar clock owned by C code.
+pub struct SolarClockHandle {
+    clock: SolarClock,
+}
+
+/// Creates a clock for the observer at `lat`, `lon` degrees, with the
+/// sunrise, transit and sunset targets in seconds after midnight and the
+/// solar wall time `offset_s` seconds east of UTC.
+///
+/// Returns null when an argument is invalid. Free the handle with
+/// [`sc_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn sc_new(
+    lat: f64,
+    lon: f64,
+    sunrise_s: u32,
+    transit_s: u32,
+    sunset_s: u32,
+    offset_s: i32,
+) -> *mut SolarClockHandle {
+    let built = panic::catch_unwind(|| {
+        let target = |seconds| NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0);
+        let (sunrise, transit, sunset) =
+            (target(sunrise_s)?, target(transit_s)?, target(sunset_s)?);
+        let offset = FixedOffset::east_opt(offset_s)?;
+        SolarClockBuilder::new()
+            .coordinates(lat, lon)
+            .targets(sunrise, transit, sunset)
+            .solar_offset(offset)
+            .build()
+            .ok()
+    });
+    match built {
+        Ok(Some(clock)) => Box::into_raw(Box::new(SolarClockHandle { clock })),
+        Ok(None) | Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Evaluates the clock at `epoch_seconds` and writes the solar instant, in
+/// Unix seconds, to `out_epoch_seconds` and the delta in seconds to
+/// `out_delta`. The outputs are left untouched on failure.
+///
+/// # Safety
+///
+/// `handle` must come from [`sc_new`] and not have been freed, and the
+/// outputs must be valid for writes. Any of them may be null.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sc_solar_time(
+    handle: *const SolarClockHandle,
+    epoch_seconds: f64,
+    out_epoch_seconds: *mut f64,
+    out_delta: *mut f64,
+) -> c_int {
+    // SAFETY: forwarded from the caller
+    unsafe { solar_time(handle, epoch_seconds, out_epoch_seconds, out_delta) as c_int }
+}
+
+/// [`sc_solar_time`] with a typed status.
+///
+/// # Safety
+///
+/// As for [`sc_solar_time`].
+unsafe fn solar_time(
+    handle: *const SolarClockHandle,
+    epoch_seconds: f64,
+    out_epoch_seconds: *mut f64,
+    out_delta: *mut f64,
+) -> ScStatus {
+    if handle.is_null() || out_epoch_seconds.is_null() || out_delta.is_null() {
+        return ScStatus::NullPointer;
+    }
+    let Some(dt) = from_seconds(epoch_seconds) else {
+        return ScStatus::InvalidArgument;
+    };
+    // SAFETY: the caller guarantees `handle` is live
+    let clock = unsafe { &(*handle).clock };
+    // The cache of a clock that panicked may be stale, so the caller is told
+    // to drop it
+    let result = panic::catch_unwind(AssertUnwindSafe(|| clock.solar_time(dt)));
+    match result {
+        Ok(Ok(result)) => {
+            let solar = result.solar_time.with_timezone(&Utc);
+            // SAFETY: the caller guarantees the outputs are valid for writes
+            unsafe {
+                *out_epoch_seconds = solar.timestamp_micros() as f64 / 1e6;
+                *out_delta = result.delta;
+            }
+            ScStatus::Ok
+        }
+        Ok(Err(e)) => e.into(),
+        Err(_) => ScStatus::Panic,
+    }
+}
+
+/// Frees a clock created by [`sc_new`]. Null is ignored.
+///
+/// # Safety
+///
+/// `handle` must come from [`sc_new`] and not have been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn sc_free(handle: *mut SolarClockHandle) {
+    if !handle.is_null() {
+        // SAFETY: the caller guarantees `handle` is live and owned by it
+        drop(unsafe { Box::from_raw(handle) });
+    }
+}
+
+/// Instant of `seconds` Unix seconds, to the microsecond.
+fn from_seconds(seconds: f64) -> Option<DateTime<Utc>> {
+    let micros = (seconds * 1e6).round();
+    if !micros.is_finite() || micros.abs() > i64::MAX as f64 {
+        return None;
+    }
+    DateTime::from_timestamp_micros(micros as i64)
+}
@@ -0,0 +1,186 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! User-supplied output templates (`--format`).
+//!
+//! A template is a chrono `strftime` format string applied to the solar time,
+//! with extra placeholders:
+//!
+//! - `{delta_s}`: signed seconds with millisecond precision, e.g. `+2821.456`
+//! - `{delta_hms}`: signed hours, minutes and seconds, e.g. `+47m01s`
+//! - `{epoch}`: the solar instant in Unix seconds with millisecond precision,
+//!   e.g. `1780300187.893`
+//! - `{eot}`: the equation of time on the UTC date of the input, e.g.
+//!   `+16m25s`, or `?` for a date the solar position algorithm rejects
+
+use chrono::format::{Item, StrftimeItems};
+use solar_clock_rs::SolarClockResult;
+use solar_clock_rs::spa::equation_of_time;
+
+use crate::commands::format_signed_hms;
+
+/// A validated output template.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputFormat {
+    pieces: Vec<Piece>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Piece {
+    Strftime(String),
+    DeltaSeconds,
+    DeltaHms,
+    Epoch,
+    EquationOfTime,
+}
+
+impl OutputFormat {
+    /// Splits `template` into strftime text and placeholders, rejecting
+    /// invalid strftime specifiers so that rendering can never fail.
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let mut pieces = Vec::new();
+        let mut rest = template;
+
+        while !rest.is_empty() {
+            let (text, placeholder) = match rest.find('{') {
+                Some(start) => {
+                    let end = rest[start..]
+                        .find('}')
+                        .map(|end| start + end)
+                        .ok_or("unterminated '{' placeholder")?;
+                    let piece = match &rest[start + 1..end] {
+                        "delta_s" => Piece::DeltaSeconds,
+                        "delta_hms" => Piece::DeltaHms,
+                        "epoch" => Piece::Epoch,
+                        "eot" => Piece::EquationOfTime,
+                        other => return Err(format!("unknown placeholder '{{{other}}}'")),
+                    };
+                    let text = &rest[..start];
+                    rest = &rest[end + 1..];
+                    (text, Some(piece))
+                }
+                None => (std::mem::take(&mut rest), None),
+            };
+
+            if !text.is_empty() {
+                if StrftimeItems::new(text).any(|item| item == Item::Error) {
+                    return Err(format!("invalid strftime specifier in '{text}'"));
+                }
+                pieces.push(Piece::Strftime(text.to_owned()));
+            }
+            pieces.extend(placeholder);
+        }
+
+        Ok(Self { pieces })
+    }
+
+    /// Template used by `--quiet` when no format is given: `HH:MM`.
+    pub fn quiet() -> Self {
+        Self {
+            pieces: vec![Piece::Strftime("%H:%M".to_owned())],
+        }
+    }
+
+    /// Renders the template for one evaluation.
+    pub fn render(&self, result: &SolarClockResult) -> String {
+        self.pieces
+            .iter()
+            .map(|piece| match piece {
+                Piece::Strftime(text) => result.solar_time.format(text).to_string(),
+                Piece::DeltaSeconds => format!("{:+.3}", result.delta),
+                Piece::DeltaHms => format_signed_hms(result.delta),
+                Piece::Epoch => {
+                    let millis = result.solar_time.timestamp_millis();
+                    let sign = if millis < 0 { "-" } else { "" };
+                    let millis = millis.unsigned_abs();
+                    format!("{sign}{}.{:03}", millis / 1000, millis % 1000)
+                }
+                Piece::EquationOfTime => equation_of_time(result.input.date_naive())
+                    .map_or_else(|_| "?".to_owned(), |eot| format_signed_hms(eot * 60.0)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, Utc};
+    use solar_clock_rs::SolarClock;
+    use solar_clock_rs::solar_clock::{
+        DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET,
+    };
+
+    use super::*;
+
+    /// Result at `input` with the solar time moved to `delta` seconds ahead.
+    fn shifted(input: &str, delta: f64) -> SolarClockResult {
+        let input: DateTime<Utc> = input.parse().unwrap();
+        let clock = SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let mut result = clock.solar_time(input).unwrap();
+        result.delta = delta;
+        result.solar_time = (input + Duration::microseconds((delta * 1e6) as i64))
+            .with_timezone(&SOLAR_TIMEZONE_OFFSET);
+        result
+    }
+
+    #[test]
+    fn renders_strftime_text_and_placeholders() {
+        let result = shifted("2026-02-03T12:00:00Z", 2821.456);
+        let render = |template: &str| OutputFormat::parse(template).unwrap().render(&result);
+
+        assert_eq!(
+            render("%H:%M:%S {delta_s} {delta_hms}"),
+            "13:47:01 +2821.456 +47m01s"
+        );
+        assert_eq!(render("{epoch}"), "1770122821.456");
+        assert_eq!(render("{delta_hms}{delta_s}"), "+47m01s+2821.456");
+        assert_eq!(render("%Y-%m-%d %%"), "2026-02-03 %");
+
+        // Negative deltas carry their sign in both placeholders
+        let behind = shifted("2026-02-03T12:00:00Z", -3723.0);
+        assert_eq!(
+            OutputFormat::parse("{delta_s} {delta_hms}")
+                .unwrap()
+                .render(&behind),
+            "-3723.000 -1h02m03s"
+        );
+
+        // Before 1970 the sign goes in front of the whole seconds
+        let mut early = behind;
+        early.solar_time = DateTime::from_timestamp_millis(-1500)
+            .unwrap()
+            .with_timezone(&SOLAR_TIMEZONE_OFFSET);
+        assert_eq!(
+            OutputFormat::parse("{epoch}").unwrap().render(&early),
+            "-1.500"
+        );
+    }
+
+    #[test]
+    fn rejects_what_cannot_be_rendered() {
+        assert_eq!(
+            OutputFormat::parse("%H {delta"),
+            Err("unterminated '{' placeholder".to_owned())
+        );
+        assert_eq!(
+            OutputFormat::parse("{delta_m}"),
+            Err("unknown placeholder '{delta_m}'".to_owned())
+        );
+        assert!(OutputFormat::parse("%H:%Q").is_err());
+    }
+}
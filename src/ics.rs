@@ -0,0 +1,153 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Minimal iCalendar (RFC 5545) writer for the calendar exports.
+
+use chrono::{DateTime, Utc};
+
+/// Calendar being written, one content line at a time.
+pub struct Calendar {
+    out: String,
+    stamp: String,
+}
+
+impl Calendar {
+    /// Opens a `VCALENDAR`. `name` is shown by clients that support
+    /// `X-WR-CALNAME`.
+    pub fn new(name: &str) -> Self {
+        let mut calendar = Self {
+            out: String::new(),
+            stamp: date_time(Utc::now()),
+        };
+        calendar.line("BEGIN:VCALENDAR");
+        calendar.line("VERSION:2.0");
+        calendar.line("PRODID:-//Kuenlun//solar-clock-rs//EN");
+        calendar.line("CALSCALE:GREGORIAN");
+        calendar.line(&format!("X-WR-CALNAME:{}", text(name)));
+        calendar
+    }
+
+    /// Adds an instantaneous `VEVENT`. `uid` must be the same every time the
+    /// same occurrence is exported, so that subscribed calendars update it
+    /// instead of duplicating it.
+    pub fn event(&mut self, uid: &str, start: DateTime<Utc>, summary: &str, description: &str) {
+        self.line("BEGIN:VEVENT");
+        self.line(&format!("UID:{}", text(uid)));
+        let stamp = format!("DTSTAMP:{}", self.stamp);
+        self.line(&stamp);
+        self.line(&format!("DTSTART:{}", date_time(start)));
+        self.line(&format!("SUMMARY:{}", text(summary)));
+        self.line(&format!("DESCRIPTION:{}", text(description)));
+        self.line("TRANSP:TRANSPARENT");
+        self.line("END:VEVENT");
+    }
+
+    pub fn finish(mut self) -> String {
+        self.line("END:VCALENDAR");
+        self.out
+    }
+
+    /// Appends a content line, folded at 75 octets and ended with CRLF.
+    fn line(&mut self, line: &str) {
+        let mut octets = 0;
+        for c in line.chars() {
+            if octets + c.len_utf8() > 75 {
+                self.out.push_str("\r\n ");
+                // The leading space counts towards the continuation line
+                octets = 1;
+            }
+            self.out.push(c);
+            octets += c.len_utf8();
+        }
+        self.out.push_str("\r\n");
+    }
+}
+
+/// UTC `DATE-TIME` such as `20260320T182628Z`, truncated to the second.
+fn date_time(t: DateTime<Utc>) -> String {
+    t.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a `TEXT` value.
+fn text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Content lines of `calendar`, with the folded ones joined back.
+    fn unfold(calendar: &str) -> Vec<String> {
+        assert!(calendar.ends_with("\r\n"));
+        calendar
+            .replace("\r\n ", "")
+            .trim_end_matches("\r\n")
+            .split("\r\n")
+            .map(str::to_owned)
+            .collect()
+    }
+
+    #[test]
+    fn events_are_escaped_and_folded() {
+        let mut calendar = Calendar::new("Solar, clock; events");
+        let start = DateTime::parse_from_rfc3339("2026-03-20T18:26:28.75Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let description = "Ñandú ".repeat(20) + "a\\b\nc";
+        calendar.event(
+            "20260320-sunset@solar-clock-rs",
+            start,
+            "Sunset (solar 20:00)",
+            &description,
+        );
+        let out = calendar.finish();
+
+        // Folded at 75 octets, without splitting a character
+        for line in out.split("\r\n") {
+            assert!(line.len() <= 75, "{line:?}");
+        }
+        let lines = unfold(&out);
+        assert_eq!(lines[0], "BEGIN:VCALENDAR");
+        assert!(lines.contains(&r"X-WR-CALNAME:Solar\, clock\; events".to_owned()));
+        let event = lines
+            .iter()
+            .position(|line| line == "BEGIN:VEVENT")
+            .unwrap();
+        assert_eq!(lines[event + 1], "UID:20260320-sunset@solar-clock-rs");
+        assert!(lines[event + 2].starts_with("DTSTAMP:"));
+        assert_eq!(lines[event + 3], "DTSTART:20260320T182628Z");
+        assert_eq!(lines[event + 4], "SUMMARY:Sunset (solar 20:00)");
+        assert_eq!(
+            lines[event + 5],
+            format!("DESCRIPTION:{}a\\\\b\\nc", "Ñandú ".repeat(20))
+        );
+        assert_eq!(lines[lines.len() - 2], "END:VEVENT");
+        assert_eq!(lines[lines.len() - 1], "END:VCALENDAR");
+    }
+}
@@ -0,0 +1,313 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Interpolation of the delta between anchors, on plain `f64` slices.
+//!
+//! Fritsch–Carlson PCHIP, Akima and linear interpolation are implemented in
+//! [`epoch`], without allocation; a handful of anchors does not warrant a
+//! numerical library.
+
+use crate::epoch;
+use crate::error::InterpError;
+
+pub use crate::epoch::InterpolationMethod;
+
+/// Piecewise interpolant through a set of points. PCHIP and Akima are cubic
+/// Hermite interpolants that differ only in their slopes at the points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interpolator {
+    method: InterpolationMethod,
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    slopes: Vec<f64>,
+}
+
+impl Interpolator {
+    /// Interpolant through the points `(xs[i], ys[i])`. Requires at least two
+    /// points, as many `ys` as `xs`, and finite, strictly increasing `xs`.
+    pub fn new(method: InterpolationMethod, xs: &[f64], ys: &[f64]) -> Result<Self, InterpError> {
+        epoch::check_points(xs, ys)?;
+        let mut slopes = match method {
+            // Unused: linear segments only depend on the secants
+            InterpolationMethod::Linear => Vec::new(),
+            InterpolationMethod::Pchip | InterpolationMethod::Akima => vec![0.0; xs.len()],
+        };
+        epoch::compute_slopes(method, xs, ys, &mut slopes)?;
+
+        Ok(Self {
+            method,
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            slopes,
+        })
+    }
+
+    /// Monotone cubic interpolant, see [`InterpolationMethod::Pchip`].
+    pub fn pchip(xs: &[f64], ys: &[f64]) -> Result<Self, InterpError> {
+        Self::new(InterpolationMethod::Pchip, xs, ys)
+    }
+
+    pub fn method(&self) -> InterpolationMethod {
+        self.method
+    }
+
+    /// Abscissae of the points, strictly increasing.
+    pub fn xs(&self) -> &[f64] {
+        &self.xs
+    }
+
+    pub fn ys(&self) -> &[f64] {
+        &self.ys
+    }
+
+    /// Number of segments between points.
+    pub fn segments(&self) -> usize {
+        self.xs.len() - 1
+    }
+
+    /// Index of the segment containing `x`, or `None` outside the point range.
+    pub fn segment(&self, x: f64) -> Option<usize> {
+        epoch::segment(&self.xs, x)
+    }
+
+    /// Evaluates the interpolant, returning `None` outside the point range.
+    pub fn evaluate(&self, x: f64) -> Option<f64> {
+        epoch::evaluate(self.method, &self.xs, &self.ys, &self.slopes, x)
+    }
+
+    /// Smallest derivative of the interpolant between the first and last
+    /// points. Below -1 the solar time it is added to runs backwards.
+    pub fn min_derivative(&self) -> f64 {
+        epoch::min_derivative(self.method, &self.xs, &self.ys, &self.slopes)
+    }
+
+    /// Derivative of the interpolant, returning `None` outside the point
+    /// range. At an interior point it is the derivative of the segment on its
+    /// right, which only matters for linear interpolation.
+    pub fn derivative(&self, x: f64) -> Option<f64> {
+        epoch::derivative(self.method, &self.xs, &self.ys, &self.slopes, x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::epoch::{EpochModel, Point};
+    use crate::solar_clock::{
+        Anchor, DEFAULT_COORDINATES, DEFAULT_TARGETS, ModelOptions, SOLAR_TIMEZONE_OFFSET,
+        SolarClock, build_interpolation_model,
+    };
+
+    const METHODS: [InterpolationMethod; 3] = [
+        InterpolationMethod::Linear,
+        InterpolationMethod::Pchip,
+        InterpolationMethod::Akima,
+    ];
+
+    /// Anchors of the reference date in Madrid, as (real time, delta) points.
+    fn solar_points() -> (Vec<f64>, Vec<f64>) {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        build_interpolation_model(date, &DEFAULT_COORDINATES, &ModelOptions::default())
+            .unwrap()
+            .iter()
+            .map(|anchor| (anchor.point().x, anchor.point().y))
+            .unzip()
+    }
+
+    #[test]
+    fn every_method_hits_the_anchors() {
+        let (xs, ys) = solar_points();
+        for method in METHODS {
+            let interpolator = Interpolator::new(method, &xs, &ys).unwrap();
+            for (x, y) in xs.iter().zip(&ys) {
+                let value = interpolator.evaluate(*x).unwrap();
+                assert!(
+                    (value - y).abs() < 1e-9,
+                    "{method:?} at {x}: {value} vs {y}"
+                );
+            }
+            assert_eq!(interpolator.evaluate(xs[0] - 1.0), None);
+            assert_eq!(interpolator.evaluate(xs[xs.len() - 1] + 1.0), None);
+        }
+    }
+
+    #[test]
+    fn linear_and_pchip_differ_between_the_anchors() {
+        let (xs, ys) = solar_points();
+        let linear = Interpolator::new(InterpolationMethod::Linear, &xs, &ys).unwrap();
+        let pchip = Interpolator::pchip(&xs, &ys).unwrap();
+
+        let mut largest = 0.0f64;
+        for pair in xs.windows(2) {
+            let difference = [0.25, 0.5, 0.75]
+                .map(|t| {
+                    let x = pair[0] + t * (pair[1] - pair[0]);
+                    (linear.evaluate(x).unwrap() - pchip.evaluate(x).unwrap()).abs()
+                })
+                .into_iter()
+                .fold(0.0, f64::max);
+            assert!(difference > 1e-3, "between {} and {}", pair[0], pair[1]);
+            largest = largest.max(difference);
+        }
+        // Tens of seconds, visible on the clock
+        assert!(largest > 10.0, "{largest}");
+    }
+
+    /// Samples `f` at `count + 1` evenly spaced points from `start` to `end`.
+    fn sample(start: f64, end: f64, count: usize, f: impl Fn(f64) -> f64) -> Vec<f64> {
+        (0..=count)
+            .map(|i| f(start + (end - start) * i as f64 / count as f64))
+            .collect()
+    }
+
+    #[test]
+    fn pchip_matches_worked_values() {
+        // Secants 1, 3, 5: interior slopes 1.5 and 3.75 by the harmonic
+        // mean, end slopes 0 (the three-point estimate) and 6
+        let pchip = Interpolator::pchip(&[0.0, 1.0, 2.0, 3.0], &[0.0, 1.0, 4.0, 9.0]).unwrap();
+        for (x, y, slope) in [
+            (0.0, 0.0, 0.0),
+            (0.5, 0.3125, 1.125),
+            (1.0, 1.0, 1.5),
+            (1.5, 2.21875, 3.1875),
+            (2.0, 4.0, 3.75),
+            (2.5, 6.21875, 5.0625),
+            (3.0, 9.0, 6.0),
+        ] {
+            assert!(
+                (pchip.evaluate(x).unwrap() - y).abs() < 1e-12,
+                "value at {x}"
+            );
+            assert!(
+                (pchip.derivative(x).unwrap() - slope).abs() < 1e-12,
+                "slope at {x}"
+            );
+        }
+        // The slope is smallest at the flat start
+        assert_eq!(pchip.min_derivative(), 0.0);
+    }
+
+    #[test]
+    fn pchip_preserves_monotonicity() {
+        // The data set of Fritsch and Carlson (1980), whose steep rise makes
+        // ordinary cubic splines overshoot and oscillate
+        let xs = [7.99, 8.09, 8.19, 8.7, 9.2, 10.0, 12.0, 15.0, 20.0];
+        let ys = [
+            0.0, 2.76429e-5, 4.37498e-2, 0.169183, 0.469428, 0.943740, 0.998636, 0.999919, 0.999994,
+        ];
+        let pchip = Interpolator::pchip(&xs, &ys).unwrap();
+        let values = sample(xs[0], xs[8], 10_000, |x| pchip.evaluate(x).unwrap());
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+        assert!(values.iter().all(|y| (0.0..=ys[8]).contains(y)));
+        let slopes = sample(xs[0], xs[8], 10_000, |x| pchip.derivative(x).unwrap());
+        assert!(slopes.iter().all(|slope| *slope >= 0.0));
+
+        // Decreasing data gives a decreasing interpolant
+        let reversed: Vec<f64> = ys.iter().map(|y| -y).collect();
+        let pchip = Interpolator::pchip(&xs, &reversed).unwrap();
+        let values = sample(xs[0], xs[8], 10_000, |x| pchip.evaluate(x).unwrap());
+        assert!(values.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    #[test]
+    fn pchip_is_flat_at_plateaus_and_extrema() {
+        let pchip = Interpolator::pchip(&[0.0, 1.0, 2.0, 3.0], &[0.0, 1.0, 1.0, 2.0]).unwrap();
+        for value in sample(1.0, 2.0, 100, |x| pchip.evaluate(x).unwrap()) {
+            assert_eq!(value, 1.0);
+        }
+
+        // No overshoot past a peak, with a zero slope on it
+        let pchip = Interpolator::pchip(&[0.0, 1.0, 3.0], &[0.0, 1.0, 0.0]).unwrap();
+        assert_eq!(pchip.derivative(1.0), Some(0.0));
+        let values = sample(0.0, 3.0, 3000, |x| pchip.evaluate(x).unwrap());
+        assert!(values.iter().all(|y| (0.0..=1.0).contains(y)));
+    }
+
+    #[test]
+    fn derivative_matches_finite_differences() {
+        let (xs, ys) = solar_points();
+        for method in METHODS {
+            let interpolator = Interpolator::new(method, &xs, &ys).unwrap();
+            for pair in xs.windows(2) {
+                let x = 0.5 * (pair[0] + pair[1]);
+                let h = 1.0;
+                let estimate = (interpolator.evaluate(x + h).unwrap()
+                    - interpolator.evaluate(x - h).unwrap())
+                    / (2.0 * h);
+                let derivative = interpolator.derivative(x).unwrap();
+                assert!((derivative - estimate).abs() < 1e-6, "{method:?} at {x}");
+            }
+        }
+    }
+
+    #[test]
+    fn clock_and_epoch_model_agree_with_the_interpolator() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let clock = SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let points: Vec<Point> = clock
+            .model_anchors(date)
+            .unwrap()
+            .iter()
+            .map(Anchor::point)
+            .collect();
+        let (xs, ys): (Vec<f64>, Vec<f64>) = points.iter().map(|p| (p.x, p.y)).unzip();
+        let pchip = Interpolator::pchip(&xs, &ys).unwrap();
+        let epoch = EpochModel::<9>::new(InterpolationMethod::Pchip, &points).unwrap();
+
+        let start = date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        for minute in (0..24 * 60).step_by(7) {
+            let instant = start + chrono::Duration::minutes(minute);
+            let x = crate::solar_clock::to_seconds(instant);
+            let expected = pchip.evaluate(x).unwrap();
+            let delta = clock.solar_time(instant).unwrap().delta;
+            assert!(
+                (delta - expected).abs() < 1e-9,
+                "{instant}: {delta} vs {expected}"
+            );
+            let delta = epoch.delta(x).unwrap();
+            assert!(
+                (delta - expected).abs() < 1e-9,
+                "{instant}: {delta} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_points_are_rejected() {
+        let pchip = Interpolator::pchip;
+        assert_eq!(pchip(&[1.0], &[1.0]), Err(InterpError::TooFewPoints(1)));
+        assert_eq!(
+            pchip(&[0.0, 1.0], &[0.0, 1.0, 2.0]),
+            Err(InterpError::LengthMismatch { xs: 2, ys: 3 })
+        );
+        assert_eq!(
+            pchip(&[0.0, 1.0, 1.0], &[0.0, 1.0, 2.0]),
+            Err(InterpError::NotIncreasing(2))
+        );
+        assert_eq!(
+            pchip(&[0.0, 2.0, 1.0], &[0.0, 1.0, 2.0]),
+            Err(InterpError::NotIncreasing(2))
+        );
+        assert_eq!(
+            pchip(&[0.0, 1.0, 2.0], &[0.0, f64::NAN, 2.0]),
+            Err(InterpError::NotFinite(1))
+        );
+    }
+}
@@ -0,0 +1,153 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Interoperability with the `jiff` crate, behind the `jiff` feature.
+//!
+//! Conversions are exact to the nanosecond. Zoned results carry a fixed-offset
+//! time zone equal to the clock's solar offset, not a named zone: the solar
+//! wall time has no daylight saving time.
+
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use jiff::tz::{self, Offset};
+use jiff::{Timestamp, Zoned};
+
+use crate::error::InteropError;
+use crate::solar_clock::{SolarClock, SolarClockResult};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Same instant as `ts`.
+pub fn from_timestamp(ts: Timestamp) -> Result<DateTime<Utc>, InteropError> {
+    let nanos = ts.as_nanosecond();
+    let seconds =
+        i64::try_from(nanos.div_euclid(NANOS_PER_SECOND)).map_err(|_| InteropError::OutOfRange)?;
+    let subsec =
+        u32::try_from(nanos.rem_euclid(NANOS_PER_SECOND)).map_err(|_| InteropError::OutOfRange)?;
+    DateTime::from_timestamp(seconds, subsec).ok_or(InteropError::OutOfRange)
+}
+
+/// Same instant as `zoned`, in the offset it has at that instant.
+pub fn from_zoned(zoned: &Zoned) -> Result<DateTime<FixedOffset>, InteropError> {
+    let offset = FixedOffset::east_opt(zoned.offset().seconds()).ok_or(InteropError::OutOfRange)?;
+    Ok(from_timestamp(zoned.timestamp())?.with_timezone(&offset))
+}
+
+/// Same instant as `dt`. Leap seconds, which jiff does not represent, fail.
+pub fn to_timestamp<Tz: TimeZone>(dt: &DateTime<Tz>) -> Result<Timestamp, InteropError> {
+    let subsec = dt.timestamp_subsec_nanos();
+    if i128::from(subsec) >= NANOS_PER_SECOND {
+        return Err(InteropError::OutOfRange);
+    }
+    // Range-checked, unlike `Timestamp::from_nanosecond`
+    Timestamp::new(dt.timestamp(), subsec as i32).map_err(|_| InteropError::OutOfRange)
+}
+
+/// Same instant as `dt`, in a fixed-offset time zone equal to its offset.
+pub fn to_zoned(dt: &DateTime<FixedOffset>) -> Result<Zoned, InteropError> {
+    let offset = Offset::from_seconds(dt.offset().local_minus_utc())
+        .map_err(|_| InteropError::OutOfRange)?;
+    Ok(to_timestamp(dt)?.to_zoned(tz::TimeZone::fixed(offset)))
+}
+
+/// Solar wall time of the result, in its solar offset.
+impl TryFrom<&SolarClockResult> for Zoned {
+    type Error = InteropError;
+
+    fn try_from(result: &SolarClockResult) -> Result<Self, Self::Error> {
+        to_zoned(&result.solar_time)
+    }
+}
+
+impl SolarClock {
+    /// [`solar_time`](Self::solar_time) for a jiff `Timestamp`, returning the
+    /// solar wall time in a fixed-offset zone equal to this clock's offset.
+    pub fn solar_time_jiff(&self, ts: Timestamp) -> Result<Zoned, InteropError> {
+        let result = self.solar_time(from_timestamp(ts)?)?;
+        Zoned::try_from(&result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+    use crate::solar_clock::{DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET};
+
+    /// 2026-02-03T12:40:00.123456789Z.
+    const NANOS: i128 = 1_770_122_400_123_456_789;
+
+    #[test]
+    fn conversions_keep_the_instant_and_the_offset() {
+        let ts = Timestamp::from_nanosecond(NANOS).unwrap();
+        let dt = from_timestamp(ts).unwrap();
+        assert_eq!(dt.timestamp_nanos_opt(), Some(NANOS as i64));
+        assert_eq!(to_timestamp(&dt), Ok(ts));
+
+        for seconds in [0, 19_800, -12_600, 86_399, -86_399] {
+            let offset = Offset::from_seconds(seconds).unwrap();
+            let zoned = ts.to_zoned(tz::TimeZone::fixed(offset));
+            let dt = from_zoned(&zoned).unwrap();
+            assert_eq!(dt.offset().local_minus_utc(), seconds);
+            assert_eq!(dt.to_utc(), from_timestamp(ts).unwrap());
+            assert_eq!(to_zoned(&dt), Ok(zoned));
+        }
+
+        // Before the epoch the nanoseconds still count forwards
+        let ts = Timestamp::from_nanosecond(-750_000_000).unwrap();
+        let dt = from_timestamp(ts).unwrap();
+        assert_eq!(
+            (dt.timestamp(), dt.timestamp_subsec_nanos()),
+            (-1, 250_000_000)
+        );
+        assert_eq!(to_timestamp(&dt), Ok(ts));
+    }
+
+    #[test]
+    fn unrepresentable_values_are_out_of_range() {
+        // jiff allows offsets up to 25:59:59, chrono under 24 hours
+        let offset = Offset::from_seconds(25 * 3600).unwrap();
+        let zoned = Timestamp::UNIX_EPOCH.to_zoned(tz::TimeZone::fixed(offset));
+        assert_eq!(from_zoned(&zoned), Err(InteropError::OutOfRange));
+        // Leap seconds
+        let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 1_500_000_000)
+            .unwrap()
+            .and_utc();
+        assert_eq!(to_timestamp(&leap), Err(InteropError::OutOfRange));
+        // And years beyond 9999
+        let far = Utc.with_ymd_and_hms(10_000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(to_timestamp(&far), Err(InteropError::OutOfRange));
+    }
+
+    #[test]
+    fn solar_times_match_the_chrono_api() {
+        let clock = SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let ts = Timestamp::from_nanosecond(NANOS).unwrap();
+        let expected = clock.solar_time(from_timestamp(ts).unwrap()).unwrap();
+
+        let solar = clock.solar_time_jiff(ts).unwrap();
+        assert_eq!(solar, to_zoned(&expected.solar_time).unwrap());
+        assert_eq!(
+            solar.offset().seconds(),
+            SOLAR_TIMEZONE_OFFSET.local_minus_utc()
+        );
+        assert_eq!(Zoned::try_from(&expected), Ok(solar));
+    }
+}
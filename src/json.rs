@@ -0,0 +1,324 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Minimal JSON writer for the machine-readable outputs, and a reader for
+//! the members of the objects it writes.
+//!
+//! Numbers are written with Rust's shortest round-trip formatting, never in
+//! exponent notation, and non-finite values as `null`. Timestamps are
+//! RFC 3339 strings with their offset.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, SecondsFormat, TimeZone};
+
+/// JSON object built one member at a time.
+pub struct Object {
+    out: String,
+}
+
+impl Object {
+    pub fn new() -> Self {
+        Self {
+            out: String::from("{"),
+        }
+    }
+
+    /// Adds a member whose value is already JSON.
+    pub fn raw(mut self, key: &str, json: &str) -> Self {
+        if self.out.len() > 1 {
+            self.out.push_str(", ");
+        }
+        self.out.push_str(&string(key));
+        self.out.push_str(": ");
+        self.out.push_str(json);
+        self
+    }
+
+    pub fn string(self, key: &str, value: &str) -> Self {
+        self.raw(key, &string(value))
+    }
+
+    pub fn number(self, key: &str, value: f64) -> Self {
+        self.raw(key, &number(value))
+    }
+
+    /// Adds `value`, or `null`.
+    pub fn optional_number(self, key: &str, value: Option<f64>) -> Self {
+        match value {
+            Some(value) => self.number(key, value),
+            None => self.raw(key, "null"),
+        }
+    }
+
+    pub fn bool(self, key: &str, value: bool) -> Self {
+        self.raw(key, if value { "true" } else { "false" })
+    }
+
+    /// Adds `value` as an RFC 3339 timestamp, or `null`.
+    pub fn time<Tz: TimeZone>(self, key: &str, value: Option<DateTime<Tz>>) -> Self
+    where
+        Tz::Offset: std::fmt::Display,
+    {
+        match value {
+            Some(t) => self.raw(key, &time(&t)),
+            None => self.raw(key, "null"),
+        }
+    }
+
+    pub fn finish(mut self) -> String {
+        self.out.push('}');
+        self.out
+    }
+}
+
+/// JSON array of already serialized values.
+pub fn array(items: impl IntoIterator<Item = String>) -> String {
+    let items: Vec<String> = items.into_iter().collect();
+    format!("[{}]", items.join(", "))
+}
+
+pub fn number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_owned()
+    }
+}
+
+/// Quotes `t` as an RFC 3339 timestamp, with as many fractional digits as
+/// it has.
+pub fn time<Tz: TimeZone>(t: &DateTime<Tz>) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    string(&t.to_rfc3339_opts(SecondsFormat::AutoSi, false))
+}
+
+/// Quotes and escapes `value` as a JSON string.
+pub fn string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// The raw JSON of the top-level member `key` of the object `document`, or
+/// `None` if `document` is not an object or has no such member. Keys are
+/// compared in their escaped form, as [`string`] writes them.
+pub fn member<'a>(document: &'a str, key: &str) -> Option<&'a str> {
+    let key = string(key);
+    let mut reader = Reader {
+        text: document.as_bytes(),
+        pos: 0,
+    };
+    reader.expect(b'{')?;
+    if reader.eat(b'}') {
+        return None;
+    }
+    loop {
+        let name = reader.string()?;
+        reader.expect(b':')?;
+        reader.skip_whitespace();
+        let start = reader.pos;
+        reader.value()?;
+        if name == key.as_bytes() {
+            return Some(&document[start..reader.pos]);
+        }
+        if !reader.eat(b',') {
+            return None;
+        }
+    }
+}
+
+/// Cursor over a JSON document that skips values without decoding them.
+struct Reader<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .text
+            .get(self.pos)
+            .is_some_and(|b| matches!(b, b' ' | b'\t' | b'\r' | b'\n'))
+        {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes `byte` after any whitespace, if it is next.
+    fn eat(&mut self, byte: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.text.get(self.pos) == Some(&byte);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.eat(byte).then_some(())
+    }
+
+    /// Consumes a string and returns it quoted and escaped as in the
+    /// document.
+    fn string(&mut self) -> Option<&'a [u8]> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.text.get(self.pos) != Some(&b'"') {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            match self.text.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some(&self.text[start..self.pos]);
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    /// Consumes a string, number, literal, array or object.
+    fn value(&mut self) -> Option<()> {
+        self.skip_whitespace();
+        match self.text.get(self.pos)? {
+            b'"' => self.string().map(|_| ()),
+            b'{' | b'[' => {
+                let mut depth = 0_usize;
+                loop {
+                    match self.text.get(self.pos)? {
+                        b'"' => {
+                            self.string()?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => depth -= 1,
+                        _ => {}
+                    }
+                    self.pos += 1;
+                    if depth == 0 {
+                        return Some(());
+                    }
+                }
+            }
+            _ => {
+                let start = self.pos;
+                while self.text.get(self.pos).is_some_and(|b| {
+                    !matches!(b, b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n')
+                }) {
+                    self.pos += 1;
+                }
+                (self.pos > start).then_some(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Timelike, Utc};
+
+    use super::*;
+
+    #[test]
+    fn objects_parse_back_to_their_members() {
+        let t = DateTime::parse_from_rfc3339("2026-02-03T12:34:56.5+01:00").unwrap();
+        let document = Object::new()
+            .string("text", "a \"quoted\"\\ line\nand\ta \u{1} control")
+            .number("delta", 2821.456)
+            .number("nan", f64::NAN)
+            .optional_number("missing", None)
+            .bool("flag", true)
+            .time("t", Some(t))
+            .time("none", None::<DateTime<Utc>>)
+            .raw("items", &array([number(1.0), string("two")]))
+            .finish();
+
+        let value: serde_json::Value = serde_json::from_str(&document).unwrap();
+        assert_eq!(value["text"], "a \"quoted\"\\ line\nand\ta \u{1} control");
+        assert_eq!(value["delta"], 2821.456);
+        assert!(value["nan"].is_null() && value["missing"].is_null());
+        assert_eq!(value["flag"], true);
+        assert_eq!(value["t"], "2026-02-03T12:34:56.500+01:00");
+        assert!(value["none"].is_null());
+        assert_eq!(value["items"], serde_json::json!([1, "two"]));
+        // Whole seconds have no fraction
+        assert_eq!(
+            time(&t.with_timezone(&Utc).with_nanosecond(0).unwrap()),
+            "\"2026-02-03T11:34:56+00:00\""
+        );
+        assert_eq!(Object::new().finish(), "{}");
+        assert_eq!(array([]), "[]");
+    }
+
+    #[test]
+    fn numbers_never_use_exponents() {
+        assert_eq!(number(1e21), "1000000000000000000000");
+        assert_eq!(number(1e-7), "0.0000001");
+        assert_eq!(number(-0.5), "-0.5");
+        assert_eq!(number(3.0), "3");
+        assert_eq!(number(f64::INFINITY), "null");
+        assert_eq!(string("\u{7f}é"), "\"\\u007fé\"");
+    }
+
+    #[test]
+    fn members_are_found_at_the_top_level_only() {
+        let document = Object::new()
+            .raw(
+                "nested",
+                r#"{"error": "inner", "list": [1, "]", {"x": []}]}"#,
+            )
+            .string("quote", "a \"b\", c}")
+            .number("delta", -1.5)
+            .raw("none", "null")
+            .string("error", "bad request")
+            .finish();
+        assert_eq!(member(&document, "error"), Some("\"bad request\""));
+        assert_eq!(member(&document, "delta"), Some("-1.5"));
+        assert_eq!(member(&document, "none"), Some("null"));
+        assert_eq!(member(&document, "quote"), Some(r#""a \"b\", c}""#));
+        assert_eq!(
+            member(&document, "nested"),
+            Some(r#"{"error": "inner", "list": [1, "]", {"x": []}]}"#)
+        );
+        // Keys of nested objects and string contents are not members
+        assert_eq!(member(&document, "list"), None);
+        assert_eq!(member(r#"{"text": "\"error\": 1"}"#, "error"), None);
+        assert_eq!(member("{}", "error"), None);
+        assert_eq!(member(r#"["error"]"#, "error"), None);
+        assert_eq!(member(r#"{"error""#, "error"), None);
+    }
+}
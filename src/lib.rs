@@ -0,0 +1,116 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Library API of the solar clock. The binary in `main.rs` is a thin consumer
+//! of [`calculate_solar_clock`].
+//!
+//! # Features
+//!
+//! | Feature    | Enables                                                   |
+//! |------------|-----------------------------------------------------------|
+//! | `cli`      | The binary (default); implies `std` and `tracing`         |
+//! | `std`      | Everything but [`epoch`], on `spa` and `chrono`           |
+//! | `tracing`  | Debug and trace events of the model construction          |
+//! | `tls`      | `mqtts://` brokers for `publish` (rustls); implies `cli`  |
+//! | `dbus`     | The `dbus` command, a session-bus service; implies `cli`  |
+//! | `serde`    | `Serialize` and `Deserialize` for the public types        |
+//! | `time`     | `time_compat`, conversions for `time::OffsetDateTime`     |
+//! | `jiff`     | `jiff_compat`, conversions for `jiff::Timestamp`          |
+//! | `accurate` | `nrel`, the full NREL SPA for every solar position        |
+//! | `ffi`      | `ffi`, the C interface                                    |
+//! | `python`   | PyO3 bindings                                             |
+//! | `wasm`     | `wasm`, wasm-bindgen bindings                             |
+//!
+//! With `default-features = false` only [`epoch`] and the errors it uses are
+//! built, on `core` alone; add `std` for the library without the binary's
+//! dependencies.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Without the `tracing` feature the library's log events compile to nothing
+#[cfg(all(feature = "std", not(feature = "tracing")))]
+macro_rules! debug {
+    ($($arg:tt)*) => {};
+}
+#[cfg(all(feature = "std", not(feature = "tracing")))]
+macro_rules! trace {
+    ($($arg:tt)*) => {};
+}
+#[cfg(all(feature = "std", not(feature = "tracing")))]
+macro_rules! warn {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod cct;
+#[cfg(feature = "std")]
+pub mod coverage;
+pub mod epoch;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod events;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod interp;
+#[cfg(feature = "jiff")]
+pub mod jiff_compat;
+#[cfg(feature = "std")]
+pub mod model_file;
+#[cfg(feature = "accurate")]
+pub mod nrel;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+pub mod solar_clock;
+#[cfg(feature = "std")]
+pub mod spa;
+#[cfg(feature = "std")]
+pub mod system_time;
+#[cfg(feature = "time")]
+pub mod time_compat;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "serde")]
+mod serde_utils;
+
+#[cfg(feature = "std")]
+pub use builder::SolarClockBuilder;
+#[cfg(feature = "std")]
+pub use cct::{CctCurve, CctSample, color_temperature, sample_cct};
+#[cfg(feature = "std")]
+pub use coverage::{CoverageIssue, CoverageIssueKind, check_coverage};
+pub use epoch::{EpochModel, InterpolationMethod, Point};
+#[cfg(feature = "std")]
+pub use error::{
+    BuildError, CctError, InteropError, ModelFileError, SolarClockError, TargetsError,
+};
+pub use error::{CoordError, InterpError};
+#[cfg(feature = "std")]
+pub use events::{EventKind, SolarEvent, events_between, next_event, previous_event};
+#[cfg(feature = "std")]
+pub use interp::Interpolator;
+#[cfg(feature = "std")]
+pub use solar_clock::{
+    Anchor, AnchorEvent, AnchorMode, AnchorSpec, Coordinates, Evaluation, ModelOptions, SolarClock,
+    SolarClockResult, SolarTargets, build_interpolation_model, calculate_solar_clock,
+    calculate_solar_clock_with,
+};
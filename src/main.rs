@@ -16,96 +16,267 @@ You should have received a copy of the GNU General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use chrono::{Duration, Utc};
-use spa::{StdFloatOps, SunriseAndSet, solar_position, sunrise_and_set};
+mod cache;
+mod cli;
+mod commands;
+mod config;
+mod exit;
+mod format;
+mod ics;
+mod json;
+mod mqtt;
+mod output;
+mod tzif;
+mod ws;
 
-fn find_true_solar_noon(
-    approx_noon: chrono::DateTime<Utc>,
-    lat: f64,
-    lon: f64,
-) -> chrono::DateTime<Utc> {
-    // Define objective function: given an offset in nanoseconds from approx_noon, returns the zenith
-    let get_zenith = |offset_nanos: i64| -> f64 {
-        let t = approx_noon + Duration::nanoseconds(offset_nanos);
-        match solar_position::<StdFloatOps>(t, lat, lon) {
-            Ok(pos) => pos.zenith_angle,
-            Err(_) => f64::MAX, // If it fails, return infinity to discard it
-        }
-    };
+use std::net::SocketAddr;
+use std::process::ExitCode;
+use std::time::Duration;
 
-    // Golden Section Search to find the minimum
-    let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
-    let resphi = 2.0 - phi;
+use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+use solar_clock_rs::SolarClock;
+use solar_clock_rs::solar_clock::DEFAULT_TARGETS;
+use tracing::Level;
 
-    // Search window: +/- 20 minutes in nanoseconds
-    let window_nanos = 20 * 60 * 1_000_000_000i64;
+use crate::cli::Command;
+use crate::commands::night_light;
+use crate::output::OutputMode;
 
-    let mut a = -window_nanos;
-    let mut b = window_nanos;
-    let mut c = a + (resphi * (b as f64 - a as f64)) as i64;
-    let mut d = b - (resphi * (b as f64 - a as f64)) as i64;
+fn main() -> ExitCode {
+    let args = match cli::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => return usage_error(e),
+    };
+    if args.help {
+        println!("{}", cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+    init_logging(args.verbose);
+    let file = match config::load(args.config.as_deref()) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(exit::USAGE);
+        }
+    };
+    let env = match config::load_env() {
+        Ok(env) => env,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(exit::USAGE);
+        }
+    };
+    let settings = match config::resolve(&args, &env, &file) {
+        Ok(settings) => settings,
+        Err(e) => return usage_error(e),
+    };
+    tracing::info!(
+        latitude = settings.coords.latitude(),
+        longitude = settings.coords.longitude(),
+        targets = ?settings.targets,
+        solar_offset = %settings.solar_offset,
+        interpolation = settings.interpolation.name(),
+        window_days = settings.window_days,
+        polar_fallback = settings.polar_fallback,
+        zenith = settings.zenith,
+        elevation = settings.elevation,
+        temperature = ?settings.temperature,
+        pressure = ?settings.pressure,
+        "resolved configuration"
+    );
+    let clock = settings.clock();
+    if let Some(path) = &args.load_model
+        && let Err(e) = clock.load_model(path, args.force)
+    {
+        eprintln!("error: {}: {e}", path.display());
+        return exit::from_model_error(&e);
+    }
+    let cached = args.cached && args.load_model.is_none() && {
+        let now = args.at.map_or_else(Utc::now, |at| at.with_timezone(&Utc));
+        cache::prime(&clock, now)
+    };
+    // Custom targets can leave solar wall times unreachable; `validate`
+    // reports the same check itself. A cached model was checked by the run
+    // that built it, and checking again would compute the solar positions
+    // the cache saves
+    if settings.targets != DEFAULT_TARGETS
+        && !cached
+        && !matches!(args.command, Command::Validate { .. })
+    {
+        config::warn_coverage(&clock, today(&clock));
+    }
+    // Quiet mode is the single-line output with a default template
+    let format = settings
+        .format
+        .clone()
+        .or_else(|| args.quiet.then(format::OutputFormat::quiet));
 
-    // Evaluate the function at points c and d
-    let mut fc = get_zenith(c);
-    let mut fd = get_zenith(d);
+    if args.stdin {
+        return commands::batch::run(&clock, format.as_ref(), args.output);
+    }
+    #[cfg(unix)]
+    if let Some(path) = &args.socket
+        && !matches!(args.command, Command::Query { .. })
+    {
+        return commands::socket::run(path, clock);
+    }
 
-    // Iterate until we have microsecond precision (or close enough)
-    // 1000 iterations are enough for nanosecond precision, but we stop
-    // when the window is small
-    while (b - a).abs() > 1000 {
-        // 1 microsecond precision (1000 ns)
-        if fc < fd {
-            b = d;
-            d = c;
-            fd = fc;
-            c = a + (resphi * (b as f64 - a as f64)) as i64;
-            fc = get_zenith(c);
-        } else {
-            a = c;
-            c = d;
-            fc = fd;
-            d = b - (resphi * (b as f64 - a as f64)) as i64;
-            fd = get_zenith(d);
+    match args.command {
+        Command::Clock if args.output == Some(OutputMode::Redshift) => {
+            let dt = args.at.unwrap_or_else(|| Local::now().fixed_offset());
+            let date = solar_date(&clock, dt.with_timezone(&Utc));
+            let dawn = args.dawn.unwrap_or(night_light::DEFAULT_DAWN);
+            let dusk = args.dusk.unwrap_or(night_light::DEFAULT_DUSK);
+            night_light::print(date, dawn, dusk, &clock)
+        }
+        Command::NightLight { tool, args: extra } => {
+            let now = args.at.map_or_else(Utc::now, |dt| dt.with_timezone(&Utc));
+            let date = solar_date(&clock, now);
+            let dawn = args.dawn.unwrap_or(night_light::DEFAULT_DAWN);
+            let dusk = args.dusk.unwrap_or(night_light::DEFAULT_DUSK);
+            night_light::run(tool, &extra, date, (dawn, dusk), &clock)
+        }
+        Command::Clock => {
+            // Use the requested instant, or the current time
+            let dt = args.at.unwrap_or_else(|| Local::now().fixed_offset());
+            commands::clock::run(
+                dt,
+                &clock,
+                format.as_ref(),
+                args.output,
+                args.verbose > 0,
+                args.sparkline,
+            )
+        }
+        Command::Events { date, photo } => {
+            let date = date.unwrap_or_else(|| today(&clock));
+            commands::events::run(date, photo, &clock, args.output)
+        }
+        Command::Ics { from, to } => commands::ics::run(from, to, &clock),
+        Command::Tzif {
+            from,
+            to,
+            granularity,
+            path,
+        } => commands::tzif::run(from, to, granularity.unwrap_or(600), &path, &clock),
+        Command::AlarmsIcs { times, from, to } => commands::ics::alarms(&times, from, to, &clock),
+        Command::Range { from, to } => commands::range::run(from, to, &clock, args.output),
+        Command::Next { time, after } => {
+            let after = after.unwrap_or_else(|| Local::now().fixed_offset());
+            commands::next::run(time, after, &clock, args.output)
+        }
+        Command::Watch { interval } => {
+            let interval = interval.unwrap_or(Duration::from_secs(1));
+            commands::watch::run(interval, &clock, format.as_ref(), args.output)
+        }
+        #[cfg(unix)]
+        Command::Query { request } => {
+            let path = args.socket.unwrap_or_else(commands::socket::default_path);
+            commands::socket::query(&path, &request)
+        }
+        #[cfg(not(unix))]
+        Command::Query { .. } => {
+            eprintln!("error: query is only available on Unix");
+            ExitCode::from(exit::USAGE)
+        }
+        #[cfg(feature = "dbus")]
+        Command::Dbus => commands::dbus::run(&settings),
+        #[cfg(not(feature = "dbus"))]
+        Command::Dbus => {
+            eprintln!("error: D-Bus support is not built in; rebuild with --features dbus");
+            ExitCode::from(exit::USAGE)
+        }
+        Command::Publish {
+            broker,
+            topic,
+            interval,
+            ca_file,
+        } => {
+            let topic = topic.as_deref().unwrap_or("solar/clock");
+            let interval = interval.unwrap_or(Duration::from_secs(60));
+            commands::publish::run(&broker, topic, interval, ca_file.as_deref(), &clock)
+        }
+        Command::Serve { listen, interval } => {
+            let listen = listen.unwrap_or_else(|| SocketAddr::from(([127, 0, 0, 1], 8080)));
+            let interval = interval.unwrap_or(Duration::from_secs(1));
+            commands::serve::run(listen, interval, &settings, clock)
+        }
+        Command::SleepUntil { target } => {
+            let now = args.at.map_or_else(Utc::now, |dt| dt.with_timezone(&Utc));
+            commands::sleep_until::run(target, now, &clock)
+        }
+        Command::Run { times, command } => commands::run::run(&times, &command, &clock),
+        Command::Repl => commands::repl::run(settings, format, args.output, args.verbose > 0),
+        Command::Validate { date } => {
+            let date = date.unwrap_or_else(|| today(&clock));
+            commands::validate::run(date, &clock)
+        }
+        Command::SaveModel { date, path } => {
+            let date = date.unwrap_or_else(|| today(&clock));
+            commands::save_model::run(date, &path, &clock)
+        }
+        Command::Cct { date, step, curve } => {
+            let now = args.at.unwrap_or_else(|| Local::now().fixed_offset());
+            let now = now.with_timezone(&Utc);
+            let date = date.unwrap_or_else(|| solar_date(&clock, now));
+            let step = chrono::Duration::minutes(i64::from(step.unwrap_or(15)));
+            commands::cct::run(now, date, step, &curve, &clock, args.output)
+        }
+        Command::Analemma { time, year, solar } => {
+            let year = year.unwrap_or_else(|| today(&clock).year());
+            commands::analemma::run(time, year, solar, &clock, args.output)
+        }
+        Command::ExportC { year } => {
+            let year = year.unwrap_or_else(|| today(&clock).year());
+            commands::export_c::run(year, &clock)
+        }
+        Command::Plot {
+            year,
+            hourly,
+            width,
+            height,
+            out,
+        } => {
+            let year = year.unwrap_or_else(|| today(&clock).year());
+            let size = (width.unwrap_or(800), height.unwrap_or(400));
+            commands::plot::run(year, hourly, size, &out, &clock)
+        }
+        Command::Seasons { year } => {
+            let year = year.unwrap_or_else(|| today(&clock).year());
+            commands::seasons::run(year, &clock)
         }
     }
-
-    let optimal_offset = (a + b) / 2;
-    approx_noon + Duration::nanoseconds(optimal_offset)
 }
 
-fn main() {
-    // Use the current date
-    let dt = Utc::now();
-
-    // Test coordinates (Madrid, Spain)
-    let lat = 40.4168;
-    let lon = -3.7038;
-
-    println!(
-        "Calculating solar data for coordinates ({}, {}) on date {}",
-        lat,
-        lon,
-        dt.format("%Y-%m-%d")
-    );
+/// Sends logs to stderr, keeping stdout for results. Warnings are always
+/// shown; each `-v` enables one more level.
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => Level::WARN,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE,
+    };
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(std::io::stderr)
+        .init();
+}
 
-    // The calculation of 'noon' (solar noon) is not direct with this library,
-    // but we can show sunrise and sunset.
-    match sunrise_and_set::<StdFloatOps>(dt, lat, lon) {
-        Ok(SunriseAndSet::Daylight(sunrise, sunset)) => {
-            println!("Sunrise: {}", sunrise);
-            println!("Sunset: {}", sunset);
+/// Solar date at `now`, or where the clock cannot be evaluated, the date in
+/// its offset.
+fn solar_date(clock: &SolarClock, now: DateTime<Utc>) -> NaiveDate {
+    clock
+        .solar_date(now)
+        .unwrap_or_else(|_| now.with_timezone(&clock.offset()).date_naive())
+}
 
-            // Simple estimation of solar noon (midpoint)
-            let duration = sunset.signed_duration_since(sunrise);
-            let approx_noon = sunrise + (duration / 2);
-            println!("Solar noon (approx): {}", approx_noon);
+/// Current date in the solar clock's offset.
+fn today(clock: &SolarClock) -> NaiveDate {
+    Local::now().with_timezone(&clock.offset()).date_naive()
+}
 
-            // Refined calculation (Minimum Zenith search)
-            let true_noon = find_true_solar_noon(approx_noon, lat, lon);
-            println!("Solar noon (exact):  {}", true_noon);
-        }
-        Ok(SunriseAndSet::PolarDay) => println!("Polar day (the sun does not set)"),
-        Ok(SunriseAndSet::PolarNight) => println!("Polar night (the sun does not rise)"),
-        Err(e) => eprintln!("Error calculating: {:?}", e),
-    }
+fn usage_error(e: cli::CliError) -> ExitCode {
+    eprintln!("error: {e}\n\n{}", cli::USAGE);
+    ExitCode::from(exit::USAGE)
 }
@@ -0,0 +1,601 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Saving the interpolation model of a solar date to a file and loading it
+//! back, so that later runs can skip the solar position computations.
+//!
+//! The file is a compact little-endian binary: a magic number and the format
+//! version, the configuration the model was built for (coordinates, anchor
+//! targets, offset, window, interpolation, horizon and solar position
+//! settings), then the anchors of the model with their instants exact to the
+//! nanosecond. A loaded model therefore evaluates exactly as the one that was
+//! saved.
+
+use std::fs;
+use std::path::Path;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveTime, Timelike, Utc};
+
+use crate::epoch::InterpolationMethod;
+use crate::error::ModelFileError;
+use crate::solar_clock::{
+    Anchor, AnchorEvent, AnchorMode, AnchorSpec, Model, ModelOptions, SolarClock,
+};
+use crate::spa::Refinement;
+
+const MAGIC: &[u8; 8] = b"SCMODEL\0";
+
+/// Version of the file layout written by [`SolarClock::save_model`].
+pub const FORMAT_VERSION: u16 = 1;
+
+impl SolarClock {
+    /// Writes the model used for instants on the solar date `date` to
+    /// `path`, building it first unless it is the cached one.
+    pub fn save_model(
+        &self,
+        date: NaiveDate,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ModelFileError> {
+        let config = Config::of(self);
+        let bytes = self.with_date_model(date, |model| Ok(encode(&config, model)))?;
+        fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Loads a model written by [`save_model`](Self::save_model) and uses it
+    /// for instants on its solar date, which is returned. Other dates still
+    /// build their own model, replacing the loaded one.
+    ///
+    /// Fails with [`ModelFileError::Mismatch`] when the file was saved for
+    /// another configuration than this clock's, unless `force`, in which
+    /// case its anchors, offset and interpolation are used as saved.
+    pub fn load_model(
+        &self,
+        path: impl AsRef<Path>,
+        force: bool,
+    ) -> Result<NaiveDate, ModelFileError> {
+        let bytes = fs::read(path)?;
+        let (config, saved) = decode(&bytes)?;
+        if !force && let Some(setting) = config.mismatch(&Config::of(self)) {
+            return Err(ModelFileError::Mismatch(setting));
+        }
+
+        let options = ModelOptions {
+            offset: config.offset,
+            interpolation: config.interpolation,
+            strict_monotonic: config.strict_monotonic,
+            ..self.options().clone()
+        };
+        let mut model = Model::from_anchors(saved.date, saved.anchors, &options)?;
+        model.window_days = saved.window_days;
+        model.mode = saved.mode;
+        self.set_model(model);
+        Ok(saved.date)
+    }
+}
+
+/// Settings a saved model depends on.
+#[derive(Debug, Clone, PartialEq)]
+struct Config {
+    latitude: f64,
+    longitude: f64,
+    anchors: Vec<AnchorSpec>,
+    offset: FixedOffset,
+    window_days: u32,
+    max_window_days: u32,
+    polar_fallback: bool,
+    strict_monotonic: bool,
+    interpolation: InterpolationMethod,
+    merge_epsilon: f64,
+    /// Combines the zenith, elevation and refraction settings.
+    horizon_zenith: f64,
+    refinement: Refinement,
+    /// NaN for the built-in estimate.
+    delta_t: f64,
+}
+
+impl Config {
+    fn of(clock: &SolarClock) -> Self {
+        let options = clock.options();
+        Self {
+            latitude: clock.coordinates().latitude(),
+            longitude: clock.coordinates().longitude(),
+            anchors: options.anchors.clone(),
+            offset: options.offset,
+            window_days: options.window_days,
+            max_window_days: options.max_window_days,
+            polar_fallback: options.polar_fallback,
+            strict_monotonic: options.strict_monotonic,
+            interpolation: options.interpolation,
+            merge_epsilon: options.merge_epsilon,
+            horizon_zenith: options.horizon_zenith(),
+            refinement: options.refinement,
+            delta_t: options.delta_t.unwrap_or(f64::NAN),
+        }
+    }
+
+    /// First setting that differs from `other`, compared bit for bit.
+    fn mismatch(&self, other: &Self) -> Option<&'static str> {
+        let same = |a: f64, b: f64| a.to_bits() == b.to_bits();
+        if !same(self.latitude, other.latitude) || !same(self.longitude, other.longitude) {
+            Some("coordinates")
+        } else if self.anchors != other.anchors {
+            Some("targets")
+        } else if self.offset != other.offset {
+            Some("offset")
+        } else if self.window_days != other.window_days
+            || self.max_window_days != other.max_window_days
+        {
+            Some("window")
+        } else if self.interpolation != other.interpolation {
+            Some("interpolation")
+        } else if self.polar_fallback != other.polar_fallback
+            || self.strict_monotonic != other.strict_monotonic
+            || !same(self.merge_epsilon, other.merge_epsilon)
+        {
+            Some("anchor options")
+        } else if !same(self.horizon_zenith, other.horizon_zenith) {
+            Some("horizon")
+        } else if self.refinement != other.refinement {
+            Some("refinement")
+        } else if !same(self.delta_t, other.delta_t) {
+            Some("delta t")
+        } else {
+            None
+        }
+    }
+}
+
+/// Model as read from a file, before it is rebuilt.
+struct Saved {
+    date: NaiveDate,
+    window_days: u32,
+    mode: AnchorMode,
+    anchors: Vec<Anchor>,
+}
+
+fn encode(config: &Config, model: &Model) -> Vec<u8> {
+    let mut out = Writer(Vec::new());
+    out.0.extend_from_slice(MAGIC);
+    out.u16(FORMAT_VERSION);
+
+    out.f64(config.latitude);
+    out.f64(config.longitude);
+    out.u32(config.anchors.len() as u32);
+    for spec in &config.anchors {
+        out.str(&spec.name);
+        out.event(spec.event);
+        out.time(spec.target);
+    }
+    out.i32(config.offset.local_minus_utc());
+    out.u32(config.window_days);
+    out.u32(config.max_window_days);
+    out.bool(config.polar_fallback);
+    out.bool(config.strict_monotonic);
+    out.u8(match config.interpolation {
+        InterpolationMethod::Pchip => 0,
+        InterpolationMethod::Linear => 1,
+        InterpolationMethod::Akima => 2,
+    });
+    out.f64(config.merge_epsilon);
+    out.f64(config.horizon_zenith);
+    out.bool(config.refinement == Refinement::SinglePass);
+    out.f64(config.delta_t);
+
+    out.i32(model.date.num_days_from_ce());
+    out.u32(model.window_days);
+    out.bool(model.mode == AnchorMode::PolarFallback);
+    out.u32(model.anchors.len() as u32);
+    for anchor in &model.anchors {
+        out.str(&anchor.name);
+        out.event(anchor.event);
+        out.i32(anchor.date.num_days_from_ce());
+        out.instant(anchor.real);
+        out.instant(anchor.target);
+    }
+    out.0
+}
+
+fn decode(bytes: &[u8]) -> Result<(Config, Saved), ModelFileError> {
+    let mut input = Reader(bytes);
+    if input.take(MAGIC.len())? != MAGIC {
+        return Err(ModelFileError::Malformed);
+    }
+    let version = input.u16()?;
+    if version != FORMAT_VERSION {
+        return Err(ModelFileError::UnsupportedVersion(version));
+    }
+
+    let latitude = input.f64()?;
+    let longitude = input.f64()?;
+    let mut anchors = Vec::new();
+    for _ in 0..input.u32()? {
+        anchors.push(AnchorSpec::new(input.str()?, input.event()?, input.time()?));
+    }
+    let config = Config {
+        latitude,
+        longitude,
+        anchors,
+        offset: FixedOffset::east_opt(input.i32()?).ok_or(ModelFileError::Malformed)?,
+        window_days: input.u32()?,
+        max_window_days: input.u32()?,
+        polar_fallback: input.bool()?,
+        strict_monotonic: input.bool()?,
+        interpolation: match input.u8()? {
+            0 => InterpolationMethod::Pchip,
+            1 => InterpolationMethod::Linear,
+            2 => InterpolationMethod::Akima,
+            _ => return Err(ModelFileError::Malformed),
+        },
+        merge_epsilon: input.f64()?,
+        horizon_zenith: input.f64()?,
+        refinement: if input.bool()? {
+            Refinement::SinglePass
+        } else {
+            Refinement::Iterative
+        },
+        delta_t: input.f64()?,
+    };
+
+    let date = input.date()?;
+    let window_days = input.u32()?;
+    let mode = if input.bool()? {
+        AnchorMode::PolarFallback
+    } else {
+        AnchorMode::Events
+    };
+    let mut anchors = Vec::new();
+    for _ in 0..input.u32()? {
+        anchors.push(Anchor {
+            name: input.str()?,
+            event: input.event()?,
+            date: input.date()?,
+            real: input.instant()?,
+            target: input.instant()?,
+        });
+    }
+    if !input.0.is_empty() {
+        return Err(ModelFileError::Malformed);
+    }
+
+    let saved = Saved {
+        date,
+        window_days,
+        mode,
+        anchors,
+    };
+    Ok((config, saved))
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.0.push(value);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(u8::from(value));
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn i64(&mut self, value: i64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.0.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) {
+        self.u32(value.len() as u32);
+        self.0.extend_from_slice(value.as_bytes());
+    }
+
+    fn event(&mut self, event: AnchorEvent) {
+        match event {
+            AnchorEvent::Midnight => self.u8(0),
+            AnchorEvent::Sunrise => self.u8(1),
+            AnchorEvent::Transit => self.u8(2),
+            AnchorEvent::Sunset => self.u8(3),
+            AnchorEvent::Elevation { degrees, rising } => {
+                self.u8(4);
+                self.f64(degrees);
+                self.bool(rising);
+            }
+        }
+    }
+
+    fn time(&mut self, time: NaiveTime) {
+        self.u32(time.num_seconds_from_midnight());
+        self.u32(time.nanosecond());
+    }
+
+    fn instant(&mut self, instant: DateTime<Utc>) {
+        self.i64(instant.timestamp());
+        self.u32(instant.timestamp_subsec_nanos());
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ModelFileError> {
+        if self.0.len() < len {
+            return Err(ModelFileError::Malformed);
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ModelFileError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, ModelFileError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn bool(&mut self) -> Result<bool, ModelFileError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ModelFileError::Malformed),
+        }
+    }
+
+    fn u16(&mut self) -> Result<u16, ModelFileError> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, ModelFileError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, ModelFileError> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64, ModelFileError> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, ModelFileError> {
+        self.array().map(f64::from_le_bytes)
+    }
+
+    fn str(&mut self) -> Result<String, ModelFileError> {
+        let len = self.u32()? as usize;
+        let bytes = self.take(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| ModelFileError::Malformed)
+    }
+
+    fn event(&mut self) -> Result<AnchorEvent, ModelFileError> {
+        Ok(match self.u8()? {
+            0 => AnchorEvent::Midnight,
+            1 => AnchorEvent::Sunrise,
+            2 => AnchorEvent::Transit,
+            3 => AnchorEvent::Sunset,
+            4 => AnchorEvent::Elevation {
+                degrees: self.f64()?,
+                rising: self.bool()?,
+            },
+            _ => return Err(ModelFileError::Malformed),
+        })
+    }
+
+    fn date(&mut self) -> Result<NaiveDate, ModelFileError> {
+        NaiveDate::from_num_days_from_ce_opt(self.i32()?).ok_or(ModelFileError::Malformed)
+    }
+
+    fn time(&mut self) -> Result<NaiveTime, ModelFileError> {
+        let secs = self.u32()?;
+        let nanos = self.u32()?;
+        NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos).ok_or(ModelFileError::Malformed)
+    }
+
+    fn instant(&mut self) -> Result<DateTime<Utc>, ModelFileError> {
+        let secs = self.i64()?;
+        let nanos = self.u32()?;
+        DateTime::from_timestamp(secs, nanos).ok_or(ModelFileError::Malformed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+    use crate::solar_clock::{Coordinates, SOLAR_TIMEZONE_OFFSET};
+    use crate::spa;
+
+    fn hm(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    /// Madrid with every kind of anchor event.
+    fn clock(offset: FixedOffset) -> SolarClock {
+        SolarClock::with_options(
+            Coordinates::new(40.4168, -3.7038).unwrap(),
+            ModelOptions {
+                anchors: vec![
+                    AnchorSpec::new(
+                        "dawn",
+                        AnchorEvent::Elevation {
+                            degrees: -6.0,
+                            rising: true,
+                        },
+                        hm(7, 30),
+                    ),
+                    AnchorSpec::new("sunrise", AnchorEvent::Sunrise, hm(8, 0)),
+                    AnchorSpec::new("noon", AnchorEvent::Transit, hm(14, 0)),
+                    AnchorSpec::new("sunset", AnchorEvent::Sunset, hm(20, 0)),
+                    AnchorSpec::new("midnight", AnchorEvent::Midnight, hm(2, 0)),
+                ],
+                offset,
+                ..ModelOptions::default()
+            },
+        )
+    }
+
+    /// A file of its own in the temporary directory.
+    fn path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("solar-clock-{}-{name}.model", std::process::id()))
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 2, 3).unwrap()
+    }
+
+    #[test]
+    fn loaded_models_evaluate_as_saved_without_recomputing() {
+        let calls = || spa::SOLAR_DATA_CALLS.with(std::cell::Cell::get);
+        let path = path("round-trip");
+        let saved = clock(SOLAR_TIMEZONE_OFFSET);
+        saved.save_model(date(), &path).unwrap();
+
+        let loaded = clock(SOLAR_TIMEZONE_OFFSET);
+        let before = calls();
+        assert_eq!(loaded.load_model(&path, false).unwrap(), date());
+        let noon = Utc.with_ymd_and_hms(2026, 2, 3, 12, 0, 0).unwrap();
+        for minutes in (-300..=600).step_by(37) {
+            let dt = noon + Duration::minutes(minutes);
+            assert_eq!(loaded.solar_time(dt), saved.solar_time(dt), "{dt}");
+        }
+        // The anchors came from the file, not from the solar position
+        assert_eq!(calls(), before);
+
+        // Saving the loaded model writes the same bytes
+        let again = path.with_extension("again");
+        loaded.save_model(date(), &again).unwrap();
+        assert_eq!(fs::read(&again).unwrap(), fs::read(&path).unwrap());
+        fs::remove_file(again).unwrap();
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn other_configurations_need_force() {
+        let path = path("mismatch");
+        clock(SOLAR_TIMEZONE_OFFSET)
+            .save_model(date(), &path)
+            .unwrap();
+
+        let other = clock(FixedOffset::east_opt(2 * 3600).unwrap());
+        assert!(matches!(
+            other.load_model(&path, false),
+            Err(ModelFileError::Mismatch("offset"))
+        ));
+        let elsewhere = SolarClock::with_options(
+            Coordinates::new(41.0, -3.7038).unwrap(),
+            clock(SOLAR_TIMEZONE_OFFSET).options().clone(),
+        );
+        assert!(matches!(
+            elsewhere.load_model(&path, false),
+            Err(ModelFileError::Mismatch("coordinates"))
+        ));
+        let single_pass = SolarClock::with_options(
+            Coordinates::new(40.4168, -3.7038).unwrap(),
+            ModelOptions {
+                refinement: spa::Refinement::SinglePass,
+                ..clock(SOLAR_TIMEZONE_OFFSET).options().clone()
+            },
+        );
+        assert!(matches!(
+            single_pass.load_model(&path, false),
+            Err(ModelFileError::Mismatch("refinement"))
+        ));
+
+        // Forced, the saved offset applies
+        assert_eq!(other.load_model(&path, true).unwrap(), date());
+        let noon = Utc.with_ymd_and_hms(2026, 2, 3, 12, 0, 0).unwrap();
+        let result = other.solar_time(noon).unwrap();
+        assert_eq!(result.solar_time.offset(), &SOLAR_TIMEZONE_OFFSET);
+        assert_eq!(Ok(result), clock(SOLAR_TIMEZONE_OFFSET).solar_time(noon));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn damaged_files_are_malformed() {
+        let clock = clock(SOLAR_TIMEZONE_OFFSET);
+        let bytes = clock
+            .with_date_model(date(), |model| Ok(encode(&Config::of(&clock), model)))
+            .unwrap();
+        let (config, saved) = decode(&bytes).unwrap();
+        // Compared bit for bit, as the built-in delta T is a NaN
+        assert_eq!(config.mismatch(&Config::of(&clock)), None);
+        assert_eq!(saved.date, date());
+        assert_eq!(saved.anchors.len(), 5 * 3);
+
+        // Every truncation, and anything past the end
+        for len in 0..bytes.len() {
+            assert!(matches!(
+                decode(&bytes[..len]),
+                Err(ModelFileError::Malformed)
+            ));
+        }
+        let mut longer = bytes.clone();
+        longer.push(0);
+        assert!(matches!(decode(&longer), Err(ModelFileError::Malformed)));
+
+        // Another file type, or another version of this one
+        let mut other = bytes.clone();
+        other[0] = b'X';
+        assert!(matches!(decode(&other), Err(ModelFileError::Malformed)));
+        let mut newer = bytes.clone();
+        newer[MAGIC.len()..MAGIC.len() + 2].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            decode(&newer),
+            Err(ModelFileError::UnsupportedVersion(v)) if v == FORMAT_VERSION + 1
+        ));
+
+        // A flag that is neither 0 nor 1: the polar fallback after the
+        // coordinates, the anchor specs, the offset and the two windows
+        let specs: usize = config
+            .anchors
+            .iter()
+            .map(|spec| {
+                let event = match spec.event {
+                    AnchorEvent::Elevation { .. } => 1 + 8 + 1,
+                    _ => 1,
+                };
+                4 + spec.name.len() + event + 8
+            })
+            .sum();
+        let flag = MAGIC.len() + 2 + 16 + 4 + specs + 4 + 4 + 4;
+        let mut flags = bytes;
+        assert_eq!(flags[flag], 0);
+        flags[flag] = 2;
+        assert!(matches!(decode(&flags), Err(ModelFileError::Malformed)));
+    }
+}
@@ -0,0 +1,308 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! A minimal MQTT 3.1.1 client: enough to connect to a broker and publish
+//! with QoS 1, over plain TCP or, with the `tls` feature, over TLS.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Duration;
+
+/// Default port of unencrypted MQTT.
+const DEFAULT_PORT: u16 = 1883;
+/// Default port of MQTT over TLS.
+const DEFAULT_TLS_PORT: u16 = 8883;
+/// Longest wait for the broker to acknowledge a packet.
+const ACK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Address of a broker, from an `mqtt://host[:port]` or
+/// `mqtts://host[:port]` URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Broker {
+    pub host: String,
+    pub port: u16,
+    /// Whether the connection is over TLS.
+    pub tls: bool,
+}
+
+impl Broker {
+    /// Parses `mqtt://host[:port]` (or `tcp://`) and `mqtts://host[:port]`
+    /// (or `ssl://`). Without the `tls` feature a TLS URL is rejected, so
+    /// that the connection is never silently in the clear.
+    pub fn parse(url: &str) -> Result<Self, String> {
+        let (authority, tls) =
+            if let Some(rest) = url.strip_prefix("mqtts://").or(url.strip_prefix("ssl://")) {
+                if !cfg!(feature = "tls") {
+                    return Err(
+                        "TLS support is not built in; rebuild with --features tls or connect \
+                     through a TLS tunnel such as stunnel"
+                            .to_owned(),
+                    );
+                }
+                (rest, true)
+            } else if let Some(rest) = url.strip_prefix("mqtt://").or(url.strip_prefix("tcp://")) {
+                (rest, false)
+            } else {
+                return Err("expected mqtt://HOST[:PORT] or mqtts://HOST[:PORT]".to_owned());
+            };
+        let authority = authority.strip_suffix('/').unwrap_or(authority);
+        // An IPv6 literal is bracketed, so its colons are not the port's
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) if !port.contains(']') => {
+                let port = port.parse().map_err(|_| format!("invalid port '{port}'"))?;
+                (host, port)
+            }
+            _ if tls => (authority, DEFAULT_TLS_PORT),
+            _ => (authority, DEFAULT_PORT),
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() || host.contains('/') {
+            return Err("expected mqtt://HOST[:PORT] or mqtts://HOST[:PORT]".to_owned());
+        }
+        Ok(Self {
+            host: host.to_owned(),
+            port,
+            tls,
+        })
+    }
+}
+
+impl fmt::Display for Broker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scheme = if self.tls { "mqtts" } else { "mqtt" };
+        if self.host.contains(':') {
+            write!(f, "{scheme}://[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{scheme}://{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// The byte stream under a session, plain or encrypted.
+trait Stream: Read + Write {}
+
+impl<T: Read + Write> Stream for T {}
+
+/// An open session with a broker.
+pub struct Client {
+    stream: Box<dyn Stream>,
+    packet_id: u16,
+}
+
+impl Client {
+    /// Connects with a clean session. The broker drops the client if it sends
+    /// nothing for 1.5 times `keep_alive`, so the caller must publish more
+    /// often than that.
+    ///
+    /// A TLS broker's certificate is checked against `ca_file`, a PEM bundle,
+    /// or else against the Mozilla root store.
+    pub fn connect(
+        broker: &Broker,
+        client_id: &str,
+        keep_alive: Duration,
+        ca_file: Option<&Path>,
+    ) -> io::Result<Self> {
+        let tcp = TcpStream::connect((broker.host.as_str(), broker.port))?;
+        tcp.set_read_timeout(Some(ACK_TIMEOUT))?;
+        tcp.set_write_timeout(Some(ACK_TIMEOUT))?;
+        let mut stream: Box<dyn Stream> = if broker.tls {
+            tls::wrap(tcp, &broker.host, ca_file)?
+        } else {
+            Box::new(tcp)
+        };
+
+        let keep_alive = u16::try_from(keep_alive.as_secs()).unwrap_or(u16::MAX);
+        let mut body = Vec::new();
+        put_string(&mut body, "MQTT");
+        // Protocol level 4 is MQTT 3.1.1; flags only ask for a clean session
+        body.extend_from_slice(&[4, 0x02]);
+        body.extend_from_slice(&keep_alive.to_be_bytes());
+        put_string(&mut body, client_id);
+        stream.write_all(&packet(0x10, &body))?;
+
+        match read_packet(&mut stream)? {
+            (0x20, ack) if ack.len() == 2 && ack[1] == 0 => Ok(Self {
+                stream,
+                packet_id: 0,
+            }),
+            (0x20, ack) if ack.len() == 2 => Err(io::Error::new(
+                io::ErrorKind::ConnectionRefused,
+                format!("broker refused the connection: {}", refusal(ack[1])),
+            )),
+            _ => Err(invalid("expected CONNACK")),
+        }
+    }
+
+    /// Publishes `payload` to `topic` with QoS 1 and waits for the broker's
+    /// acknowledgement, so that a lost broker is noticed at once. With
+    /// `retain`, the broker keeps the message for future subscribers.
+    pub fn publish(&mut self, topic: &str, payload: &str, retain: bool) -> io::Result<()> {
+        self.packet_id = self.packet_id.checked_add(1).unwrap_or(1);
+        let mut body = Vec::new();
+        put_string(&mut body, topic);
+        body.extend_from_slice(&self.packet_id.to_be_bytes());
+        body.extend_from_slice(payload.as_bytes());
+        self.stream
+            .write_all(&packet(0x32 | u8::from(retain), &body))?;
+
+        match read_packet(&mut self.stream)? {
+            (0x40, id) if id == self.packet_id.to_be_bytes() => Ok(()),
+            _ => Err(invalid("expected PUBACK")),
+        }
+    }
+
+    /// Ends the session cleanly.
+    pub fn disconnect(mut self) -> io::Result<()> {
+        self.stream.write_all(&packet(0xE0, &[]))
+    }
+}
+
+/// A control packet: the fixed header, with the remaining length encoded as
+/// in section 2.2.3, then `body`.
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut packet = vec![header];
+    let mut len = body.len();
+    loop {
+        let byte = (len % 128) as u8;
+        len /= 128;
+        if len == 0 {
+            packet.push(byte);
+            break;
+        }
+        packet.push(byte | 0x80);
+    }
+    packet.extend_from_slice(body);
+    packet
+}
+
+/// Reads a control packet, returning its type in the high nibble and its
+/// body.
+fn read_packet(stream: &mut impl Read) -> io::Result<(u8, Vec<u8>)> {
+    let mut byte = [0u8];
+    stream.read_exact(&mut byte)?;
+    let kind = byte[0] & 0xF0;
+    let mut len = 0usize;
+    for shift in (0..28).step_by(7) {
+        stream.read_exact(&mut byte)?;
+        len |= usize::from(byte[0] & 0x7F) << shift;
+        if byte[0] & 0x80 == 0 {
+            let mut body = vec![0u8; len];
+            stream.read_exact(&mut body)?;
+            return Ok((kind, body));
+        }
+    }
+    Err(invalid("malformed remaining length"))
+}
+
+fn put_string(buffer: &mut Vec<u8>, value: &str) {
+    let len = u16::try_from(value.len()).unwrap_or(u16::MAX);
+    buffer.extend_from_slice(&len.to_be_bytes());
+    buffer.extend_from_slice(&value.as_bytes()[..usize::from(len)]);
+}
+
+fn refusal(code: u8) -> &'static str {
+    match code {
+        1 => "unacceptable protocol version",
+        2 => "identifier rejected",
+        3 => "server unavailable",
+        4 => "bad user name or password",
+        5 => "not authorized",
+        _ => "unknown reason",
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("protocol error: {message}"),
+    )
+}
+
+#[cfg(feature = "tls")]
+mod tls {
+    use std::io;
+    use std::net::TcpStream;
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, ServerName};
+    use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+
+    use super::Stream;
+
+    /// Starts a TLS session with `host` over `tcp`. The handshake itself runs
+    /// on the first read or write.
+    pub(super) fn wrap(
+        tcp: TcpStream,
+        host: &str,
+        ca_file: Option<&Path>,
+    ) -> io::Result<Box<dyn Stream>> {
+        let mut roots = RootCertStore::empty();
+        match ca_file {
+            Some(path) => {
+                for cert in CertificateDer::pem_file_iter(path).map_err(other)? {
+                    roots.add(cert.map_err(other)?).map_err(other)?;
+                }
+            }
+            None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+        let config =
+            ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .map_err(other)?
+                .with_root_certificates(roots)
+                .with_no_client_auth();
+        let name = ServerName::try_from(host.to_owned()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid TLS server name '{host}'"),
+            )
+        })?;
+        let connection = ClientConnection::new(Arc::new(config), name).map_err(other)?;
+        Ok(Box::new(StreamOwned::new(connection, tcp)))
+    }
+
+    fn other(error: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+        io::Error::other(error)
+    }
+}
+
+#[cfg(not(feature = "tls"))]
+mod tls {
+    use std::io;
+    use std::net::TcpStream;
+    use std::path::Path;
+
+    use super::Stream;
+
+    /// Never reached: [`Broker::parse`](super::Broker::parse) rejects TLS
+    /// URLs without the feature.
+    pub(super) fn wrap(
+        _tcp: TcpStream,
+        _host: &str,
+        _ca_file: Option<&Path>,
+    ) -> io::Result<Box<dyn Stream>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "TLS support is not built in",
+        ))
+    }
+}
@@ -0,0 +1,82 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! The full NREL Solar Position Algorithm of Reda and Andreas
+//! (NREL/TP-560-34302), from the `solar-positioning` crate, with an
+//! uncertainty of ±0.0003° between the years −2000 and 6000.
+//!
+//! With the `accurate` feature it replaces the `spa` crate's PSA algorithm,
+//! good to about half an arcminute around its 1999–2015 fit period, in every
+//! position computed by [`crate::spa`].
+
+use chrono::{DateTime, Datelike, Utc};
+use solar_positioning::{Location, SolarPositions};
+
+use crate::solar_clock::Coordinates;
+use crate::spa::{self, SolarPosition, SpaError};
+
+/// True geometric position of the sun at `dt` seen from sea level at the
+/// given coordinates, corrected for parallax but not for refraction.
+///
+/// Terrestrial time is taken `ΔT` after `dt` by the Espenak and Meeus
+/// estimate for the middle of its month, see [`spa::estimate_delta_t`].
+pub fn solar_position(dt: DateTime<Utc>, coords: &Coordinates) -> Result<SolarPosition, SpaError> {
+    let year = f64::from(dt.year()) + (f64::from(dt.month()) - 0.5) / 12.0;
+    solar_position_with_delta_t(dt, coords, spa::estimate_delta_t(year))
+}
+
+/// Like [`solar_position`], with terrestrial time taken `delta_t` seconds
+/// after `dt`, e.g. the value published in the IERS Bulletin A.
+pub fn solar_position_with_delta_t(
+    dt: DateTime<Utc>,
+    coords: &Coordinates,
+    delta_t: f64,
+) -> Result<SolarPosition, SpaError> {
+    let location = Location {
+        latitude: coords.latitude,
+        longitude: coords.longitude,
+    };
+    let pos = SolarPositions::new()
+        .at(&dt, location, 0.0, delta_t, None)
+        .map_err(|_| SpaError::BadParam)?;
+
+    Ok(SolarPosition {
+        azimuth: pos.azimuth(),
+        elevation: pos.elevation_angle(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_published_example() {
+        // Example of NREL/TP-560-34302, table A5.1: 2003-10-17 12:30:30
+        // local time (UTC−7) at Golden, Colorado. The published topocentric
+        // zenith 50.11162° includes 0.01635° of refraction at 820 hPa and
+        // 11 °C, and 0.00002° less parallax at its 1830 m of elevation.
+        let golden = Coordinates::new(39.742476, -105.1786).unwrap();
+        let dt = "2003-10-17T19:30:30Z".parse().unwrap();
+        let pos = solar_position(dt, &golden).unwrap();
+
+        assert!((pos.azimuth - 194.340241).abs() < 0.0005, "{pos:?}");
+        let zenith = 90.0 - pos.elevation;
+        assert!((zenith - (50.111622 + 0.01635)).abs() < 0.0005, "{zenith}");
+    }
+}
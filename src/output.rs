@@ -0,0 +1,408 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Machine-readable output modes (`--output`).
+
+use std::fmt::Write;
+
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use solar_clock_rs::spa::{
+    calculate_solar_data_with_zenith, day_length_with_zenith, solar_elevation,
+};
+use solar_clock_rs::{
+    Anchor, AnchorEvent, AnchorMode, AnchorSpec, Evaluation, SolarClock, SolarClockError,
+};
+
+use crate::commands::format_signed_hms;
+use crate::json::{self, Object};
+
+/// Sun elevation at sunrise and sunset, accounting for refraction and the
+/// solar radius.
+const HORIZON: f64 = -0.833;
+/// Sun elevation at the end of civil twilight.
+const CIVIL_TWILIGHT: f64 = -6.0;
+
+/// Output format selected with `--output`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputMode {
+    /// One JSON object per evaluation for waybar and i3status custom modules.
+    Waybar,
+    /// A single JSON document per command (`--json`).
+    Json,
+    /// One compact JSON object per line and per evaluation, for loggers and
+    /// message buses.
+    Ndjson,
+    /// Comma-separated values with a header row, for the tables of `range`
+    /// and `--stdin`.
+    Csv,
+    /// One flat JSON object per evaluation for Home Assistant `command_line`
+    /// sensors.
+    HomeAssistant,
+    /// `dawn-time=` and `dusk-time=` lines for gammastep and redshift.
+    Redshift,
+}
+
+impl OutputMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "waybar" => Some(Self::Waybar),
+            "json" => Some(Self::Json),
+            "ndjson" => Some(Self::Ndjson),
+            "csv" => Some(Self::Csv),
+            "home-assistant" => Some(Self::HomeAssistant),
+            "redshift" => Some(Self::Redshift),
+            _ => None,
+        }
+    }
+}
+
+/// Renders the waybar custom-module object for `now`:
+/// `{"text": "14:03", "tooltip": "...", "class": "day"}`.
+///
+/// `class` is `day` while the sun is above the horizon, `twilight` down to
+/// civil twilight and `night` below.
+pub fn waybar(clock: &SolarClock, now: DateTime<Utc>) -> Result<String, SolarClockError> {
+    let result = clock.solar_time(now)?;
+    let elevation = solar_elevation(now, clock.coordinates())
+        .map_err(|_| SolarClockError::SolarPositionFailed)?;
+    let class = if elevation >= HORIZON {
+        "day"
+    } else if elevation >= CIVIL_TWILIGHT {
+        "twilight"
+    } else {
+        "night"
+    };
+
+    let mut tooltip = format!("Δ {}", format_signed_hms(result.delta));
+    if let Some((anchor, _, _)) = clock.time_to_next_anchor(now)? {
+        let _ = write!(
+            tooltip,
+            "\n{} (solar {}) at {} civil",
+            capitalize(&anchor.name),
+            anchor.target.with_timezone(&clock.offset()).format("%H:%M"),
+            anchor.real.with_timezone(&Local).format("%H:%M")
+        );
+    }
+
+    Ok(format!(
+        "{{\"text\": {}, \"tooltip\": {}, \"class\": {}}}",
+        json::string(&result.solar_time.format("%H:%M").to_string()),
+        json::string(&tooltip),
+        json::string(class)
+    ))
+}
+
+/// Waybar object reporting a failed evaluation, so that persistent modules
+/// keep receiving one line per tick.
+pub fn waybar_error(e: &SolarClockError) -> String {
+    format!(
+        "{{\"text\": \"--:--\", \"tooltip\": {}, \"class\": \"error\"}}",
+        json::string(&e.to_string())
+    )
+}
+
+pub fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Renders the Home Assistant object for `now`: `{"state": "14:03",
+/// "attributes": {...}}` with the solar time as the state and flat
+/// attributes: `solar_time` (RFC 3339), `delta_s`, `delta` (as `+44m12s`),
+/// `next_event` and `next_event_target` (the name and solar wall time of the
+/// next anchor), `next_event_time` (its civil instant, RFC 3339),
+/// `day_length_s`, `latitude` and `longitude`. The next event attributes
+/// are `null` when no anchor is coming.
+///
+/// For a `command_line` sensor:
+///
+/// ```yaml
+/// command_line:
+///   - sensor:
+///       name: Solar clock
+///       command: solar-clock-rs --output home-assistant
+///       value_template: "{{ value_json.state }}"
+///       json_attributes_path: "$.attributes"
+///       json_attributes: [solar_time, delta_s, delta, next_event,
+///         next_event_target, next_event_time, day_length_s, latitude,
+///         longitude]
+///       scan_interval: 60
+/// ```
+pub fn home_assistant(clock: &SolarClock, now: DateTime<Utc>) -> Result<String, SolarClockError> {
+    let result = clock.solar_time(now)?;
+    let offset = clock.offset();
+    let coords = clock.coordinates();
+    let day_length = day_length_with_zenith(
+        now.with_timezone(&offset).date_naive(),
+        coords,
+        clock.options().horizon_zenith(),
+    )
+    .map_err(|_| SolarClockError::SolarPositionFailed)?;
+
+    let mut attributes = Object::new()
+        .time("solar_time", Some(result.solar_time))
+        .number("delta_s", result.delta)
+        .string("delta", &format_signed_hms(result.delta));
+    attributes = match clock.time_to_next_anchor(now)? {
+        Some((anchor, _, _)) => attributes
+            .string("next_event", &anchor.name)
+            .string(
+                "next_event_target",
+                &anchor
+                    .target
+                    .with_timezone(&offset)
+                    .format("%H:%M")
+                    .to_string(),
+            )
+            .time("next_event_time", Some(anchor.real.with_timezone(&offset))),
+        None => attributes
+            .raw("next_event", "null")
+            .raw("next_event_target", "null")
+            .raw("next_event_time", "null"),
+    };
+    let attributes = attributes
+        .number("day_length_s", day_length.duration().as_seconds_f64())
+        .number("latitude", coords.latitude())
+        .number("longitude", coords.longitude())
+        .finish();
+
+    Ok(Object::new()
+        .string("state", &result.solar_time.format("%H:%M").to_string())
+        .raw("attributes", &attributes)
+        .finish())
+}
+
+/// Home Assistant object reporting a failed evaluation: the `unavailable`
+/// state and the reason in the `error` attribute.
+pub fn home_assistant_error(e: &SolarClockError) -> String {
+    let attributes = Object::new().string("error", &e.to_string()).finish();
+    Object::new()
+        .string("state", "unavailable")
+        .raw("attributes", &attributes)
+        .finish()
+}
+
+/// Renders the `--json` document for `dt`: the fields of
+/// [`SolarClockResult`](solar_clock_rs::SolarClockResult) (`input`,
+/// `solar_time`, `delta` in seconds, `offset`, `evaluation`, `anchors`,
+/// `interpolation`, `linear_fallback`, `mode`, `window_days`), plus
+/// `coordinates`, the configured `targets` and the `events` of the UTC date
+/// of the instant, in the solar offset.
+pub fn json(clock: &SolarClock, dt: DateTime<FixedOffset>) -> Result<String, SolarClockError> {
+    let result = clock.solar_time(dt.with_timezone(&Utc))?;
+    let date = result.input.date_naive();
+    let data = calculate_solar_data_with_zenith(
+        date,
+        clock.coordinates(),
+        clock.options().horizon_zenith(),
+    )
+    .map_err(|_| SolarClockError::SolarPositionFailed)?;
+
+    let evaluation = match result.evaluation {
+        Evaluation::Interpolated => "Interpolated",
+        Evaluation::WindowEdge => "WindowEdge",
+    };
+    let mode = match result.mode {
+        AnchorMode::Events => "events",
+        AnchorMode::PolarFallback => "polar_fallback",
+    };
+    let offset = clock.offset();
+    let events = Object::new()
+        .string("date", &date.to_string())
+        .time("midnight", Some(data.midnight.with_timezone(&offset)))
+        .time("sunrise", data.sunrise.map(|t| t.with_timezone(&offset)))
+        .time("transit", Some(data.transit.with_timezone(&offset)))
+        .time("sunset", data.sunset.map(|t| t.with_timezone(&offset)))
+        .finish();
+    let document = Object::new()
+        .time("input", Some(dt))
+        .time("solar_time", Some(result.solar_time))
+        .number("delta", result.delta)
+        .string("offset", &result.offset.to_string())
+        .string("evaluation", evaluation)
+        .raw(
+            "anchors",
+            &json::array(result.anchors.iter().map(anchor_json)),
+        )
+        .string("interpolation", result.interpolation.name())
+        .bool("linear_fallback", result.linear_fallback)
+        .string("mode", mode)
+        .number("window_days", f64::from(result.window_days))
+        .raw("coordinates", &coordinates_json(clock))
+        .raw(
+            "targets",
+            &json::array(clock.options().anchors.iter().map(anchor_spec_json)),
+        )
+        .raw("events", &events)
+        .finish();
+    Ok(document)
+}
+
+/// `{"error": "..."}` for a failed evaluation in watch mode.
+pub fn json_error(e: &SolarClockError) -> String {
+    Object::new().string("error", &e.to_string()).finish()
+}
+
+/// Renders the NDJSON line of evaluation number `seq`:
+/// `{"seq": 0, "ts": "...", "solar": "...", "delta_s": 2321.5}`, or
+/// `{"seq": 0, "ts": "...", "error": "..."}` when the clock cannot be
+/// evaluated. Consecutive lines have consecutive `seq`, so gaps reveal
+/// dropped lines.
+pub fn ndjson(clock: &SolarClock, now: DateTime<Utc>, seq: u64) -> String {
+    let line = Object::new()
+        .raw("seq", &seq.to_string())
+        .time("ts", Some(now));
+    match clock.solar_time(now) {
+        Ok(result) => line
+            .time("solar", Some(result.solar_time))
+            .number("delta_s", result.delta),
+        Err(e) => line.string("error", &e.to_string()),
+    }
+    .finish()
+}
+
+/// Joins `fields` into a CSV record as in RFC 4180, quoting the fields that
+/// contain a comma, a double quote or a line break.
+pub fn csv_record<S: AsRef<str>>(fields: &[S]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_owned()
+            }
+        })
+        .collect();
+    fields.join(",")
+}
+
+/// `{"latitude": 40.4168, "longitude": -3.7038}`.
+pub fn coordinates_json(clock: &SolarClock) -> String {
+    let coords = clock.coordinates();
+    Object::new()
+        .number("latitude", coords.latitude())
+        .number("longitude", coords.longitude())
+        .finish()
+}
+
+/// Configured anchor: `{"name": "sunrise", "event": "Sunrise", "target":
+/// "08:00:00"}`, the shape of [`AnchorSpec`].
+pub fn anchor_spec_json(spec: &AnchorSpec) -> String {
+    Object::new()
+        .string("name", &spec.name)
+        .raw("event", &event_json(spec.event))
+        .string("target", &spec.target.to_string())
+        .finish()
+}
+
+/// Model anchor in the shape of [`Anchor`], plus its `delta` in seconds.
+pub fn anchor_json(anchor: &Anchor) -> String {
+    Object::new()
+        .string("name", &anchor.name)
+        .raw("event", &event_json(anchor.event))
+        .string("date", &anchor.date.to_string())
+        .time("real", Some(anchor.real))
+        .time("target", Some(anchor.target))
+        .number("delta", anchor.delta())
+        .finish()
+}
+
+/// `"Sunrise"`, or `{"Elevation": {"degrees": -6, "rising": true}}`.
+fn event_json(event: AnchorEvent) -> String {
+    match event {
+        AnchorEvent::Midnight => json::string("Midnight"),
+        AnchorEvent::Sunrise => json::string("Sunrise"),
+        AnchorEvent::Transit => json::string("Transit"),
+        AnchorEvent::Sunset => json::string("Sunset"),
+        AnchorEvent::Elevation { degrees, rising } => Object::new()
+            .raw(
+                "Elevation",
+                &Object::new()
+                    .number("degrees", degrees)
+                    .bool("rising", rising)
+                    .finish(),
+            )
+            .finish(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solar_clock_rs::solar_clock::{
+        DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET,
+    };
+
+    use super::*;
+
+    fn madrid() -> SolarClock {
+        SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET)
+    }
+
+    #[test]
+    fn waybar_class_follows_the_sun_elevation() {
+        let clock = madrid();
+        let object = |at: &str| -> serde_json::Value {
+            let now: DateTime<Utc> = at.parse().unwrap();
+            serde_json::from_str(&waybar(&clock, now).unwrap()).unwrap()
+        };
+
+        // Sunset at 17:35, end of civil twilight about half an hour later
+        for (at, class, next) in [
+            ("2026-02-03T12:00:00Z", "day", "Transit (solar 14:00)"),
+            ("2026-02-03T17:50:00Z", "twilight", "Sunrise (solar 08:00)"),
+            ("2026-02-03T23:00:00Z", "night", "Sunrise (solar 08:00)"),
+        ] {
+            let object = object(at);
+            assert_eq!(object["class"], class, "{at}");
+            let now: DateTime<Utc> = at.parse().unwrap();
+            let result = clock.solar_time(now).unwrap();
+            assert_eq!(
+                object["text"],
+                result.solar_time.format("%H:%M").to_string()
+            );
+            let tooltip = object["tooltip"].as_str().unwrap();
+            let (delta, next_anchor) = tooltip.split_once('\n').unwrap();
+            assert_eq!(delta, format!("Δ {}", format_signed_hms(result.delta)));
+            assert!(next_anchor.starts_with(next), "{tooltip}");
+            assert!(next_anchor.ends_with(" civil"), "{tooltip}");
+        }
+
+        let error: serde_json::Value =
+            serde_json::from_str(&waybar_error(&SolarClockError::SolarPositionFailed)).unwrap();
+        assert_eq!(error["text"], "--:--");
+        assert_eq!(error["class"], "error");
+        assert_eq!(
+            error["tooltip"],
+            SolarClockError::SolarPositionFailed.to_string()
+        );
+    }
+
+    #[test]
+    fn csv_records_quote_only_when_needed() {
+        assert_eq!(csv_record(&["2026-02-03", "+47m01s"]), "2026-02-03,+47m01s");
+        assert_eq!(
+            csv_record(&["a,b", "say \"hi\"", "two\nlines", ""]),
+            "\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\","
+        );
+    }
+}
@@ -0,0 +1,125 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Python bindings, behind the `python` feature.
+//!
+//! Built with `maturin develop` as the `solar_clock` module:
+//!
+//! ```python
+//! from datetime import datetime, time, timezone, timedelta
+//! from solar_clock import SolarClock
+//!
+//! clock = SolarClock(40.4168, -3.7038, (time(8), time(14), time(20)),
+//!                    timezone(timedelta(hours=1)))
+//! clock.solar_time(datetime.now(timezone.utc))
+//! ```
+//!
+//! Datetimes must be timezone-aware; any `tzinfo` is accepted. Results are in
+//! the clock's offset, rounded to the microsecond Python can hold.
+//!
+//! The tests in `tests/python` run with `pip install -e '.[test]'` or
+//! `maturin develop`, then `pytest`.
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, SubsecRound, Utc};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDateTime, timezone_utc};
+
+use crate::builder::SolarClockBuilder;
+use crate::events::events_between;
+use crate::solar_clock::{SolarClock, SolarClockResult};
+
+/// Solar clock as seen from Python, exported as `SolarClock`. The model
+/// cache is not thread-safe, so instances stay on the thread that made them.
+#[pyclass(name = "SolarClock", unsendable)]
+pub struct PySolarClock {
+    clock: SolarClock,
+}
+
+#[pymethods]
+impl PySolarClock {
+    /// Clock for the observer at `latitude`, `longitude`, with `targets` as a
+    /// `(sunrise, transit, sunset)` tuple of `datetime.time` and the solar
+    /// wall time in the fixed `offset` timezone, or the defaults when omitted.
+    #[new]
+    #[pyo3(signature = (latitude, longitude, targets = None, offset = None))]
+    fn new(
+        latitude: f64,
+        longitude: f64,
+        targets: Option<(NaiveTime, NaiveTime, NaiveTime)>,
+        offset: Option<FixedOffset>,
+    ) -> PyResult<Self> {
+        let mut builder = SolarClockBuilder::new().coordinates(latitude, longitude);
+        if let Some((sunrise, transit, sunset)) = targets {
+            builder = builder.targets(sunrise, transit, sunset);
+        }
+        if let Some(offset) = offset {
+            builder = builder.solar_offset(offset);
+        }
+        let clock = builder
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { clock })
+    }
+
+    /// Solar wall time at `dt`, as a datetime in the clock's offset.
+    fn solar_time(&self, dt: &Bound<'_, PyDateTime>) -> PyResult<DateTime<FixedOffset>> {
+        let result = self.evaluate(dt)?;
+        Ok(result.solar_time.round_subsecs(6))
+    }
+
+    /// Seconds added to `dt` to obtain the solar time.
+    fn delta(&self, dt: &Bound<'_, PyDateTime>) -> PyResult<f64> {
+        Ok(self.evaluate(dt)?.delta)
+    }
+
+    /// Sunrise, transit and sunset of `date` in the clock's offset, as
+    /// `(kind, datetime)` pairs. Events that do not occur are left out.
+    fn events(&self, date: NaiveDate) -> PyResult<Vec<(&'static str, DateTime<FixedOffset>)>> {
+        let offset = self.clock.offset();
+        let start = (date.and_time(NaiveTime::MIN) - offset).and_utc();
+        let end = start + Duration::days(1) - Duration::nanoseconds(1);
+        events_between(start, end, self.clock.coordinates())
+            .map(|event| {
+                let event = event.map_err(|e| PyValueError::new_err(e.to_string()))?;
+                let time = event.time.with_timezone(&offset).round_subsecs(6);
+                Ok((event.kind.name(), time))
+            })
+            .collect()
+    }
+}
+
+impl PySolarClock {
+    fn evaluate(&self, dt: &Bound<'_, PyDateTime>) -> PyResult<SolarClockResult> {
+        // A naive datetime would be read in the system's local time
+        if dt.getattr("tzinfo")?.is_none() {
+            return Err(PyValueError::new_err("datetime must be timezone-aware"));
+        }
+        // Converting through UTC accepts any tzinfo, not only fixed offsets
+        let utc = timezone_utc(dt.py());
+        let dt: DateTime<Utc> = dt.call_method1("astimezone", (utc,))?.extract()?;
+        self.clock
+            .solar_time(dt)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+}
+
+#[pymodule]
+fn solar_clock(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PySolarClock>()
+}
@@ -0,0 +1,206 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Serde helpers for types without a serde representation of their own, and
+//! validating shadows of the public configuration types.
+
+use chrono::NaiveTime;
+use serde::Deserialize;
+
+use crate::cct::CctCurve;
+use crate::error::{CctError, CoordError, TargetsError};
+use crate::solar_clock::{Coordinates, SolarTargets};
+
+/// (De)serializes a `FixedOffset` as a `±HH:MM` string.
+pub(crate) mod fixed_offset {
+    use chrono::FixedOffset;
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub(crate) fn serialize<S: Serializer>(
+        offset: &FixedOffset,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(offset)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<FixedOffset, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RawCoordinates {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl TryFrom<RawCoordinates> for Coordinates {
+    type Error = CoordError;
+
+    fn try_from(raw: RawCoordinates) -> Result<Self, Self::Error> {
+        Coordinates::new(raw.latitude, raw.longitude)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RawTargets {
+    sunrise: NaiveTime,
+    transit: NaiveTime,
+    sunset: NaiveTime,
+    #[serde(default)]
+    midnight: Option<NaiveTime>,
+}
+
+impl TryFrom<RawTargets> for SolarTargets {
+    type Error = TargetsError;
+
+    fn try_from(raw: RawTargets) -> Result<Self, Self::Error> {
+        SolarTargets::new(raw.sunrise, raw.transit, raw.sunset)?.with_midnight(raw.midnight)
+    }
+}
+
+#[derive(Deserialize)]
+pub(crate) struct RawCctCurve {
+    night: f64,
+    day: f64,
+    morning: (NaiveTime, NaiveTime),
+    evening: (NaiveTime, NaiveTime),
+}
+
+impl TryFrom<RawCctCurve> for CctCurve {
+    type Error = CctError;
+
+    fn try_from(raw: RawCctCurve) -> Result<Self, Self::Error> {
+        CctCurve::new(raw.night, raw.day, raw.morning, raw.evening)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, NaiveDate, Utc};
+    use serde::Serialize;
+    use serde::de::DeserializeOwned;
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::solar_clock::{DEFAULT_COORDINATES, DEFAULT_TARGETS, Point, SolarClock};
+    use crate::spa::{self, SolarData};
+
+    /// Serializes `value`, checks that it reads back equal, and returns the
+    /// JSON for inspection.
+    fn round_trip<T>(value: &T) -> Value
+    where
+        T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let json = serde_json::to_value(value).unwrap();
+        assert_eq!(&serde_json::from_value::<T>(json.clone()).unwrap(), value);
+        json
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn configuration_round_trips() {
+        assert_eq!(
+            round_trip(&DEFAULT_COORDINATES),
+            json!({ "latitude": 40.4168, "longitude": -3.7038 })
+        );
+        assert_eq!(
+            round_trip(&DEFAULT_TARGETS),
+            json!({
+                "sunrise": "08:00:00",
+                "transit": "14:00:00",
+                "sunset": "20:00:00",
+                "midnight": null,
+            })
+        );
+        let targets = DEFAULT_TARGETS.with_midnight(Some(time(2, 0))).unwrap();
+        assert_eq!(round_trip(&targets)["midnight"], "02:00:00");
+        assert_eq!(
+            round_trip(&Point { x: 1.5, y: -2.0 }),
+            json!({ "x": 1.5, "y": -2.0 })
+        );
+
+        let targets: SolarTargets = serde_json::from_value(
+            json!({ "sunrise": "07:30:00", "transit": "13:00:00", "sunset": "19:45:00" }),
+        )
+        .unwrap();
+        assert_eq!(targets.transit(), time(13, 0));
+        // A missing midnight reads as none
+        assert_eq!(targets.midnight(), None);
+    }
+
+    #[test]
+    fn invalid_configurations_are_rejected() {
+        let coords = json!({ "latitude": 91.0, "longitude": 0.0 });
+        assert!(serde_json::from_value::<Coordinates>(coords).is_err());
+        let coords = json!({ "latitude": 0.0, "longitude": -180.5 });
+        assert!(serde_json::from_value::<Coordinates>(coords).is_err());
+
+        let targets = json!({ "sunrise": "14:00:00", "transit": "08:00:00", "sunset": "20:00:00" });
+        assert!(serde_json::from_value::<SolarTargets>(targets).is_err());
+        let targets = json!({
+            "sunrise": "08:00:00",
+            "transit": "14:00:00",
+            "sunset": "20:00:00",
+            "midnight": "09:00:00",
+        });
+        assert!(serde_json::from_value::<SolarTargets>(targets).is_err());
+    }
+
+    #[test]
+    fn solar_data_round_trips() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let data = spa::calculate_solar_data(date, &DEFAULT_COORDINATES).unwrap();
+        let json = round_trip(&data);
+        let transit = json["transit"].as_str().unwrap();
+        assert_eq!(DateTime::parse_from_rfc3339(transit).unwrap(), data.transit);
+        assert!(transit.starts_with("2026-02-03T") && transit.ends_with('Z'));
+
+        // Polar night in Tromsø: no sunrise nor sunset
+        let tromso = Coordinates::new(69.6492, 18.9553).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 12, 21).unwrap();
+        let data: SolarData = spa::calculate_solar_data(date, &tromso).unwrap();
+        let json = round_trip(&data);
+        for field in ["sunrise", "sunset", "sunrise_azimuth", "sunset_azimuth"] {
+            assert_eq!(json[field], Value::Null, "{field}");
+        }
+    }
+
+    #[test]
+    fn results_round_trip() {
+        let clock = SolarClock::new(
+            DEFAULT_COORDINATES,
+            DEFAULT_TARGETS,
+            crate::solar_clock::SOLAR_TIMEZONE_OFFSET,
+        );
+        let input: DateTime<Utc> = "2026-02-03T12:00:00Z".parse().unwrap();
+        let result = clock.solar_time(input).unwrap();
+        let json = round_trip(&result);
+        assert_eq!(json["input"], "2026-02-03T12:00:00Z");
+        assert_eq!(json["offset"], "+01:00");
+        assert!(json["solar_time"].as_str().unwrap().ends_with("+01:00"));
+        assert_eq!(json["anchors"].as_array().unwrap().len(), 9);
+        assert_eq!(json["anchors"][0]["date"], "2026-02-02");
+    }
+}
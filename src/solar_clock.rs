@@ -0,0 +1,2271 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Solar clock: maps civil time onto a clock whose wall time is pinned to the
+//! sun. Sunrise, transit and sunset are anchored to fixed target hours and the
+//! offset in between is interpolated with a monotone cubic (PCHIP).
+
+use std::cell::RefCell;
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+#[cfg(feature = "tracing")]
+use tracing::{debug, trace, warn};
+
+pub use crate::epoch::Point;
+use crate::error::{CoordError, InterpError, SolarClockError, TargetsError};
+use crate::interp::{InterpolationMethod, Interpolator};
+use crate::spa;
+
+/// Fixed UTC offset in which the solar clock wall time is expressed (+01:00).
+pub const SOLAR_TIMEZONE_OFFSET: FixedOffset = match FixedOffset::east_opt(3600) {
+    Some(offset) => offset,
+    None => panic!("invalid solar timezone offset"),
+};
+
+/// Geographic location of the observer, in decimal degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "crate::serde_utils::RawCoordinates")
+)]
+pub struct Coordinates {
+    /// Latitude, positive north.
+    pub(crate) latitude: f64,
+    /// Longitude, positive east.
+    pub(crate) longitude: f64,
+}
+
+impl Coordinates {
+    /// Validates that `latitude` lies in `[-90, 90]` and `longitude` in
+    /// `[-180, 180]`, both inclusive.
+    pub fn new(latitude: f64, longitude: f64) -> Result<Self, CoordError> {
+        if !(-90.0..=90.0).contains(&latitude) {
+            return Err(CoordError::InvalidLatitude(latitude));
+        }
+        if !(-180.0..=180.0).contains(&longitude) {
+            return Err(CoordError::InvalidLongitude(longitude));
+        }
+        Ok(Self {
+            latitude,
+            longitude,
+        })
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+}
+
+/// Default observer location (Madrid, Spain).
+pub const DEFAULT_COORDINATES: Coordinates = Coordinates {
+    latitude: 40.4168,
+    longitude: -3.7038,
+};
+
+/// Solar clock wall times that sunrise, transit, sunset and optionally solar
+/// midnight are pinned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::serde_utils::RawTargets"))]
+pub struct SolarTargets {
+    pub(crate) sunrise: NaiveTime,
+    pub(crate) transit: NaiveTime,
+    pub(crate) sunset: NaiveTime,
+    /// Target of the solar midnight that precedes the transit, if anchored.
+    pub(crate) midnight: Option<NaiveTime>,
+}
+
+impl SolarTargets {
+    /// Validates that `sunrise < transit < sunset`. Solar midnight is not
+    /// anchored; see [`with_midnight`](Self::with_midnight).
+    ///
+    /// All three targets must fall within the same solar day; schedules that
+    /// wrap around midnight are rejected rather than reinterpreted.
+    pub fn new(
+        sunrise: NaiveTime,
+        transit: NaiveTime,
+        sunset: NaiveTime,
+    ) -> Result<Self, TargetsError> {
+        check_order(&[sunrise, transit, sunset])?;
+        Ok(Self {
+            sunrise,
+            transit,
+            sunset,
+            midnight: None,
+        })
+    }
+
+    /// Anchors solar midnight to `midnight`, which must precede the sunrise
+    /// target, or stops anchoring it when `None`.
+    ///
+    /// Without it the night is a single long segment from sunset to the next
+    /// sunrise, whose shape is only constrained by the daytime anchors.
+    pub fn with_midnight(self, midnight: Option<NaiveTime>) -> Result<Self, TargetsError> {
+        if let Some(midnight) = midnight {
+            check_order(&[midnight, self.sunrise])?;
+        }
+        Ok(Self { midnight, ..self })
+    }
+
+    pub fn sunrise(&self) -> NaiveTime {
+        self.sunrise
+    }
+
+    pub fn transit(&self) -> NaiveTime {
+        self.transit
+    }
+
+    pub fn sunset(&self) -> NaiveTime {
+        self.sunset
+    }
+
+    pub fn midnight(&self) -> Option<NaiveTime> {
+        self.midnight
+    }
+
+    /// Anchor specification equivalent to these targets.
+    pub fn anchors(&self) -> Vec<AnchorSpec> {
+        let midnight = self
+            .midnight
+            .map(|target| AnchorSpec::new("midnight", AnchorEvent::Midnight, target));
+        midnight
+            .into_iter()
+            .chain([
+                AnchorSpec::new("sunrise", AnchorEvent::Sunrise, self.sunrise),
+                AnchorSpec::new("transit", AnchorEvent::Transit, self.transit),
+                AnchorSpec::new("sunset", AnchorEvent::Sunset, self.sunset),
+            ])
+            .collect()
+    }
+}
+
+/// Checks that `targets` are strictly increasing.
+pub(crate) fn check_order(targets: &[NaiveTime]) -> Result<(), TargetsError> {
+    match targets.windows(2).find(|w| w[0] >= w[1]) {
+        Some(w) => Err(TargetsError::Unordered {
+            earlier: w[0],
+            later: w[1],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Suggested target of solar midnight when it is anchored, opposite the
+/// 14:00 transit.
+pub const DEFAULT_MIDNIGHT: NaiveTime = NaiveTime::from_hms_opt(2, 0, 0).unwrap();
+
+/// Default targets: sunrise at 08:00, transit at 14:00 and sunset at 20:00,
+/// with solar midnight not anchored, like [`SolarTargets::new`].
+pub const DEFAULT_TARGETS: SolarTargets = SolarTargets {
+    sunrise: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+    transit: NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+    sunset: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+    midnight: None,
+};
+
+/// Default number of days taken on each side of the evaluated date.
+pub const DEFAULT_WINDOW_DAYS: u32 = 1;
+
+/// Default cap on the window when it has to be widened.
+pub const DEFAULT_MAX_WINDOW_DAYS: u32 = 15;
+
+/// Default distance in seconds below which anchors are merged.
+pub const DEFAULT_MERGE_EPSILON: f64 = 1.0;
+
+/// Days over which [`ModelOptions::polar_fallback`] blends back to the
+/// configured targets after sunrise and sunset reappear, and away from them
+/// before they disappear.
+pub const POLAR_BLEND_DAYS: u32 = 14;
+
+/// Daily solar event that can be pinned to a solar wall time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AnchorEvent {
+    /// Solar midnight (lower culmination) preceding the day's transit.
+    Midnight,
+    Sunrise,
+    Transit,
+    Sunset,
+    /// The sun's center crossing `degrees` of elevation, in the morning when
+    /// `rising` or in the evening otherwise.
+    Elevation {
+        degrees: f64,
+        rising: bool,
+    },
+}
+
+/// Named anchor: a daily event and the solar wall time it is pinned to.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnchorSpec {
+    pub name: String,
+    pub event: AnchorEvent,
+    pub target: NaiveTime,
+}
+
+impl AnchorSpec {
+    pub fn new(name: impl Into<String>, event: AnchorEvent, target: NaiveTime) -> Self {
+        Self {
+            name: name.into(),
+            event,
+            target,
+        }
+    }
+}
+
+/// How the delta of a [`SolarClockResult`] was obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Evaluation {
+    /// The input lies between anchors well inside the window.
+    Interpolated,
+    /// The input lies in the first or last segment of the window, where the
+    /// interpolant is only constrained from one side.
+    WindowEdge,
+}
+
+/// Which events the model of a [`SolarClockResult`] is anchored to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AnchorMode {
+    /// The configured anchors, skipping events that do not occur.
+    Events,
+    /// Sunrise or sunset is missing on some day within
+    /// [`POLAR_BLEND_DAYS`] of the window, so the clock runs as a mean solar
+    /// time shifted onto the transit target, blending into the configured
+    /// targets around the polar season. See
+    /// [`ModelOptions::polar_fallback`].
+    PolarFallback,
+}
+
+/// Outcome of evaluating the solar clock at a given instant.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarClockResult {
+    /// Solar wall time, expressed in [`offset`](Self::offset).
+    pub solar_time: DateTime<FixedOffset>,
+    /// Seconds added to the civil instant to obtain the solar time.
+    pub delta: f64,
+    /// Civil instant that was evaluated.
+    pub input: DateTime<Utc>,
+    /// Reference offset of the solar wall time.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::fixed_offset"))]
+    pub offset: FixedOffset,
+    pub evaluation: Evaluation,
+    /// Anchors the interpolation model was built from, sorted by real time.
+    pub anchors: Vec<Anchor>,
+    /// Interpolation actually used. It is [`InterpolationMethod::Linear`]
+    /// instead of the configured cubic when [`linear_fallback`](Self::linear_fallback)
+    /// is set.
+    pub interpolation: InterpolationMethod,
+    /// The configured cubic could not be built from the anchors, or would
+    /// have made the solar time run backwards between them, so straight
+    /// segments were used at the cost of a rate that jumps at each anchor.
+    pub linear_fallback: bool,
+    pub mode: AnchorMode,
+    /// Days on each side of the date that the anchors were taken from. It
+    /// exceeds [`ModelOptions::window_days`] when events were missing and the
+    /// window had to be widened, so the anchors are further apart than usual.
+    pub window_days: u32,
+}
+
+/// Returns the UTC instant at which the solar clock should read `target` on `date`.
+pub fn get_target_time(date: NaiveDate, target: NaiveTime, offset: FixedOffset) -> DateTime<Utc> {
+    offset
+        .from_local_datetime(&date.and_time(target))
+        .single()
+        .expect("fixed offsets have no ambiguous local times")
+        .with_timezone(&Utc)
+}
+
+/// Anchor of the interpolation model, annotated with the event it stems from.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Anchor {
+    /// Name of the [`AnchorSpec`] that produced this anchor.
+    pub name: String,
+    pub event: AnchorEvent,
+    /// Day whose event this is.
+    pub date: NaiveDate,
+    /// Instant at which the event actually happens.
+    pub real: DateTime<Utc>,
+    /// Instant at which the solar clock reads the target wall time.
+    pub target: DateTime<Utc>,
+}
+
+impl Anchor {
+    /// Seconds from the real event to its target.
+    pub fn delta(&self) -> f64 {
+        (self.target - self.real).as_seconds_f64()
+    }
+
+    pub fn point(&self) -> Point {
+        Point {
+            x: to_seconds(self.real),
+            y: self.delta(),
+        }
+    }
+}
+
+/// Parameters of the interpolation model.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelOptions {
+    /// Events to anchor and their solar wall times.
+    pub anchors: Vec<AnchorSpec>,
+    /// Reference offset of the target wall times.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_utils::fixed_offset"))]
+    pub offset: FixedOffset,
+    /// Number of days taken on each side of the evaluated date.
+    pub window_days: u32,
+    /// Anchors whose real times are less than this many seconds apart are
+    /// merged into one, averaging their deltas. Zero or less disables merging.
+    #[cfg_attr(feature = "serde", serde(default = "default_merge_epsilon"))]
+    pub merge_epsilon: f64,
+    /// Anchor only transits and solar midnights on days without sunrise or
+    /// sunset, instead of widening the window to reach days where they
+    /// occur. The clock then runs as a mean solar time through polar day
+    /// and night, and moves back to the other targets over
+    /// [`POLAR_BLEND_DAYS`] days on each side of them.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub polar_fallback: bool,
+    /// Largest window the model may widen to when `window_days` does not
+    /// yield anchors on both sides of the evaluated date, or no sunrise or
+    /// sunset among them, as happens around polar day and night. Without any
+    /// at this width the clock fails with
+    /// [`SolarClockError::NoSunriseOrSunset`].
+    #[cfg_attr(feature = "serde", serde(default = "default_max_window_days"))]
+    pub max_window_days: u32,
+    /// What to do when consecutive anchors would make the solar clock run
+    /// backwards, i.e. a later anchor has an earlier or equal target: fail
+    /// with [`SolarClockError::NonMonotonic`] when `true` (the default), or
+    /// drop the offending later anchors when `false`.
+    #[cfg_attr(feature = "serde", serde(default = "default_strict_monotonic"))]
+    pub strict_monotonic: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub interpolation: InterpolationMethod,
+    /// How far sunrise and sunset are refined: [`spa::Refinement::SinglePass`]
+    /// is faster, but up to about half a minute off.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refinement: spa::Refinement,
+    /// ΔT in seconds, terrestrial time minus UT, or `None` for the Espenak
+    /// and Meeus estimate. Only the `accurate` feature's NREL SPA uses it.
+    /// See [`spa::calculate_solar_data_with_delta_t`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub delta_t: Option<f64>,
+    /// Zenith angle in degrees at which sunrise and sunset are taken. See
+    /// [`spa::calculate_solar_data_with_zenith`].
+    #[cfg_attr(feature = "serde", serde(default = "default_zenith"))]
+    pub zenith: f64,
+    /// Observer elevation in metres above the horizon, which lowers the
+    /// horizon by [`spa::horizon_dip`] and makes sunrise earlier and sunset
+    /// later.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub elevation: f64,
+    /// Air temperature in °C for the refraction at the horizon, 10 °C when
+    /// only the pressure is set. See [`spa::refraction_correction`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub temperature: Option<f64>,
+    /// Air pressure in hPa for the refraction at the horizon, 1010 hPa when
+    /// only the temperature is set.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pressure: Option<f64>,
+}
+
+impl ModelOptions {
+    /// Zenith at which sunrise and sunset are taken: [`Self::zenith`] plus
+    /// the dip of the horizon at [`Self::elevation`] and the change of
+    /// refraction for [`Self::temperature`] and [`Self::pressure`], if set.
+    pub fn horizon_zenith(&self) -> f64 {
+        let mut zenith = self.zenith + spa::horizon_dip(self.elevation);
+        if self.temperature.is_some() || self.pressure.is_some() {
+            zenith += spa::refraction_correction(
+                self.temperature.unwrap_or(10.0),
+                self.pressure.unwrap_or(1010.0),
+            );
+        }
+        zenith
+    }
+}
+
+#[cfg(feature = "serde")]
+fn default_strict_monotonic() -> bool {
+    true
+}
+
+#[cfg(feature = "serde")]
+fn default_max_window_days() -> u32 {
+    DEFAULT_MAX_WINDOW_DAYS
+}
+
+#[cfg(feature = "serde")]
+fn default_merge_epsilon() -> f64 {
+    DEFAULT_MERGE_EPSILON
+}
+
+#[cfg(feature = "serde")]
+fn default_zenith() -> f64 {
+    spa::STANDARD_ZENITH
+}
+
+impl Default for ModelOptions {
+    fn default() -> Self {
+        Self {
+            anchors: DEFAULT_TARGETS.anchors(),
+            offset: SOLAR_TIMEZONE_OFFSET,
+            window_days: DEFAULT_WINDOW_DAYS,
+            max_window_days: DEFAULT_MAX_WINDOW_DAYS,
+            merge_epsilon: DEFAULT_MERGE_EPSILON,
+            polar_fallback: false,
+            strict_monotonic: true,
+            interpolation: InterpolationMethod::Pchip,
+            refinement: spa::Refinement::Iterative,
+            delta_t: None,
+            zenith: spa::STANDARD_ZENITH,
+            elevation: 0.0,
+            temperature: None,
+            pressure: None,
+        }
+    }
+}
+
+/// Builds the anchors for `date` and the `window_days` days on each side of
+/// it, sorted by real time.
+///
+/// Events that do not occur on a given day (e.g. sunrise during polar night)
+/// are skipped.
+pub fn build_interpolation_model(
+    date: NaiveDate,
+    coords: &Coordinates,
+    options: &ModelOptions,
+) -> Result<Vec<Anchor>, SolarClockError> {
+    let window = Duration::days(i64::from(options.window_days));
+    anchors_between(date - window, date + window, coords, options)
+}
+
+/// Anchors of every day from `first` to `last`, both inclusive, sorted by
+/// real time.
+fn anchors_between(
+    first: NaiveDate,
+    last: NaiveDate,
+    coords: &Coordinates,
+    options: &ModelOptions,
+) -> Result<Vec<Anchor>, SolarClockError> {
+    let mut anchors = Vec::new();
+
+    for day in first.iter_days().take_while(|day| *day <= last) {
+        let data = spa::calculate_solar_data_with_delta_t(
+            day,
+            coords,
+            options.horizon_zenith(),
+            options.refinement,
+            options.delta_t,
+        )
+        .map_err(|_| SolarClockError::SolarPositionFailed)?;
+        anchors.extend(day_anchors(day, &data, coords, options)?);
+    }
+
+    anchors.sort_by_key(|anchor| anchor.real);
+    Ok(merge_close(anchors, options.merge_epsilon))
+}
+
+/// Anchors of the events of `day` that occur, in the order of
+/// [`ModelOptions::anchors`].
+fn day_anchors(
+    day: NaiveDate,
+    data: &spa::SolarData,
+    coords: &Coordinates,
+    options: &ModelOptions,
+) -> Result<Vec<Anchor>, SolarClockError> {
+    let mut anchors = Vec::new();
+    for spec in &options.anchors {
+        let event = match spec.event {
+            AnchorEvent::Midnight => Some(data.midnight),
+            AnchorEvent::Sunrise => data.sunrise,
+            AnchorEvent::Transit => Some(data.transit),
+            AnchorEvent::Sunset => data.sunset,
+            AnchorEvent::Elevation { degrees, rising } => {
+                spa::elevation_crossing(data.transit, coords, degrees, rising)
+                    .map_err(|_| SolarClockError::SolarPositionFailed)?
+            }
+        };
+        // Sunrise and sunset are absent during polar day and polar night
+        let Some(real) = event else {
+            debug!(name = %spec.name, %day, "event does not occur");
+            continue;
+        };
+        let anchor = Anchor {
+            name: spec.name.clone(),
+            event: spec.event,
+            date: day,
+            real,
+            target: get_target_time(day, spec.target, options.offset),
+        };
+        debug!(
+            name = %anchor.name,
+            event = ?anchor.event,
+            real = %anchor.real,
+            target = %anchor.target,
+            delta = anchor.delta(),
+            "anchor"
+        );
+        anchors.push(anchor);
+    }
+    Ok(anchors)
+}
+
+/// Anchors of every day from `first` to `last` for
+/// [`ModelOptions::polar_fallback`], or `None` when no day lacking sunrise
+/// or sunset is within [`POLAR_BLEND_DAYS`] of them or no transit target
+/// is configured.
+///
+/// Days without sunrise or sunset keep only their transit and solar
+/// midnight, which is pinned half a day before the transit target when it
+/// is not anchored. On the days that follow or precede them, the delta of
+/// every other event moves linearly from the transit delta to its own, so
+/// that the clock returns to the configured targets without a jump.
+fn polar_anchors(
+    first: NaiveDate,
+    last: NaiveDate,
+    coords: &Coordinates,
+    options: &ModelOptions,
+) -> Result<Option<Vec<Anchor>>, SolarClockError> {
+    let Some(transit_target) = options
+        .anchors
+        .iter()
+        .find(|spec| spec.event == AnchorEvent::Transit)
+        .map(|spec| spec.target)
+    else {
+        return Ok(None);
+    };
+    let has_midnight = options
+        .anchors
+        .iter()
+        .any(|spec| spec.event == AnchorEvent::Midnight);
+
+    // Each day's data serves both to find the polar days and to anchor it
+    let blend = Duration::days(i64::from(POLAR_BLEND_DAYS));
+    let days = (first - blend)
+        .iter_days()
+        .take_while(|day| *day <= last + blend)
+        .map(|day| {
+            spa::calculate_solar_data_with_delta_t(
+                day,
+                coords,
+                options.horizon_zenith(),
+                options.refinement,
+                options.delta_t,
+            )
+            .map(|data| (day, data))
+            .map_err(|_| SolarClockError::SolarPositionFailed)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let polar_days: Vec<NaiveDate> = days
+        .iter()
+        .filter(|(_, data)| data.sunrise.is_none() || data.sunset.is_none())
+        .map(|(day, _)| *day)
+        .collect();
+    // Share of its own delta that each event keeps on `day`
+    let weight = |day: NaiveDate| {
+        polar_days
+            .iter()
+            .map(|polar| (day - *polar).num_days().abs())
+            .min()
+            .map_or(1.0, |apart| {
+                (apart as f64 / f64::from(POLAR_BLEND_DAYS)).min(1.0)
+            })
+    };
+    let window = &days[POLAR_BLEND_DAYS as usize..days.len() - POLAR_BLEND_DAYS as usize];
+    if window.iter().all(|(day, _)| weight(*day) >= 1.0) {
+        return Ok(None);
+    }
+
+    let mut anchors = Vec::new();
+    for &(day, ref data) in window {
+        let weight = weight(day);
+        let transit_delta = get_target_time(day, transit_target, options.offset) - data.transit;
+
+        if weight < 1.0 && !has_midnight {
+            anchors.push(Anchor {
+                name: "midnight".to_owned(),
+                event: AnchorEvent::Midnight,
+                date: day,
+                real: data.midnight,
+                target: data.midnight + transit_delta,
+            });
+        }
+        for mut anchor in day_anchors(day, data, coords, options)? {
+            if !matches!(anchor.event, AnchorEvent::Midnight | AnchorEvent::Transit) {
+                if weight <= 0.0 {
+                    continue;
+                }
+                let mean = to_seconds(anchor.real + transit_delta);
+                anchor.target = from_seconds(mean + weight * (to_seconds(anchor.target) - mean));
+            }
+            anchors.push(anchor);
+        }
+    }
+
+    anchors.sort_by_key(|anchor| anchor.real);
+    Ok(Some(merge_close(anchors, options.merge_epsilon)))
+}
+
+/// Merges runs of sorted anchors whose real times are less than `epsilon`
+/// seconds apart into a single anchor at their mean real time and mean
+/// delta. Near the polar circles sunrise, transit and sunset can fall on
+/// nearly the same instant, which the interpolator cannot resolve.
+fn merge_close(anchors: Vec<Anchor>, epsilon: f64) -> Vec<Anchor> {
+    let mut groups: Vec<Vec<Anchor>> = Vec::new();
+    for anchor in anchors {
+        match groups.last_mut() {
+            Some(group)
+                if (anchor.real - group[group.len() - 1].real).as_seconds_f64() < epsilon =>
+            {
+                group.push(anchor)
+            }
+            _ => groups.push(vec![anchor]),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|mut group| {
+            if group.len() == 1 {
+                return group.remove(0);
+            }
+            let count = group.len() as f64;
+            let real = group.iter().map(|a| to_seconds(a.real)).sum::<f64>() / count;
+            let delta = group.iter().map(Anchor::delta).sum::<f64>() / count;
+            let name = group
+                .iter()
+                .map(|a| a.name.as_str())
+                .collect::<Vec<_>>()
+                .join("+");
+            debug!(%name, real, delta, "merged close anchors");
+            Anchor {
+                name,
+                event: group[0].event,
+                date: group[0].date,
+                real: from_seconds(real),
+                target: from_seconds(real + delta),
+            }
+        })
+        .collect()
+}
+
+/// Evaluates the solar clock at `dt`.
+///
+/// Only the physical instant of `dt` matters: the same instant given in any
+/// time zone yields the same result. The solar wall time is expressed in
+/// [`SOLAR_TIMEZONE_OFFSET`], and the anchors are interpolated with
+/// [`InterpolationMethod::Pchip`].
+pub fn calculate_solar_clock<Tz: TimeZone>(
+    dt: DateTime<Tz>,
+    coords: &Coordinates,
+    targets: &SolarTargets,
+) -> Result<SolarClockResult, SolarClockError> {
+    let options = ModelOptions {
+        anchors: targets.anchors(),
+        ..ModelOptions::default()
+    };
+    calculate_solar_clock_with(dt, coords, &options)
+}
+
+/// [`calculate_solar_clock`] with every option of the model, such as the
+/// interpolation method, the solar offset or the anchors.
+pub fn calculate_solar_clock_with<Tz: TimeZone>(
+    dt: DateTime<Tz>,
+    coords: &Coordinates,
+    options: &ModelOptions,
+) -> Result<SolarClockResult, SolarClockError> {
+    let input = dt.with_timezone(&Utc);
+    let date = input.with_timezone(&options.offset).date_naive();
+
+    let model = Model::build(date, coords, options)?;
+    model.solar_time(input)
+}
+
+/// Solar clock for a fixed location and configuration.
+///
+/// The interpolation model is built lazily and cached for the solar date of
+/// the last query; it is only rebuilt when a query falls on another date, so
+/// results are identical to [`calculate_solar_clock`].
+///
+/// Use [`SolarClockBuilder`](crate::SolarClockBuilder) to configure and
+/// validate every parameter.
+#[derive(Debug)]
+pub struct SolarClock {
+    coords: Coordinates,
+    options: ModelOptions,
+    model: RefCell<Option<Model>>,
+}
+
+impl SolarClock {
+    pub fn new(coords: Coordinates, targets: SolarTargets, offset: FixedOffset) -> Self {
+        Self::with_options(
+            coords,
+            ModelOptions {
+                anchors: targets.anchors(),
+                offset,
+                ..ModelOptions::default()
+            },
+        )
+    }
+
+    pub(crate) fn with_options(coords: Coordinates, options: ModelOptions) -> Self {
+        Self {
+            coords,
+            options,
+            model: RefCell::new(None),
+        }
+    }
+
+    pub fn coordinates(&self) -> &Coordinates {
+        &self.coords
+    }
+
+    pub fn options(&self) -> &ModelOptions {
+        &self.options
+    }
+
+    pub fn offset(&self) -> FixedOffset {
+        self.options.offset
+    }
+
+    /// Anchors of the model used for instants on the solar date `date`.
+    pub fn model_anchors(&self, date: NaiveDate) -> Result<Vec<Anchor>, SolarClockError> {
+        build_interpolation_model(date, &self.coords, &self.options)
+    }
+
+    /// Evaluates the solar clock at `dt`, with the solar wall time expressed in
+    /// this clock's offset.
+    pub fn solar_time(&self, dt: DateTime<Utc>) -> Result<SolarClockResult, SolarClockError> {
+        self.with_model(dt, |model| model.solar_time(dt))
+    }
+
+    /// Rate at which solar time advances relative to civil time at `dt`,
+    /// `1 + d(delta)/dt`: the length of a solar second in civil seconds is
+    /// its inverse.
+    pub fn rate_at(&self, dt: DateTime<Utc>) -> Result<f64, SolarClockError> {
+        self.with_model(dt, |model| model.rate(dt))
+    }
+
+    /// Samples the delta every `step` from `start` to `end`, both included
+    /// when reached exactly.
+    ///
+    /// A single model is built for the whole range, with anchors for every
+    /// day touched plus the window on either side, so samples are not
+    /// affected by the per-date window used by [`solar_time`](Self::solar_time).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive.
+    pub fn sample_delta(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        step: Duration,
+    ) -> Result<Vec<(DateTime<Utc>, f64)>, SolarClockError> {
+        assert!(step > Duration::zero(), "sampling step must be positive");
+        if start > end {
+            return Ok(Vec::new());
+        }
+
+        let window = Duration::days(i64::from(self.options.window_days));
+        let first = start.with_timezone(&self.options.offset).date_naive() - window;
+        let last = end.with_timezone(&self.options.offset).date_naive() + window;
+        let model = Model::from_anchors(
+            first,
+            anchors_between(first, last, &self.coords, &self.options)?,
+            &self.options,
+        )?;
+
+        let mut samples = Vec::new();
+        let mut t = start;
+        while t <= end {
+            samples.push((t, model.delta(t)?));
+            t += step;
+        }
+        Ok(samples)
+    }
+
+    /// Runs `f` on the model for the solar date of `dt`, building it when the
+    /// cached one is for another date.
+    fn with_model<T>(
+        &self,
+        dt: DateTime<Utc>,
+        f: impl FnOnce(&Model) -> Result<T, SolarClockError>,
+    ) -> Result<T, SolarClockError> {
+        let date = dt.with_timezone(&self.options.offset).date_naive();
+        self.with_date_model(date, f)
+    }
+
+    /// Runs `f` on the model for the solar date `date`, building it when the
+    /// cached one is for another date.
+    pub(crate) fn with_date_model<T>(
+        &self,
+        date: NaiveDate,
+        f: impl FnOnce(&Model) -> Result<T, SolarClockError>,
+    ) -> Result<T, SolarClockError> {
+        let mut cache = self.model.borrow_mut();
+        match cache.as_ref() {
+            Some(model) if model.date == date => f(model),
+            _ => {
+                let model = Model::build(date, &self.coords, &self.options)?;
+                f(cache.insert(model))
+            }
+        }
+    }
+
+    /// Replaces the cached model, which is then used for instants on its
+    /// solar date.
+    pub(crate) fn set_model(&self, model: Model) {
+        *self.model.borrow_mut() = Some(model);
+    }
+
+    /// Civil instant at which the solar clock reads `dt_solar`.
+    ///
+    /// This inverts [`solar_time`](Self::solar_time) numerically; the mapping
+    /// is monotone, so the instant is unique. Only the physical instant of
+    /// `dt_solar` matters, not its offset. Fails when no model around
+    /// `dt_solar` covers the answer.
+    pub fn solar_to_civil(
+        &self,
+        dt_solar: DateTime<FixedOffset>,
+    ) -> Result<DateTime<Utc>, SolarClockError> {
+        let target = dt_solar.with_timezone(&Utc);
+        // The delta barely changes over its own magnitude, so the delta at the
+        // target is a good first guess for the delta at the root
+        let delta = self.solar_time(target)?.delta;
+        self.invert(to_seconds(target), to_seconds(target) - delta)
+    }
+
+    /// Solar day `dt` belongs to: the date shown by the solar clock, in this
+    /// clock's offset.
+    ///
+    /// Solar days run from the instant the solar clock reads 00:00, included,
+    /// to the next such instant, excluded.
+    pub fn solar_date(&self, dt: DateTime<Utc>) -> Result<NaiveDate, SolarClockError> {
+        Ok(self.solar_time(dt)?.solar_time.date_naive())
+    }
+
+    /// Civil bounds of the solar day containing `dt`, as a half-open interval
+    /// `[start, end)` between consecutive solar midnights.
+    pub fn solar_day_bounds(
+        &self,
+        dt: DateTime<Utc>,
+    ) -> Result<(DateTime<Utc>, DateTime<Utc>), SolarClockError> {
+        let date = self.solar_date(dt)?;
+        let midnight = |date: NaiveDate| -> Result<DateTime<Utc>, SolarClockError> {
+            let solar = get_target_time(date, NaiveTime::MIN, self.options.offset);
+            let guess = self.solar_to_civil(solar.with_timezone(&self.options.offset))?;
+            // The inversion can land a hair to either side of midnight; bisect
+            // for the first microsecond that already belongs to `date`
+            let (mut before, mut after) =
+                (guess - Duration::seconds(1), guess + Duration::seconds(1));
+            if self.solar_date(before)? >= date || self.solar_date(after)? < date {
+                return Err(SolarClockError::InversionDidNotConverge);
+            }
+            while after - before > Duration::microseconds(1) {
+                let middle = before + (after - before) / 2;
+                if self.solar_date(middle)? < date {
+                    before = middle;
+                } else {
+                    after = middle;
+                }
+            }
+            Ok(after)
+        };
+        Ok((midnight(date)?, midnight(date + Duration::days(1))?))
+    }
+
+    /// Next anchor after `dt`, with the time left until it on the civil clock
+    /// and on the solar clock.
+    ///
+    /// The two durations differ by the change in delta until the anchor. The
+    /// anchors are those of the model used at `dt`, which span at least the
+    /// following day, so no solar position is computed when the model is
+    /// already at hand; `None` means no anchor happens in them, as in polar
+    /// day or night.
+    pub fn time_to_next_anchor(
+        &self,
+        dt: DateTime<Utc>,
+    ) -> Result<Option<(Anchor, Duration, Duration)>, SolarClockError> {
+        let (upcoming, solar_now) = self.with_model(dt, |model| {
+            let upcoming = model.anchors.iter().find(|anchor| anchor.real > dt);
+            Ok((upcoming.cloned(), model.solar_time(dt)?))
+        })?;
+        let Some(anchor) = upcoming else {
+            return Ok(None);
+        };
+
+        let solar_now = solar_now.solar_time.with_timezone(&Utc);
+        let civil = anchor.real - dt;
+        let solar = anchor.target - solar_now;
+        Ok(Some((anchor, civil, solar)))
+    }
+
+    /// First instant after `after` at which the solar clock reads the wall
+    /// time `time` in this clock's offset.
+    ///
+    /// When the solar clock has already passed `time` on its current date,
+    /// the result falls on the next solar date.
+    pub fn next_civil_time(
+        &self,
+        after: DateTime<Utc>,
+        time: NaiveTime,
+    ) -> Result<DateTime<Utc>, SolarClockError> {
+        let now = self.solar_time(after)?;
+        let mut date = now.solar_time.date_naive();
+        if now.solar_time.time() >= time {
+            date += Duration::days(1);
+        }
+        let target = get_target_time(date, time, self.options.offset);
+
+        // Start from the current delta, which is close to the one at the root
+        self.invert(to_seconds(target), to_seconds(target) - now.delta)
+    }
+
+    /// Solves `x + delta(x) = target` for the civil instant `x` by bisection,
+    /// starting from a bracket around `guess`.
+    fn invert(&self, target: f64, guess: f64) -> Result<DateTime<Utc>, SolarClockError> {
+        let residual = |x: f64| -> Result<f64, SolarClockError> {
+            let result = self.solar_time(from_seconds(x))?;
+            Ok(x + result.delta - target)
+        };
+
+        let mut step = 3600.0;
+        let (mut lo, mut hi) = (guess - step, guess + step);
+        while residual(lo)? > 0.0 || residual(hi)? < 0.0 {
+            if step > 2.0 * SECONDS_PER_DAY {
+                return Err(SolarClockError::InversionDidNotConverge);
+            }
+            step *= 2.0;
+            lo = guess - step;
+            hi = guess + step;
+        }
+
+        // Down to a microsecond, well below the precision of the anchors
+        while hi - lo > 1e-6 {
+            let mid = 0.5 * (lo + hi);
+            if residual(mid)? < 0.0 {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Ok(from_seconds(0.5 * (lo + hi)))
+    }
+}
+
+/// Interpolator over the anchor window centred on `date`.
+#[derive(Debug)]
+pub(crate) struct Model {
+    pub(crate) date: NaiveDate,
+    offset: FixedOffset,
+    interpolator: Interpolator,
+    linear_fallback: bool,
+    pub(crate) anchors: Vec<Anchor>,
+    pub(crate) window_days: u32,
+    pub(crate) mode: AnchorMode,
+}
+
+impl Model {
+    fn build(
+        date: NaiveDate,
+        coords: &Coordinates,
+        options: &ModelOptions,
+    ) -> Result<Self, SolarClockError> {
+        if options.polar_fallback {
+            let window = Duration::days(i64::from(options.window_days));
+            if let Some(anchors) = polar_anchors(date - window, date + window, coords, options)? {
+                debug!(%date, "polar fallback: anchoring on transits");
+                let model = Self::from_anchors(date, anchors, options)?;
+                return Ok(Self {
+                    mode: AnchorMode::PolarFallback,
+                    ..model
+                });
+            }
+        }
+
+        // Sunrise and sunset can be missing for weeks near the poles: widen
+        // the window a day at a time until the anchors enclose the whole date,
+        // and include one of them if any is configured
+        let start = to_seconds(get_target_time(date, NaiveTime::MIN, options.offset));
+        let end = start + SECONDS_PER_DAY;
+        let horizon =
+            |event: AnchorEvent| matches!(event, AnchorEvent::Sunrise | AnchorEvent::Sunset);
+        let needs_horizon = options.anchors.iter().any(|spec| horizon(spec.event));
+        let mut window_days = options.window_days;
+        loop {
+            let window = Duration::days(i64::from(window_days));
+            let anchors = anchors_between(date - window, date + window, coords, options)?;
+            let model = Self::from_anchors(date, anchors, options);
+            let (enclosed, sunless) = match &model {
+                Ok(model) => {
+                    let xs = model.interpolator.xs();
+                    (
+                        xs[0] <= start && xs[xs.len() - 1] >= end,
+                        needs_horizon && !model.anchors.iter().any(|anchor| horizon(anchor.event)),
+                    )
+                }
+                Err(SolarClockError::InsufficientAnchors { .. }) => (false, false),
+                Err(_) => return model,
+            };
+            if (enclosed && !sunless) || window_days >= options.max_window_days {
+                if sunless {
+                    return Err(SolarClockError::NoSunriseOrSunset { window_days });
+                }
+                return model.map(|model| Self {
+                    window_days,
+                    ..model
+                });
+            }
+            window_days += 1;
+            debug!(%date, window_days, "widening the anchor window");
+        }
+    }
+
+    pub(crate) fn from_anchors(
+        date: NaiveDate,
+        anchors: Vec<Anchor>,
+        options: &ModelOptions,
+    ) -> Result<Self, SolarClockError> {
+        let anchors = monotonic(anchors, options.strict_monotonic)?;
+        if anchors.len() < 2 {
+            return Err(SolarClockError::InsufficientAnchors {
+                count: anchors.len(),
+            });
+        }
+        let points: Vec<Point> = anchors.iter().map(Anchor::point).collect();
+        let xs: Vec<f64> = points.iter().map(|p| p.x).collect();
+        let ys: Vec<f64> = points.iter().map(|p| p.y).collect();
+
+        // A cubic that cannot be built, or that would run the clock backwards
+        // between anchors, degrades to straight segments rather than leaving
+        // the clock without a result. Straight segments run forwards whenever
+        // the targets do.
+        let linear = || Interpolator::new(InterpolationMethod::Linear, &xs, &ys);
+        let interpolator = match Interpolator::new(options.interpolation, &xs, &ys) {
+            #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+            Err(e @ InterpError::NonFiniteSlope(_)) => {
+                warn!(
+                    method = options.interpolation.name(),
+                    "{e}; falling back to linear interpolation"
+                );
+                linear()
+            }
+            Ok(cubic) if cubic.min_derivative() <= -1.0 => {
+                warn!(
+                    method = options.interpolation.name(),
+                    "solar time runs backwards between anchors; falling back to linear interpolation"
+                );
+                linear()
+            }
+            result => result,
+        }
+        .map_err(SolarClockError::Interpolation)?;
+        let linear_fallback = interpolator.method() != options.interpolation;
+
+        Ok(Self {
+            date,
+            offset: options.offset,
+            interpolator,
+            linear_fallback,
+            anchors,
+            window_days: options.window_days,
+            mode: AnchorMode::Events,
+        })
+    }
+
+    /// Interpolated delta at `input`, failing outside the anchors and on a
+    /// non-finite value rather than falling back to a delta of zero.
+    fn delta(&self, input: DateTime<Utc>) -> Result<f64, SolarClockError> {
+        let delta = self
+            .interpolator
+            .evaluate(to_seconds(input))
+            .ok_or_else(|| self.out_of_range())?;
+        finite(delta, input)
+    }
+
+    fn rate(&self, input: DateTime<Utc>) -> Result<f64, SolarClockError> {
+        let derivative = self
+            .interpolator
+            .derivative(to_seconds(input))
+            .ok_or_else(|| self.out_of_range())?;
+        finite(1.0 + derivative, input)
+    }
+
+    fn out_of_range(&self) -> SolarClockError {
+        SolarClockError::OutOfRange {
+            earliest: from_seconds(self.interpolator.xs()[0]),
+            latest: from_seconds(self.interpolator.xs()[self.interpolator.segments()]),
+        }
+    }
+
+    fn solar_time(&self, input: DateTime<Utc>) -> Result<SolarClockResult, SolarClockError> {
+        let x = to_seconds(input);
+        let delta = self.delta(input)?;
+        let segment = self
+            .interpolator
+            .segment(x)
+            .ok_or_else(|| self.out_of_range())?;
+        trace!(x, delta, segment, "evaluated interpolator");
+        let evaluation = if segment == 0 || segment + 1 == self.interpolator.segments() {
+            Evaluation::WindowEdge
+        } else {
+            Evaluation::Interpolated
+        };
+
+        // Split into whole seconds and nanoseconds to keep sub-second precision
+        let secs = delta.trunc() as i64;
+        let nanos = ((delta - delta.trunc()) * 1e9).round() as i64;
+        let solar = input + Duration::seconds(secs) + Duration::nanoseconds(nanos);
+
+        Ok(SolarClockResult {
+            solar_time: solar.with_timezone(&self.offset),
+            delta,
+            input,
+            offset: self.offset,
+            evaluation,
+            anchors: self.anchors.clone(),
+            interpolation: self.interpolator.method(),
+            linear_fallback: self.linear_fallback,
+            window_days: self.window_days,
+            mode: self.mode,
+        })
+    }
+}
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+fn finite(value: f64, input: DateTime<Utc>) -> Result<f64, SolarClockError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(SolarClockError::EvaluationFailed { input })
+    }
+}
+
+/// Ensures the targets increase with the real times, so that the solar clock
+/// never runs backwards across an anchor. Violations are errors when `strict`,
+/// otherwise the later anchor of each violating pair is dropped.
+fn monotonic(anchors: Vec<Anchor>, strict: bool) -> Result<Vec<Anchor>, SolarClockError> {
+    let mut kept: Vec<Anchor> = Vec::with_capacity(anchors.len());
+    for anchor in anchors {
+        match kept.last() {
+            Some(previous) if anchor.target <= previous.target => {
+                if strict {
+                    return Err(SolarClockError::NonMonotonic {
+                        earlier: previous.target,
+                        later: anchor.target,
+                    });
+                }
+                debug!(name = %anchor.name, real = %anchor.real, "dropped anchor");
+            }
+            _ => kept.push(anchor),
+        }
+    }
+    Ok(kept)
+}
+
+/// Converts an instant into fractional Unix seconds.
+pub(crate) fn to_seconds(dt: DateTime<Utc>) -> f64 {
+    dt.timestamp() as f64 + f64::from(dt.timestamp_subsec_nanos()) * 1e-9
+}
+
+/// Inverse of [`to_seconds`], rounded to the nanosecond.
+fn from_seconds(seconds: f64) -> DateTime<Utc> {
+    DateTime::from_timestamp_nanos((seconds * 1e9).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn madrid() -> Coordinates {
+        Coordinates::new(40.4168, -3.7038).unwrap()
+    }
+
+    /// Sunrise and sunset of `date` in Madrid with the horizon of `options`.
+    fn events(date: NaiveDate, options: &ModelOptions) -> (DateTime<Utc>, DateTime<Utc>) {
+        let data = spa::calculate_solar_data_with_zenith(date, &madrid(), options.horizon_zenith())
+            .unwrap();
+        (data.sunrise.unwrap(), data.sunset.unwrap())
+    }
+
+    #[test]
+    fn elevation_makes_sunrise_earlier_and_sunset_later() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let at = |elevation| {
+            events(
+                date,
+                &ModelOptions {
+                    elevation,
+                    ..ModelOptions::default()
+                },
+            )
+        };
+
+        assert_eq!(
+            ModelOptions::default().horizon_zenith().to_bits(),
+            spa::STANDARD_ZENITH.to_bits()
+        );
+        assert_eq!(at(0.0), events(date, &ModelOptions::default()));
+
+        let mut previous = at(0.0);
+        for elevation in [1.0, 10.0, 350.0, 3000.0] {
+            let (sunrise, sunset) = at(elevation);
+            assert!(sunrise < previous.0, "sunrise at {elevation} m");
+            assert!(sunset > previous.1, "sunset at {elevation} m");
+            previous = (sunrise, sunset);
+        }
+
+        let earlier = at(0.0).0 - at(3000.0).0;
+        assert!(
+            earlier > Duration::minutes(8) && earlier < Duration::minutes(10),
+            "{earlier}"
+        );
+    }
+
+    #[test]
+    fn cold_dense_air_makes_sunrise_earlier() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let at = |temperature, pressure| {
+            events(
+                date,
+                &ModelOptions {
+                    temperature: Some(temperature),
+                    pressure: Some(pressure),
+                    ..ModelOptions::default()
+                },
+            )
+        };
+
+        let (cold_sunrise, cold_sunset) = at(-20.0, 1040.0);
+        let (hot_sunrise, hot_sunset) = at(30.0, 990.0);
+        assert!(cold_sunrise < hot_sunrise);
+        assert!(cold_sunset > hot_sunset);
+        // About a tenth of a degree of refraction, at 40° N in January
+        let earlier = hot_sunrise - cold_sunrise;
+        assert!(
+            earlier > Duration::seconds(20) && earlier < Duration::seconds(60),
+            "{earlier}"
+        );
+
+        // The standard atmosphere gives the default horizon
+        let standard = ModelOptions {
+            temperature: Some(10.0),
+            pressure: Some(1010.0),
+            ..ModelOptions::default()
+        };
+        assert_eq!(standard.horizon_zenith(), spa::STANDARD_ZENITH);
+    }
+
+    fn targets() -> SolarTargets {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        SolarTargets::new(time(7), time(14), time(21)).unwrap()
+    }
+
+    #[test]
+    fn coordinates_accept_their_boundaries() {
+        for (latitude, longitude) in [(90.0, 180.0), (-90.0, -180.0), (0.0, 0.0), (-0.0, -0.0)] {
+            let coords = Coordinates::new(latitude, longitude).unwrap();
+            assert_eq!(
+                (coords.latitude(), coords.longitude()),
+                (latitude, longitude)
+            );
+        }
+        for latitude in [90.000_001, -90.000_001, 123.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                Coordinates::new(latitude, 0.0),
+                Err(CoordError::InvalidLatitude(_))
+            ));
+        }
+        for longitude in [
+            180.000_001,
+            -180.000_001,
+            500.0,
+            f64::NAN,
+            f64::NEG_INFINITY,
+        ] {
+            assert!(matches!(
+                Coordinates::new(0.0, longitude),
+                Err(CoordError::InvalidLongitude(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn targets_must_be_strictly_ordered() {
+        let time = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+        let unordered = |earlier, later| Err(TargetsError::Unordered { earlier, later });
+
+        assert!(SolarTargets::new(time(0, 0), time(0, 1), time(23, 59)).is_ok());
+        assert_eq!(
+            SolarTargets::new(time(20, 0), time(14, 0), time(8, 0)),
+            unordered(time(20, 0), time(14, 0))
+        );
+        assert_eq!(
+            SolarTargets::new(time(8, 0), time(14, 0), time(14, 0)),
+            unordered(time(14, 0), time(14, 0))
+        );
+        // A schedule wrapping around midnight is rejected, not reinterpreted
+        assert_eq!(
+            SolarTargets::new(time(22, 0), time(4, 0), time(10, 0)),
+            unordered(time(22, 0), time(4, 0))
+        );
+    }
+
+    #[test]
+    fn results_do_not_depend_on_the_input_time_zone() {
+        let utc = Utc.with_ymd_and_hms(2026, 2, 3, 17, 45, 12).unwrap();
+        fn evaluate<Tz: TimeZone>(dt: DateTime<Tz>) -> SolarClockResult {
+            calculate_solar_clock(dt, &madrid(), &DEFAULT_TARGETS).unwrap()
+        }
+
+        let expected = evaluate(utc);
+        assert_eq!(expected.input, utc);
+        assert_eq!(evaluate(utc.with_timezone(&chrono::Local)), expected);
+        for seconds in [-12 * 3600, -(3 * 3600 + 1800), 5 * 3600 + 2700, 14 * 3600] {
+            let offset = FixedOffset::east_opt(seconds).unwrap();
+            assert_eq!(evaluate(utc.with_timezone(&offset)), expected, "{offset}");
+        }
+    }
+
+    #[test]
+    fn mid_latitude_models_have_nine_labelled_anchors() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let anchors = build_interpolation_model(date, &madrid(), &ModelOptions::default()).unwrap();
+
+        assert_eq!(anchors.len(), 9);
+        assert!(anchors.windows(2).all(|w| w[0].real < w[1].real));
+        let events = [
+            ("sunrise", AnchorEvent::Sunrise),
+            ("transit", AnchorEvent::Transit),
+            ("sunset", AnchorEvent::Sunset),
+        ];
+        for (i, anchor) in anchors.iter().enumerate() {
+            let (name, event) = events[i % 3];
+            assert_eq!(anchor.name, name);
+            assert_eq!(anchor.event, event);
+            assert_eq!(anchor.date, date + Duration::days(i as i64 / 3 - 1));
+            assert_eq!(anchor.real.date_naive(), anchor.date);
+        }
+        let data = spa::calculate_solar_data(date, &madrid()).unwrap();
+        assert_eq!(anchors[3].real, data.sunrise.unwrap());
+        assert_eq!(anchors[4].real, data.transit);
+        assert_eq!(anchors[5].real, data.sunset.unwrap());
+    }
+
+    #[test]
+    fn models_follow_the_configured_anchors() {
+        let time = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let two = vec![
+            AnchorSpec::new("sunrise", AnchorEvent::Sunrise, time(8, 0)),
+            AnchorSpec::new("sunset", AnchorEvent::Sunset, time(20, 0)),
+        ];
+        let afternoon = AnchorEvent::Elevation {
+            degrees: 20.0,
+            rising: false,
+        };
+        let dinner = AnchorEvent::Elevation {
+            degrees: -6.0,
+            rising: false,
+        };
+        let five = vec![
+            AnchorSpec::new("sunrise", AnchorEvent::Sunrise, time(8, 0)),
+            AnchorSpec::new("transit", AnchorEvent::Transit, time(14, 0)),
+            AnchorSpec::new("afternoon", afternoon, time(17, 30)),
+            AnchorSpec::new("sunset", AnchorEvent::Sunset, time(20, 0)),
+            AnchorSpec::new("dinner", dinner, time(21, 0)),
+        ];
+
+        for specs in [two, five] {
+            let options = ModelOptions {
+                anchors: specs.clone(),
+                ..ModelOptions::default()
+            };
+            let anchors = build_interpolation_model(date, &madrid(), &options).unwrap();
+            // Every spec on each day of the window, in the order of the specs
+            assert_eq!(anchors.len(), 3 * specs.len());
+            for (day, chunk) in (-1..=1).zip(anchors.chunks(specs.len())) {
+                for (anchor, spec) in chunk.iter().zip(&specs) {
+                    assert_eq!(anchor.name, spec.name);
+                    assert_eq!(anchor.event, spec.event);
+                    assert_eq!(anchor.date, date + Duration::days(day));
+                    assert_eq!(
+                        anchor.target,
+                        get_target_time(anchor.date, spec.target, SOLAR_TIMEZONE_OFFSET)
+                    );
+                }
+            }
+            assert!(anchors.windows(2).all(|w| w[0].real < w[1].real));
+
+            // The clock reads each target at its event
+            let clock = SolarClock::with_options(madrid(), options);
+            for anchor in anchors.iter().filter(|anchor| anchor.date == date) {
+                let result = clock.solar_time(anchor.real).unwrap();
+                assert!(
+                    (result.delta - anchor.delta()).abs() < 1e-6,
+                    "{}: {} vs {}",
+                    anchor.name,
+                    result.delta,
+                    anchor.delta()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn new_clocks_use_the_default_options() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        assert_eq!(clock.options(), &ModelOptions::default());
+    }
+
+    #[test]
+    fn next_civil_time_around_transit_and_midnight() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let time = |hour, minute, second| NaiveTime::from_hms_opt(hour, minute, second).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let transit = |date| spa::calculate_solar_data(date, &madrid()).unwrap().transit;
+        let close = |a: DateTime<Utc>, b: DateTime<Utc>| {
+            assert!((a - b).abs() < Duration::milliseconds(1), "{a} vs {b}");
+        };
+
+        // Transit is an anchor, so solar 14:00 is exactly the real transit
+        let noon = transit(date);
+        close(
+            clock
+                .next_civil_time(noon - Duration::minutes(1), time(14, 0, 0))
+                .unwrap(),
+            noon,
+        );
+        // Once it has passed, the next one is tomorrow's
+        close(
+            clock
+                .next_civil_time(noon + Duration::minutes(1), time(14, 0, 0))
+                .unwrap(),
+            transit(date + Duration::days(1)),
+        );
+
+        // Solar midnight starts the next solar date
+        let evening = get_target_time(date, time(21, 0, 0), SOLAR_TIMEZONE_OFFSET);
+        let reads = |civil: DateTime<Utc>, expected: chrono::NaiveDateTime| {
+            let solar = clock.solar_time(civil).unwrap().solar_time.naive_local();
+            assert!(
+                (solar - expected).abs() < Duration::milliseconds(1),
+                "{solar}"
+            );
+        };
+        let midnight = clock.next_civil_time(evening, time(0, 0, 0)).unwrap();
+        reads(midnight, (date + Duration::days(1)).and_time(time(0, 0, 0)));
+        let before = clock.next_civil_time(evening, time(23, 59, 59)).unwrap();
+        reads(before, date.and_time(time(23, 59, 59)));
+        assert!(before < midnight && midnight - before < Duration::seconds(2));
+
+        // Right after midnight, the next midnight is a day later
+        let next = clock
+            .next_civil_time(midnight + Duration::seconds(1), time(0, 0, 0))
+            .unwrap();
+        reads(next, (date + Duration::days(2)).and_time(time(0, 0, 0)));
+        let day = next - midnight;
+        assert!(
+            day > Duration::hours(23) && day < Duration::hours(25),
+            "{day}"
+        );
+        // Just after midnight, the early hours are still ahead today
+        let early = clock
+            .next_civil_time(midnight + Duration::seconds(1), time(0, 30, 0))
+            .unwrap();
+        reads(early, (date + Duration::days(1)).and_time(time(0, 30, 0)));
+    }
+
+    #[test]
+    fn solar_to_civil_inverts_solar_time() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let data = spa::calculate_solar_data(date, &madrid()).unwrap();
+
+        let utc = FixedOffset::east_opt(0).unwrap();
+        let noon = get_target_time(date, NaiveTime::from_hms_opt(12, 0, 0).unwrap(), utc);
+        for civil in [
+            data.sunrise.unwrap(),
+            data.transit,
+            data.sunset.unwrap(),
+            noon,
+        ] {
+            let solar = clock.solar_time(civil).unwrap().solar_time;
+            let back = clock.solar_to_civil(solar).unwrap();
+            assert!(
+                (back - civil).abs() < Duration::milliseconds(1),
+                "{civil}: {back}"
+            );
+            // Only the instant matters, not the offset it is written in
+            let back = clock.solar_to_civil(solar.with_timezone(&utc)).unwrap();
+            assert!(
+                (back - civil).abs() < Duration::milliseconds(1),
+                "{civil}: {back}"
+            );
+        }
+
+        // Anchors are exact: solar 20:00 is sunset
+        let evening = SOLAR_TIMEZONE_OFFSET
+            .from_local_datetime(&date.and_hms_opt(20, 0, 0).unwrap())
+            .unwrap();
+        let sunset = clock.solar_to_civil(evening).unwrap();
+        assert!((sunset - data.sunset.unwrap()).abs() < Duration::milliseconds(1));
+
+        // Without sunrise and sunset anchors within reach, as in polar night
+        // when the window may not widen, there is no model to invert
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let polar = SolarClock::with_options(
+            Coordinates::new(78.22, 15.65).unwrap(),
+            ModelOptions {
+                anchors: vec![
+                    AnchorSpec::new("sunrise", AnchorEvent::Sunrise, time(8)),
+                    AnchorSpec::new("sunset", AnchorEvent::Sunset, time(20)),
+                ],
+                window_days: DEFAULT_WINDOW_DAYS,
+                ..ModelOptions::default()
+            },
+        );
+        let winter = SOLAR_TIMEZONE_OFFSET
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2026, 12, 21)
+                    .unwrap()
+                    .and_time(time(12)),
+            )
+            .unwrap();
+        assert!(polar.solar_to_civil(winter).is_err());
+    }
+
+    #[test]
+    fn backwards_targets_are_rejected_or_dropped() {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        // Sunset targeted before transit: the clock would run backwards
+        let options = ModelOptions {
+            anchors: vec![
+                AnchorSpec::new("sunrise", AnchorEvent::Sunrise, time(8)),
+                AnchorSpec::new("transit", AnchorEvent::Transit, time(14)),
+                AnchorSpec::new("sunset", AnchorEvent::Sunset, time(13)),
+            ],
+            ..ModelOptions::default()
+        };
+        let input = get_target_time(date, time(12), SOLAR_TIMEZONE_OFFSET);
+
+        let strict = SolarClock::with_options(madrid(), options.clone());
+        assert_eq!(
+            strict.solar_time(input),
+            Err(SolarClockError::NonMonotonic {
+                earlier: get_target_time(date - Duration::days(1), time(14), SOLAR_TIMEZONE_OFFSET),
+                later: get_target_time(date - Duration::days(1), time(13), SOLAR_TIMEZONE_OFFSET),
+            })
+        );
+
+        let lenient = SolarClock::with_options(
+            madrid(),
+            ModelOptions {
+                strict_monotonic: false,
+                ..options
+            },
+        );
+        let result = lenient.solar_time(input).unwrap();
+        assert!(result.anchors.iter().all(|anchor| anchor.name != "sunset"));
+        assert_eq!(result.anchors.len(), 6);
+    }
+
+    #[test]
+    fn default_clock_never_runs_backwards_over_a_year() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let start = get_target_time(
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            NaiveTime::MIN,
+            SOLAR_TIMEZONE_OFFSET,
+        );
+
+        // Every 20 minutes through the year, solstices and equinoxes included
+        let mut previous = clock.solar_time(start).unwrap().solar_time;
+        for step in 1..=365 * 72 {
+            let input = start + Duration::minutes(20 * step);
+            let solar = clock.solar_time(input).unwrap().solar_time;
+            let elapsed = solar - previous;
+            // Forwards, and never more than twice or half as fast as civil
+            // time, even where long winter nights are squeezed
+            assert!(
+                elapsed > Duration::minutes(10) && elapsed < Duration::minutes(40),
+                "{input}: {elapsed}"
+            );
+            previous = solar;
+        }
+    }
+
+    #[test]
+    fn sampled_deltas_are_smooth() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let start = get_target_time(date, NaiveTime::MIN, SOLAR_TIMEZONE_OFFSET);
+
+        let day = clock
+            .sample_delta(start, start + Duration::days(1), Duration::seconds(60))
+            .unwrap();
+        assert_eq!(day.len(), 24 * 60 + 1);
+        assert_eq!(day[0].0, start);
+        assert!(
+            day.windows(2)
+                .all(|pair| pair[1].0 - pair[0].0 == Duration::seconds(60))
+        );
+        for triple in day.windows(3) {
+            let before = triple[1].1 - triple[0].1;
+            let after = triple[2].1 - triple[1].1;
+            // Under a quarter minute per minute, as the February nights are
+            // squeezed, and without kinks: the rate barely changes per minute
+            assert!(after.abs() < 15.0, "{}: {after} s", triple[2].0);
+            assert!(
+                (after - before).abs() < 0.25,
+                "{}: {before} s then {after} s",
+                triple[1].0
+            );
+        }
+
+        // The anchors of the day are hit, as by solar_time
+        for anchor in clock.model_anchors(date).unwrap() {
+            let at = clock
+                .sample_delta(anchor.real, anchor.real, Duration::seconds(60))
+                .unwrap();
+            assert!((at[0].1 - anchor.delta()).abs() < 1e-6, "{}", anchor.name);
+        }
+
+        // A week at once, beyond the window of a single date
+        let week = clock
+            .sample_delta(start, start + Duration::days(7), Duration::seconds(60))
+            .unwrap();
+        assert_eq!(week.len(), 7 * 24 * 60 + 1);
+        assert!(
+            week.windows(2)
+                .all(|pair| (pair[1].1 - pair[0].1).abs() < 15.0)
+        );
+    }
+
+    #[test]
+    fn results_carry_their_anchors() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let input = date.and_hms_opt(12, 0, 0).unwrap().and_utc();
+        let result = clock.solar_time(input).unwrap();
+
+        assert_eq!(result.anchors, clock.model_anchors(date).unwrap());
+        let transit = result
+            .anchors
+            .iter()
+            .find(|anchor| anchor.date == date && anchor.event == AnchorEvent::Transit)
+            .unwrap();
+        // Madrid's solar noon at 13:28:37 CET is pinned to 14:00
+        assert!(
+            (transit.delta() - 1883.0).abs() < 5.0,
+            "{}",
+            transit.delta()
+        );
+        assert_eq!(
+            transit.target,
+            get_target_time(date, DEFAULT_TARGETS.transit(), SOLAR_TIMEZONE_OFFSET)
+        );
+        assert_eq!(transit.point().y, transit.delta());
+    }
+
+    #[test]
+    fn anchors_a_millisecond_apart_are_merged() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let real = date.and_hms_opt(12, 0, 0).unwrap().and_utc();
+        let anchor = |name: &str, real: DateTime<Utc>, delta: i64| Anchor {
+            name: name.to_owned(),
+            event: AnchorEvent::Transit,
+            date,
+            real,
+            target: real + Duration::seconds(delta),
+        };
+        let anchors = vec![
+            anchor("a", real, 100),
+            anchor("b", real + Duration::milliseconds(1), 200),
+            anchor("c", real + Duration::milliseconds(2), 300),
+            anchor("d", real + Duration::hours(6), 400),
+        ];
+
+        let merged = merge_close(anchors.clone(), DEFAULT_MERGE_EPSILON);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].name, "a+b+c");
+        // Averaged in f64 Unix seconds, good to a microsecond
+        let middle = merged[0].real - (real + Duration::milliseconds(1));
+        assert!(middle.abs() < Duration::microseconds(1), "{middle}");
+        assert!((merged[0].delta() - 200.0).abs() < 1e-6);
+        assert_eq!(merged[1], anchors[3]);
+        let options = ModelOptions::default();
+        assert!(Model::from_anchors(date, merged, &options).is_ok());
+
+        // Unmerged, anchors on the same instant cannot be interpolated
+        let mut same = anchors;
+        same[1].real = real;
+        same[1].target = real + Duration::seconds(150);
+        assert_eq!(merge_close(same.clone(), 0.0).len(), 4);
+        assert_eq!(
+            Model::from_anchors(date, merge_close(same, 0.0), &options).map(|_| ()),
+            Err(SolarClockError::Interpolation(
+                crate::error::InterpError::NotIncreasing(1)
+            ))
+        );
+    }
+
+    #[test]
+    fn polar_circle_winter_solstice_has_a_model() {
+        // At 66.5° N the sun shows for about two hours around the transit
+        let coords = Coordinates::new(66.5, 25.7).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 12, 21).unwrap();
+        let clock = SolarClock::new(coords, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+
+        let anchors = clock.model_anchors(date).unwrap();
+        assert!(anchors.len() >= 3);
+        assert!(anchors.windows(2).all(|w| w[0].real < w[1].real));
+        let data = spa::calculate_solar_data(date, &coords).unwrap();
+        let day = data.sunset.unwrap() - data.sunrise.unwrap();
+        assert!(day < Duration::hours(3), "{day}");
+
+        let start = get_target_time(date, NaiveTime::MIN, SOLAR_TIMEZONE_OFFSET);
+        let mut previous = clock.solar_time(start).unwrap().solar_time;
+        for minute in (10..24 * 60).step_by(10) {
+            let solar = clock
+                .solar_time(start + Duration::minutes(minute))
+                .unwrap()
+                .solar_time;
+            assert!(solar > previous, "{solar}");
+            previous = solar;
+        }
+    }
+
+    #[test]
+    fn queries_on_the_same_date_reuse_the_model() {
+        let calls = || spa::SOLAR_DATA_CALLS.with(std::cell::Cell::get);
+        let clock = SolarClock::new(madrid(), targets(), SOLAR_TIMEZONE_OFFSET);
+        // 07:00 to 23:00 on 2026-02-03 in the solar offset
+        let morning = Utc.with_ymd_and_hms(2026, 2, 3, 6, 0, 0).unwrap();
+
+        let before = calls();
+        clock.solar_time(morning).unwrap();
+        let built = calls() - before;
+        assert!(built > 0);
+        for minutes in (0..16 * 60).step_by(7) {
+            clock
+                .solar_time(morning + Duration::minutes(minutes))
+                .unwrap();
+        }
+        assert_eq!(
+            calls() - before,
+            built,
+            "no solar data after the first query"
+        );
+
+        // Two days later the model is rebuilt, and agrees with a fresh one
+        let later = morning + Duration::days(2);
+        let result = clock.solar_time(later).unwrap();
+        assert_eq!(calls() - before, 2 * built);
+        let fresh = calculate_solar_clock(later, &madrid(), &targets());
+        assert_eq!(Ok(result), fresh);
+        // And going back rebuilds the first date's model
+        let before = calls();
+        let again = clock.solar_time(morning).unwrap();
+        assert_eq!(calls() - before, built);
+        assert_eq!(again.solar_time.date_naive(), morning.date_naive());
+    }
+
+    #[test]
+    fn polar_night_is_anchored_to_the_transits_alone() {
+        // No day around the solstice has a sunrise or a sunset at 80° N
+        let coords = Coordinates::new(80.0, 15.0).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 12, 21).unwrap();
+        let points = build_interpolation_model(
+            date,
+            &coords,
+            &ModelOptions {
+                anchors: targets().anchors(),
+                ..ModelOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(points.len(), 3);
+
+        // The targets want a sunrise or a sunset, and none is in sight
+        let noon = Utc.with_ymd_and_hms(2026, 12, 21, 11, 0, 0).unwrap();
+        assert_eq!(
+            calculate_solar_clock(noon, &coords, &targets()),
+            Err(SolarClockError::NoSunriseOrSunset {
+                window_days: DEFAULT_MAX_WINDOW_DAYS
+            })
+        );
+
+        // Anchored on the transits alone, both segments border the window
+        let transits = ModelOptions {
+            anchors: vec![AnchorSpec::new(
+                "transit",
+                AnchorEvent::Transit,
+                NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+            )],
+            ..ModelOptions::default()
+        };
+        let result = calculate_solar_clock_with(noon, &coords, &transits);
+        assert_eq!(result.unwrap().evaluation, Evaluation::WindowEdge);
+    }
+
+    #[test]
+    fn rate_integrates_to_the_change_in_delta() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        // All on the solar date 2026-02-03, so one model throughout
+        let start: DateTime<Utc> = "2026-02-03T00:30:00Z".parse().unwrap();
+        let end: DateTime<Utc> = "2026-02-03T22:00:00Z".parse().unwrap();
+        let step = Duration::seconds(60);
+
+        // Trapezoids of rate - 1, which is d(delta)/dt
+        let mut integral = 0.0;
+        let mut t = start;
+        let mut previous = clock.rate_at(t).unwrap();
+        while t < end {
+            t += step;
+            let rate = clock.rate_at(t).unwrap();
+            assert!((0.7..1.3).contains(&rate), "{t}: {rate}");
+            integral += (previous + rate - 2.0) / 2.0 * 60.0;
+            previous = rate;
+        }
+
+        let delta = |t| clock.solar_time(t).unwrap().delta;
+        let change = delta(end) - delta(start);
+        assert!((integral - change).abs() < 0.05, "{integral} vs {change}");
+    }
+
+    #[test]
+    fn solar_days_change_exactly_at_solar_midnight() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let noon = Utc.with_ymd_and_hms(2026, 2, 3, 12, 0, 0).unwrap();
+        let (start, end) = clock.solar_day_bounds(noon).unwrap();
+        let second = Duration::seconds(1);
+        let solar_date = |t| clock.solar_date(t).unwrap();
+
+        for (midnight, day) in [(start, date), (end, date + Duration::days(1))] {
+            let reading = clock.solar_time(midnight).unwrap().solar_time;
+            assert_eq!(reading.date_naive(), day);
+            assert!(reading.time() < NaiveTime::from_hms_milli_opt(0, 0, 0, 1).unwrap());
+
+            assert_eq!(solar_date(midnight - second), day - Duration::days(1));
+            assert_eq!(
+                solar_date(midnight - Duration::microseconds(1)),
+                day - Duration::days(1)
+            );
+            assert_eq!(solar_date(midnight), day);
+            assert_eq!(solar_date(midnight + second), day);
+        }
+
+        // Instants on either side of a midnight get the days it separates
+        assert_eq!(clock.solar_day_bounds(start).unwrap(), (start, end));
+        assert_eq!(clock.solar_day_bounds(end - second).unwrap(), (start, end));
+        assert_eq!(clock.solar_day_bounds(start - second).unwrap().1, start);
+        assert_eq!(clock.solar_day_bounds(end + second).unwrap().0, end);
+    }
+
+    #[test]
+    fn time_to_next_anchor_on_both_clocks() {
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let data = spa::calculate_solar_data(date, &madrid()).unwrap();
+
+        // Half an hour before transit, with the clock running fast
+        let now = Utc.with_ymd_and_hms(2026, 2, 3, 12, 0, 0).unwrap();
+        let (anchor, civil, solar) = clock.time_to_next_anchor(now).unwrap().unwrap();
+        assert_eq!((anchor.name.as_str(), anchor.date), ("transit", date));
+        assert_eq!(anchor.real, data.transit);
+        assert_eq!(civil, data.transit - now);
+        let delta = clock.solar_time(now).unwrap().delta;
+        let expected = civil.as_seconds_f64() + anchor.delta() - delta;
+        assert!((solar.as_seconds_f64() - expected).abs() < 1e-3);
+        // The delta grows towards transit, so the solar wait is longer
+        assert!(solar > civil + Duration::minutes(5), "{civil} vs {solar}");
+
+        // After sunset, the next anchor is the following sunrise
+        let evening = data.sunset.unwrap() + Duration::seconds(1);
+        let (anchor, civil, solar) = clock.time_to_next_anchor(evening).unwrap().unwrap();
+        assert_eq!(
+            (anchor.name.as_str(), anchor.date),
+            ("sunrise", date + Duration::days(1))
+        );
+        assert_eq!(civil, anchor.real - evening);
+        // The night is longer than the 12 solar hours it is given
+        assert!(solar < civil, "{civil} vs {solar}");
+    }
+
+    #[test]
+    fn calculate_solar_clock_defaults_to_pchip() {
+        let dt = Utc.with_ymd_and_hms(2026, 2, 3, 10, 0, 0).unwrap();
+        let with = |interpolation| {
+            let options = ModelOptions {
+                anchors: DEFAULT_TARGETS.anchors(),
+                interpolation,
+                ..ModelOptions::default()
+            };
+            calculate_solar_clock_with(dt, &madrid(), &options).unwrap()
+        };
+
+        let result = calculate_solar_clock(dt, &madrid(), &DEFAULT_TARGETS).unwrap();
+        assert_eq!(result, with(InterpolationMethod::Pchip));
+        assert_eq!(result.interpolation, InterpolationMethod::Pchip);
+        assert_eq!(
+            result,
+            SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET)
+                .solar_time(dt)
+                .unwrap()
+        );
+        // Between anchors the methods disagree
+        let linear = with(InterpolationMethod::Linear);
+        assert_eq!(linear.interpolation, InterpolationMethod::Linear);
+        assert!((linear.delta - result.delta).abs() > 1.0);
+    }
+
+    #[test]
+    fn wider_windows_agree_at_the_central_transit() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let transit = spa::calculate_solar_data(date, &madrid()).unwrap().transit;
+        let clock = |window_days| {
+            SolarClock::with_options(
+                madrid(),
+                ModelOptions {
+                    window_days,
+                    ..ModelOptions::default()
+                },
+            )
+        };
+
+        let wide = clock(3).solar_time(transit).unwrap();
+        assert_eq!(wide.anchors.len(), 21);
+        assert_eq!(wide.window_days, 3);
+        assert!(wide.anchors.windows(2).all(|w| w[0].real < w[1].real));
+        assert_eq!(
+            (wide.anchors[0].date, wide.anchors[20].date),
+            (date - Duration::days(3), date + Duration::days(3))
+        );
+
+        let narrow = clock(1).solar_time(transit).unwrap();
+        assert_eq!(narrow.anchors.len(), 9);
+        assert!((wide.delta - narrow.delta).abs() < 1e-3);
+        assert!((wide.solar_time - narrow.solar_time).abs() < Duration::milliseconds(1));
+    }
+
+    #[test]
+    fn tromso_keeps_a_result_through_the_polar_night_onset() {
+        let tromso = Coordinates::new(69.6492, 18.9553).unwrap();
+        let clock = SolarClock::new(tromso, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let fallback = SolarClock::with_options(
+            tromso,
+            ModelOptions {
+                polar_fallback: true,
+                ..ModelOptions::default()
+            },
+        );
+
+        // The sun sets for the last time around 27 November
+        let mut dt = Utc.with_ymd_and_hms(2026, 11, 20, 0, 0, 0).unwrap();
+        let mut widened = false;
+        while dt < Utc.with_ymd_and_hms(2026, 12, 4, 0, 0, 0).unwrap() {
+            let result = clock.solar_time(dt).unwrap_or_else(|e| panic!("{dt}: {e}"));
+            assert!(result.delta.abs() < 12.0 * 3600.0, "{dt}: {}", result.delta);
+            assert!(result.window_days <= DEFAULT_MAX_WINDOW_DAYS);
+            assert!(
+                result
+                    .anchors
+                    .iter()
+                    .any(|anchor| anchor.event == AnchorEvent::Sunset),
+                "{dt}"
+            );
+            widened |= result.window_days > DEFAULT_WINDOW_DAYS;
+
+            let result = fallback
+                .solar_time(dt)
+                .unwrap_or_else(|e| panic!("{dt}: {e}"));
+            assert!(result.delta.abs() < 12.0 * 3600.0, "{dt}: {}", result.delta);
+            dt += Duration::hours(6);
+        }
+        // By the end, the window reaches back to the last sunset
+        assert!(widened);
+    }
+
+    #[test]
+    fn polar_night_without_sunrise_or_sunset_has_no_anchors() {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let coords = Coordinates::new(80.0, 15.0).unwrap();
+        let winter = Utc.with_ymd_and_hms(2026, 12, 21, 12, 0, 0).unwrap();
+
+        // Even widened to the largest window, no day around the solstice
+        // has a sunrise or a sunset at 80° N
+        let clock = SolarClock::with_options(
+            coords,
+            ModelOptions {
+                anchors: vec![
+                    AnchorSpec::new("sunrise", AnchorEvent::Sunrise, time(8)),
+                    AnchorSpec::new("sunset", AnchorEvent::Sunset, time(20)),
+                ],
+                ..ModelOptions::default()
+            },
+        );
+        assert_eq!(
+            clock.solar_time(winter),
+            Err(SolarClockError::InsufficientAnchors { count: 0 })
+        );
+
+        // A single transit is not enough either
+        let date = winter.date_naive();
+        let data = spa::calculate_solar_data(date, &coords).unwrap();
+        let anchors = vec![Anchor {
+            name: "transit".to_owned(),
+            event: AnchorEvent::Transit,
+            date,
+            real: data.transit,
+            target: get_target_time(date, time(14), SOLAR_TIMEZONE_OFFSET),
+        }];
+        assert_eq!(
+            Model::from_anchors(date, anchors, &ModelOptions::default()).map(|_| ()),
+            Err(SolarClockError::InsufficientAnchors { count: 1 })
+        );
+        // The daily transits of the default targets do not stand in for them
+        assert_eq!(
+            calculate_solar_clock(winter, &coords, &DEFAULT_TARGETS),
+            Err(SolarClockError::NoSunriseOrSunset {
+                window_days: DEFAULT_MAX_WINDOW_DAYS
+            })
+        );
+    }
+
+    #[test]
+    fn queries_outside_the_model_are_errors() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let options = ModelOptions::default();
+        let anchors = build_interpolation_model(date, &madrid(), &options).unwrap();
+        let (first, last) = (anchors[0].real, anchors[anchors.len() - 1].real);
+        let model = Model::from_anchors(date, anchors, &options).unwrap();
+
+        // The range is stored as f64 seconds, so compare to the microsecond
+        let close = |a: DateTime<Utc>, b: DateTime<Utc>| (a - b).abs() < Duration::microseconds(1);
+        for far in [first - Duration::days(30), last + Duration::hours(1)] {
+            for result in [
+                model.delta(far),
+                model.rate(far),
+                model.solar_time(far).map(|_| 0.0),
+            ] {
+                match result {
+                    Err(SolarClockError::OutOfRange { earliest, latest }) => {
+                        assert!(close(earliest, first) && close(latest, last), "{far}");
+                    }
+                    other => panic!("{far}: expected OutOfRange, got {other:?}"),
+                }
+            }
+        }
+        // Just inside the ends is covered
+        let inside = Duration::milliseconds(1);
+        assert!(model.delta(first + inside).unwrap().abs() > 0.0);
+        assert!(model.delta(last - inside).is_ok());
+    }
+
+    #[test]
+    fn mid_latitude_models_are_not_widened() {
+        let clock = SolarClock::with_options(madrid(), ModelOptions::default());
+        let unwidened = SolarClock::with_options(
+            madrid(),
+            ModelOptions {
+                max_window_days: DEFAULT_WINDOW_DAYS,
+                ..ModelOptions::default()
+            },
+        );
+
+        for (month, day) in [(3, 20), (6, 21), (9, 23), (12, 21)] {
+            let date = NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+            for time in [(0, 30), (23, 30)] {
+                let time = NaiveTime::from_hms_opt(time.0, time.1, 0).unwrap();
+                let input = get_target_time(date, time, SOLAR_TIMEZONE_OFFSET);
+
+                let result = clock.solar_time(input).unwrap();
+                assert_eq!(result.window_days, DEFAULT_WINDOW_DAYS, "{input}");
+                assert_eq!(result.mode, AnchorMode::Events);
+                assert!(result.delta.abs() < 7200.0, "{input}: {}", result.delta);
+
+                let bounded = unwidened.solar_time(input).unwrap();
+                assert_eq!(bounded.delta.to_bits(), result.delta.to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn a_cubic_that_runs_backwards_falls_back_to_linear() {
+        // Flat, then a drop of 590 s over ten minutes, then flat again: the
+        // targets still increase, but a cubic with flat ends at the drop
+        // falls up to 1.5 times as fast as the secant, faster than time runs
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let start = Utc.with_ymd_and_hms(2026, 2, 3, 6, 0, 0).unwrap();
+        let anchors: Vec<Anchor> = [(0, 0), (600, 0), (1200, -590), (43_200, -590)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (real, delta))| Anchor {
+                name: format!("anchor{i}"),
+                event: AnchorEvent::Transit,
+                date,
+                real: start + Duration::seconds(real),
+                target: start + Duration::seconds(real + delta),
+            })
+            .collect();
+        let inside = start + Duration::seconds(900);
+
+        for method in [InterpolationMethod::Pchip, InterpolationMethod::Akima] {
+            let options = ModelOptions {
+                interpolation: method,
+                ..ModelOptions::default()
+            };
+            let model = Model::from_anchors(date, anchors.clone(), &options).unwrap();
+            let result = model.solar_time(inside).unwrap();
+            assert!(result.linear_fallback, "{method:?}");
+            assert_eq!(result.interpolation, InterpolationMethod::Linear);
+            assert!(model.rate(inside).unwrap() > 0.0);
+        }
+
+        // Linear by choice is no fallback, and real anchors keep the cubic
+        let options = ModelOptions {
+            interpolation: InterpolationMethod::Linear,
+            ..ModelOptions::default()
+        };
+        let model = Model::from_anchors(date, anchors, &options).unwrap();
+        assert!(!model.solar_time(inside).unwrap().linear_fallback);
+        let clock = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let result = clock.solar_time(start + Duration::hours(4)).unwrap();
+        assert!(!result.linear_fallback);
+        assert_eq!(result.interpolation, InterpolationMethod::Pchip);
+    }
+
+    #[test]
+    fn midnight_is_anchored_only_on_request() {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let targets = SolarTargets::new(time(8), time(14), time(20)).unwrap();
+        assert_eq!(targets, DEFAULT_TARGETS);
+        assert_eq!(DEFAULT_TARGETS.midnight(), None);
+        assert_eq!(ModelOptions::default().anchors, targets.anchors());
+        assert!(
+            crate::SolarClockBuilder::default()
+                .build()
+                .unwrap()
+                .options()
+                .anchors
+                .iter()
+                .all(|anchor| anchor.event != AnchorEvent::Midnight)
+        );
+    }
+
+    #[test]
+    fn midnight_anchor_smooths_the_night() {
+        let with_midnight = SolarClock::new(
+            madrid(),
+            DEFAULT_TARGETS
+                .with_midnight(Some(DEFAULT_MIDNIGHT))
+                .unwrap(),
+            SOLAR_TIMEZONE_OFFSET,
+        );
+        let without = SolarClock::new(madrid(), DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+
+        // The 15-hour midwinter night takes the 12 solar hours from 20:00 to
+        // 08:00, so the delta falls by up to a quarter of each step, and the
+        // fall changes gradually through the midnight anchor
+        let start = "2026-12-21T17:00:00Z".parse().unwrap();
+        let end = "2026-12-22T07:00:00Z".parse().unwrap();
+        let samples = with_midnight
+            .sample_delta(start, end, Duration::minutes(10))
+            .unwrap();
+        let steps: Vec<f64> = samples.windows(2).map(|w| w[1].1 - w[0].1).collect();
+        assert!(steps.iter().all(|step| step.abs() < 150.0), "{steps:?}");
+        assert!(
+            steps.windows(2).all(|w| (w[1] - w[0]).abs() < 10.0),
+            "{steps:?}"
+        );
+
+        // The daytime anchors are kept exactly
+        let date = NaiveDate::from_ymd_opt(2026, 12, 21).unwrap();
+        let daytime = |clock: &SolarClock| -> Vec<(AnchorEvent, f64)> {
+            clock
+                .model_anchors(date)
+                .unwrap()
+                .into_iter()
+                .filter(|anchor| anchor.event != AnchorEvent::Midnight)
+                .map(|anchor| (anchor.event, anchor.delta()))
+                .collect()
+        };
+        assert_eq!(daytime(&with_midnight), daytime(&without));
+    }
+
+    #[test]
+    fn no_jumps_between_days_through_the_svalbard_spring() {
+        // Polar night ends mid-February and midnight sun starts mid-April
+        let longyearbyen = Coordinates::new(78.2232, 15.6267).unwrap();
+        let options = ModelOptions {
+            polar_fallback: true,
+            ..ModelOptions::default()
+        };
+        for clock in [
+            SolarClock::new(longyearbyen, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET),
+            SolarClock::with_options(longyearbyen, options),
+        ] {
+            let mut noon = Utc.with_ymd_and_hms(2026, 2, 1, 12, 0, 0).unwrap();
+            while noon < Utc.with_ymd_and_hms(2026, 5, 1, 12, 0, 0).unwrap() {
+                // Both sides of the midnight ending the day, each from the
+                // model of its own day
+                let (_, end) = clock.solar_day_bounds(noon).unwrap();
+                let before = clock.solar_time(end - Duration::milliseconds(1)).unwrap();
+                let after = clock.solar_time(end).unwrap();
+                let jump = after.delta - before.delta;
+                assert!(jump.abs() < 120.0, "{end}: {jump} s");
+                noon += Duration::days(1);
+            }
+        }
+    }
+
+    #[test]
+    fn polar_anchors_compute_each_day_once() {
+        let longyearbyen = Coordinates::new(78.2232, 15.6267).unwrap();
+        let date = NaiveDate::from_ymd_opt(2026, 2, 20).unwrap();
+        let calls = || spa::SOLAR_DATA_CALLS.with(std::cell::Cell::get);
+        let window = Duration::days(1);
+
+        let before = calls();
+        let anchors = polar_anchors(
+            date - window,
+            date + window,
+            &longyearbyen,
+            &ModelOptions::default(),
+        )
+        .unwrap();
+        assert!(anchors.is_some());
+        // The three days of the window and the blend on each side
+        assert_eq!(calls() - before, 3 + 2 * POLAR_BLEND_DAYS as usize);
+    }
+}
@@ -0,0 +1,2106 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Thin layer over the `spa` crate that extracts the daily events the solar
+//! clock is anchored to.
+//!
+//! Despite its name, the `spa` crate implements the PSA algorithm of Blanco
+//! et al., not the NREL SPA: its positions are good to about half an
+//! arcminute around its 1999–2015 fit period, and its `sunrise_and_set` is
+//! a low-precision method a few minutes off at high latitudes. The
+//! `accurate` feature computes every position with the full NREL SPA of
+//! the `nrel` module instead, which alone takes ΔT into account: see
+//! [`calculate_solar_data_with_delta_t`].
+
+use ::spa::StdFloatOps;
+use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
+#[cfg(feature = "tracing")]
+use tracing::trace;
+
+use crate::solar_clock::Coordinates;
+
+pub use ::spa::SpaError;
+
+#[cfg(test)]
+thread_local! {
+    /// Days of solar data computed on this thread, for the tests of the
+    /// model caches.
+    pub(crate) static SOLAR_DATA_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Astronomical events of a single UTC day.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarData {
+    /// Sunrise instant, `None` during polar day or polar night.
+    pub sunrise: Option<DateTime<Utc>>,
+    /// Solar transit (true solar noon), the instant the sun crosses the
+    /// meridian.
+    pub transit: DateTime<Utc>,
+    /// Apparent elevation of the sun at transit in degrees, the highest of
+    /// the day. [`solar_position`] without refraction gives the true one.
+    pub transit_elevation: f64,
+    /// Sunset instant, `None` during polar day or polar night.
+    pub sunset: Option<DateTime<Utc>>,
+    /// Azimuth of the sun at sunrise in degrees clockwise from north,
+    /// `None` exactly when `sunrise` is.
+    pub sunrise_azimuth: Option<f64>,
+    /// Azimuth of the sun at sunset, `None` exactly when `sunset` is.
+    pub sunset_azimuth: Option<f64>,
+    /// Solar midnight preceding the transit: the lower culmination, the
+    /// instant the sun crosses the meridian below the pole, about half a day
+    /// before.
+    pub midnight: DateTime<Utc>,
+}
+
+/// Dawn and dusk of the civil (−6°), nautical (−12°) and astronomical (−18°)
+/// twilights of a single UTC day: the instants the sun's centre crosses that
+/// elevation before and after the transit. Each is `None` when the sun does
+/// not cross it on that side of the transit, e.g. astronomical dusk during
+/// the white nights of high latitudes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TwilightData {
+    pub civil_dawn: Option<DateTime<Utc>>,
+    pub civil_dusk: Option<DateTime<Utc>>,
+    pub nautical_dawn: Option<DateTime<Utc>>,
+    pub nautical_dusk: Option<DateTime<Utc>>,
+    pub astronomical_dawn: Option<DateTime<Utc>>,
+    pub astronomical_dusk: Option<DateTime<Utc>>,
+}
+
+/// Zenith angle in degrees of the sun's centre at standard sunrise and
+/// sunset: the horizon plus 50′ for refraction and the solar radius.
+pub const STANDARD_ZENITH: f64 = 90.833;
+
+/// How far the instants of sunrise, sunset and the twilights are refined.
+///
+/// Both start from the transit and move to the hour angle at which the sun
+/// reaches the zenith of the event, for the declination it has at the
+/// current estimate. Where that fails, e.g. on the last days before polar
+/// day, the instant is found by bisection of the elevation instead.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Refinement {
+    /// A single step, with the declination at the transit: up to about half
+    /// a minute off near the equinoxes, when the declination changes
+    /// fastest.
+    SinglePass,
+    /// Steps repeated from each new estimate until they move it less than a
+    /// millisecond, usually after two or three.
+    #[default]
+    Iterative,
+}
+
+/// Computes sunrise, transit and sunset for `date` at the given coordinates,
+/// with sunrise and sunset at [`STANDARD_ZENITH`].
+pub fn calculate_solar_data(date: NaiveDate, coords: &Coordinates) -> Result<SolarData, SpaError> {
+    calculate_solar_data_with_zenith(date, coords, STANDARD_ZENITH)
+}
+
+/// Equation of time on `date` in minutes: apparent minus mean solar time,
+/// positive when a sundial is ahead of the clock (about +16 min in early
+/// November, −14 min in mid-February).
+///
+/// Taken from the instant the sun crosses the Greenwich meridian, which is
+/// noon UTC minus the equation of time. Fails where the solar position
+/// algorithm does, for dates outside its range.
+pub fn equation_of_time(date: NaiveDate) -> Result<f64, SpaError> {
+    let noon = date.and_time(chrono::NaiveTime::MIN).and_utc() + Duration::hours(12);
+    let greenwich = Coordinates {
+        latitude: 45.0,
+        longitude: 0.0,
+    };
+    // The sun crosses the meridian within 3 hours of noon every day
+    let passage = find_meridian_passage(noon, &greenwich, true, None).ok_or(SpaError::BadParam)?;
+    Ok((noon - passage).num_microseconds().unwrap_or_default() as f64 / 60e6)
+}
+
+/// Declination of the sun at `dt` in degrees, positive north: about +23.44
+/// at the June solstice and 0 at the equinoxes.
+///
+/// Derived from the true position seen from the point of the equator where
+/// the sun is on the meridian at `dt`, where sin δ = cos h × cos A. The sun
+/// is then at least 66° high, so refraction and parallax barely affect it.
+pub fn declination(dt: DateTime<Utc>) -> Result<f64, SpaError> {
+    let equator = Coordinates {
+        latitude: 0.0,
+        longitude: -hour_angle(dt, 0.0, equation_of_time(dt.date_naive())?),
+    };
+    let pos = solar_position(dt, &equator, false)?;
+    Ok(
+        (pos.elevation.to_radians().cos() * pos.azimuth.to_radians().cos())
+            .asin()
+            .to_degrees(),
+    )
+}
+
+/// Instants of the equinoxes and solstices of a year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeasonMarkers {
+    pub march_equinox: DateTime<Utc>,
+    pub june_solstice: DateTime<Utc>,
+    pub september_equinox: DateTime<Utc>,
+    pub december_solstice: DateTime<Utc>,
+}
+
+/// Equinoxes and solstices of `year`, from the series of Meeus,
+/// *Astronomical Algorithms*, chapter 27: within about a minute of the
+/// instants the sun's apparent longitude reaches 0°, 90°, 180° and 270°,
+/// converted to UTC with the ΔT of [`estimate_delta_t`]. `None` outside the
+/// years −1000 to 3000 that the series cover.
+///
+/// The declination is not used: the sun's ecliptic latitude of up to about
+/// 1″ moves its extremes by several minutes from the solstices.
+pub fn solstices_equinoxes(year: i32) -> Option<SeasonMarkers> {
+    if !(-1000..=3000).contains(&year) {
+        return None;
+    }
+    // Mean instants as Julian ephemeris days, tables 27.A and 27.B
+    let (y, mean) = if year < 1000 {
+        (f64::from(year) / 1000.0, &SEASONS_BEFORE_1000)
+    } else {
+        (f64::from(year - 2000) / 1000.0, &SEASONS_FROM_1000)
+    };
+    let instant = |coefficients: &[f64; 5]| {
+        let jde0 = coefficients.iter().rev().fold(0.0, |sum, c| sum * y + c);
+        let t = (jde0 - 2_451_545.0) / 36_525.0;
+        let w = (35_999.373 * t - 2.47).to_radians();
+        let dlambda = 1.0 + 0.0334 * w.cos() + 0.0007 * (2.0 * w).cos();
+        let periodic: f64 = SEASON_TERMS
+            .iter()
+            .map(|(a, b, c)| a * (b + c * t).to_radians().cos())
+            .sum();
+        let jde = jde0 + 0.00001 * periodic / dlambda;
+
+        // Julian day 2440587.5 is the Unix epoch
+        let tt = (jde - 2_440_587.5) * 86_400.0;
+        let year = 2000.0 + (jde - 2_451_545.0) / 365.25;
+        let seconds = tt - estimate_delta_t(year);
+        DateTime::from_timestamp(seconds.floor() as i64, (seconds.fract() * 1e9) as u32)
+    };
+
+    Some(SeasonMarkers {
+        march_equinox: instant(&mean[0])?,
+        june_solstice: instant(&mean[1])?,
+        september_equinox: instant(&mean[2])?,
+        december_solstice: instant(&mean[3])?,
+    })
+}
+
+/// Mean March equinox, June solstice, September equinox and December
+/// solstice of the years −1000 to 1000, as polynomials in thousands of
+/// years since year 0 (Meeus, table 27.A).
+const SEASONS_BEFORE_1000: [[f64; 5]; 4] = [
+    [1721139.29189, 365242.13740, 0.06134, 0.00111, -0.00071],
+    [1721233.25401, 365241.72562, -0.05323, 0.00907, 0.00025],
+    [1721325.70455, 365242.49558, -0.11677, -0.00297, 0.00074],
+    [1721414.39987, 365242.88257, -0.00769, -0.00933, -0.00006],
+];
+
+/// As [`SEASONS_BEFORE_1000`] for the years 1000 to 3000, in thousands of
+/// years since 2000 (Meeus, table 27.B).
+const SEASONS_FROM_1000: [[f64; 5]; 4] = [
+    [2451623.80984, 365242.37404, 0.05169, -0.00411, -0.00057],
+    [2451716.56767, 365241.62603, 0.00325, 0.00888, -0.00030],
+    [2451810.21715, 365242.01767, -0.11575, 0.00337, 0.00078],
+    [2451900.05952, 365242.74049, -0.06223, -0.00823, 0.00032],
+];
+
+/// Periodic terms `(A, B, C)` of the seasons, A cos(B + C T) with T in
+/// Julian centuries (Meeus, table 27.C).
+const SEASON_TERMS: [(f64, f64, f64); 24] = [
+    (485.0, 324.96, 1934.136),
+    (203.0, 337.23, 32964.467),
+    (199.0, 342.08, 20.186),
+    (182.0, 27.85, 445267.112),
+    (156.0, 73.14, 45036.886),
+    (136.0, 171.52, 22518.443),
+    (77.0, 222.54, 65928.934),
+    (74.0, 296.72, 3034.906),
+    (70.0, 243.58, 9037.513),
+    (58.0, 119.81, 33718.147),
+    (52.0, 297.17, 150.678),
+    (50.0, 21.02, 2281.226),
+    (45.0, 247.54, 29929.562),
+    (44.0, 325.15, 31555.956),
+    (29.0, 60.93, 4443.417),
+    (18.0, 155.12, 67555.328),
+    (17.0, 288.79, 4562.452),
+    (16.0, 198.04, 62894.029),
+    (14.0, 199.76, 31436.921),
+    (12.0, 95.39, 14577.848),
+    (12.0, 287.11, 31931.756),
+    (12.0, 320.81, 34777.259),
+    (9.0, 227.73, 1222.114),
+    (8.0, 15.45, 16859.074),
+];
+
+/// ΔT, terrestrial time minus UT, in seconds in the decimal `year`, by the
+/// polynomial fits of Espenak and Meeus to the historical record and its
+/// extrapolation: about 63 s in 2000 and 69 s in the 2020s as observed,
+/// and growing with the square of the centuries away from 1820.
+pub fn estimate_delta_t(year: f64) -> f64 {
+    let poly =
+        |t: f64, coefficients: &[f64]| coefficients.iter().rev().fold(0.0, |sum, c| sum * t + c);
+    let parabola = |year: f64| -20.0 + 32.0 * ((year - 1820.0) / 100.0).powi(2);
+    match year {
+        y if y < -500.0 => parabola(y),
+        y if y < 500.0 => poly(
+            y / 100.0,
+            &[
+                10583.6,
+                -1014.41,
+                33.78311,
+                -5.952053,
+                -0.1798452,
+                0.022174192,
+                0.0090316521,
+            ],
+        ),
+        y if y < 1600.0 => poly(
+            (y - 1000.0) / 100.0,
+            &[
+                1574.2,
+                -556.01,
+                71.23472,
+                0.319781,
+                -0.8503463,
+                -0.005050998,
+                0.0083572073,
+            ],
+        ),
+        y if y < 1700.0 => poly(y - 1600.0, &[120.0, -0.9808, -0.01532, 1.0 / 7129.0]),
+        y if y < 1800.0 => poly(
+            y - 1700.0,
+            &[8.83, 0.1603, -0.0059285, 0.00013336, -1.0 / 1_174_000.0],
+        ),
+        y if y < 1860.0 => poly(
+            y - 1800.0,
+            &[
+                13.72,
+                -0.332447,
+                0.0068612,
+                0.0041116,
+                -0.00037436,
+                0.0000121272,
+                -0.0000001699,
+                0.000000000875,
+            ],
+        ),
+        y if y < 1900.0 => poly(
+            y - 1860.0,
+            &[
+                7.62,
+                0.5737,
+                -0.251754,
+                0.01680668,
+                -0.0004473624,
+                1.0 / 233_174.0,
+            ],
+        ),
+        y if y < 1920.0 => poly(
+            y - 1900.0,
+            &[-2.79, 1.494119, -0.0598939, 0.0061966, -0.000197],
+        ),
+        y if y < 1941.0 => poly(y - 1920.0, &[21.20, 0.84493, -0.076100, 0.0020936]),
+        y if y < 1961.0 => poly(y - 1950.0, &[29.07, 0.407, -1.0 / 233.0, 1.0 / 2547.0]),
+        y if y < 1986.0 => poly(y - 1975.0, &[45.45, 1.067, -1.0 / 260.0, -1.0 / 718.0]),
+        y if y < 2005.0 => poly(
+            y - 2000.0,
+            &[
+                63.86,
+                0.3345,
+                -0.060374,
+                0.0017275,
+                0.000651814,
+                0.00002373599,
+            ],
+        ),
+        y if y < 2050.0 => poly(y - 2000.0, &[62.92, 0.32217, 0.005589]),
+        y if y < 2150.0 => parabola(y) - 0.5628 * (2150.0 - y),
+        y => parabola(y),
+    }
+}
+
+/// Largest angle in degrees between the latitude and the declination at a
+/// transit for [`subsolar_transits`] to count it as overhead: twice the
+/// daily change of the declination is below it, so a crossing is not missed.
+const SUBSOLAR_THRESHOLD: f64 = 0.25;
+
+/// Days of `year` on which the sun passes directly overhead at transit
+/// ("Lahaina noon"), with their transit instants.
+///
+/// These are the days on which |latitude − declination| at transit is
+/// smallest and below 0.25°: usually two between the tropics, one or two
+/// near them and none outside them or for a year chrono cannot represent.
+/// Close to a tropic both passages can fall around the same solstice, and
+/// a third can appear when that solstice is in late December.
+pub fn subsolar_transits(
+    year: i32,
+    coords: &Coordinates,
+) -> Result<Vec<(NaiveDate, DateTime<Utc>)>, SpaError> {
+    let (Some(first), Some(last)) = (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) else {
+        return Ok(Vec::new());
+    };
+    // Beyond the tropics the sun is never overhead
+    if coords.latitude.abs() > 24.0 {
+        return Ok(Vec::new());
+    }
+
+    // One more day on each side so that the ends of the year can be minima
+    let mut days = Vec::new();
+    for date in (first - Duration::days(1))
+        .iter_days()
+        .take_while(|date| *date <= last + Duration::days(1))
+    {
+        let transit = calculate_solar_data(date, coords)?.transit;
+        let distance = (coords.latitude - declination(transit)?).abs();
+        days.push((date, transit, distance));
+    }
+
+    Ok(days
+        .windows(3)
+        .filter(|window| {
+            let distance = window[1].2;
+            distance < SUBSOLAR_THRESHOLD && window[0].2 > distance && window[2].2 >= distance
+        })
+        .map(|window| (window[1].0, window[1].1))
+        .collect())
+}
+
+/// Local hour angle of the sun at `dt` in degrees, at `longitude` degrees
+/// east with the equation of time `eot` in minutes (see
+/// [`equation_of_time`]): zero at the transit, negative before it and
+/// positive after it, 15° per hour.
+pub fn hour_angle(dt: DateTime<Utc>, longitude: f64, eot: f64) -> f64 {
+    let seconds =
+        f64::from(dt.num_seconds_from_midnight()) + f64::from(dt.timestamp_subsec_nanos()) * 1e-9;
+    let hour_angle = (seconds / 3600.0 - 12.0) * 15.0 + longitude + eot / 4.0;
+    (hour_angle + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Refraction at the horizon included in [`STANDARD_ZENITH`], in degrees
+/// (34′), for 10 °C and 1010 hPa.
+pub const HORIZON_REFRACTION: f64 = 34.0 / 60.0;
+
+/// Change of the refraction at the horizon, in degrees, at `temperature` °C
+/// and `pressure` hPa with respect to [`HORIZON_REFRACTION`]. Refraction
+/// scales with the air density, P/1010 × 283/(273 + T).
+pub fn refraction_correction(temperature: f64, pressure: f64) -> f64 {
+    HORIZON_REFRACTION * (pressure / 1010.0 * 283.0 / (273.0 + temperature) - 1.0)
+}
+
+/// Morning and evening golden hour (sun between −4° and +6°) and blue hour
+/// (between −6° and −4°) of a single UTC day, as `(start, end)` intervals.
+///
+/// Each interval is `None` when the sun does not enter the band on that
+/// side of the transit. Where the sun does not leave the band it extends to
+/// the transit, or to twelve hours before (morning) or after (evening) it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhotoHours {
+    pub morning_blue: Option<Interval>,
+    pub morning_golden: Option<Interval>,
+    pub evening_golden: Option<Interval>,
+    pub evening_blue: Option<Interval>,
+}
+
+/// `(start, end)` instants of a period of the day.
+pub type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+/// Computes the golden and blue hours of `date` at the given coordinates.
+pub fn calculate_photo_hours(
+    date: NaiveDate,
+    coords: &Coordinates,
+) -> Result<PhotoHours, SpaError> {
+    let transit = calculate_solar_data(date, coords)?.transit;
+    let band = |lower: f64, upper: f64, rising: bool| {
+        elevation_band(transit, coords, lower, upper, rising)
+    };
+
+    Ok(PhotoHours {
+        morning_blue: band(-6.0, -4.0, true)?,
+        morning_golden: band(-4.0, 6.0, true)?,
+        evening_golden: band(-4.0, 6.0, false)?,
+        evening_blue: band(-6.0, -4.0, false)?,
+    })
+}
+
+/// Interval in which the sun is between `lower` and `upper` degrees, on the
+/// rising or setting side of `transit`. The elevation is monotone over the
+/// twelve hours on each side, so the interval is contiguous.
+fn elevation_band(
+    transit: DateTime<Utc>,
+    coords: &Coordinates,
+    lower: f64,
+    upper: f64,
+    rising: bool,
+) -> Result<Option<Interval>, SpaError> {
+    let far = if rising {
+        transit - Duration::hours(12)
+    } else {
+        transit + Duration::hours(12)
+    };
+    let far_elevation = solar_elevation(far, coords)?;
+    let transit_elevation = solar_elevation(transit, coords)?;
+    if transit_elevation < lower || far_elevation > upper {
+        return Ok(None);
+    }
+
+    let lower_edge = if far_elevation >= lower {
+        far
+    } else {
+        elevation_crossing(transit, coords, lower, rising)?.unwrap_or(far)
+    };
+    let upper_edge = if transit_elevation <= upper {
+        transit
+    } else {
+        elevation_crossing(transit, coords, upper, rising)?.unwrap_or(transit)
+    };
+    Ok(Some(if rising {
+        (lower_edge, upper_edge)
+    } else {
+        (upper_edge, lower_edge)
+    }))
+}
+
+/// Computes the twilights of `date` at the given coordinates.
+pub fn calculate_twilight_data(
+    date: NaiveDate,
+    coords: &Coordinates,
+) -> Result<TwilightData, SpaError> {
+    // Like sunrise and sunset, so that the events of a day keep their order
+    let transit = calculate_solar_data(date, coords)?.transit;
+    let twilight = |depression: f64| -> Result<Crossings, SpaError> {
+        Ok((
+            zenith_crossing(
+                transit,
+                coords,
+                90.0 + depression,
+                true,
+                Refinement::Iterative,
+                None,
+            )?,
+            zenith_crossing(
+                transit,
+                coords,
+                90.0 + depression,
+                false,
+                Refinement::Iterative,
+                None,
+            )?,
+        ))
+    };
+    let (civil_dawn, civil_dusk) = twilight(6.0)?;
+    let (nautical_dawn, nautical_dusk) = twilight(12.0)?;
+    let (astronomical_dawn, astronomical_dusk) = twilight(18.0)?;
+
+    Ok(TwilightData {
+        civil_dawn,
+        civil_dusk,
+        nautical_dawn,
+        nautical_dusk,
+        astronomical_dawn,
+        astronomical_dusk,
+    })
+}
+
+/// Rising and setting instants of an [`elevation_crossings`] pair.
+pub type Crossings = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// Instants the sun crosses `elevation` degrees on `date`, going up before
+/// the transit and down after it. Either is `None` when the sun does not
+/// cross that elevation on its side of the transit, e.g. both for an
+/// elevation above the day's maximum.
+pub fn elevation_crossings(
+    date: NaiveDate,
+    coords: &Coordinates,
+    elevation: f64,
+) -> Result<Crossings, SpaError> {
+    let transit = calculate_solar_data(date, coords)?.transit;
+    crossings_around(transit, coords, elevation)
+}
+
+/// Rising and setting crossings of `elevation` on either side of `transit`.
+fn crossings_around(
+    transit: DateTime<Utc>,
+    coords: &Coordinates,
+    elevation: f64,
+) -> Result<Crossings, SpaError> {
+    Ok((
+        elevation_crossing(transit, coords, elevation, true)?,
+        elevation_crossing(transit, coords, elevation, false)?,
+    ))
+}
+
+/// Dip of the sea horizon below the astronomical horizon, in degrees, for an
+/// observer `elevation` metres above it: 1.76′ × √h.
+pub fn horizon_dip(elevation: f64) -> f64 {
+    1.76 / 60.0 * elevation.sqrt()
+}
+
+/// Like [`calculate_solar_data`], with sunrise and sunset at the instants the
+/// sun's centre crosses `zenith` degrees, as returned by [`solar_elevation`]
+/// (e.g. 90 for the centre on the horizon, 108 for astronomical twilight).
+/// They are `None` on days the sun does not cross that zenith.
+pub fn calculate_solar_data_with_zenith(
+    date: NaiveDate,
+    coords: &Coordinates,
+    zenith: f64,
+) -> Result<SolarData, SpaError> {
+    calculate_solar_data_with_refinement(date, coords, zenith, Refinement::Iterative)
+}
+
+/// Like [`calculate_solar_data_with_zenith`], with sunrise and sunset
+/// refined as set by `refinement`.
+pub fn calculate_solar_data_with_refinement(
+    date: NaiveDate,
+    coords: &Coordinates,
+    zenith: f64,
+    refinement: Refinement,
+) -> Result<SolarData, SpaError> {
+    calculate_solar_data_with_delta_t(date, coords, zenith, refinement, None)
+}
+
+/// Like [`calculate_solar_data_with_refinement`], with terrestrial time
+/// taken `delta_t` seconds ahead of UT, or by the Espenak and Meeus estimate
+/// for the month when `None`, e.g. to follow the IERS bulletins.
+///
+/// Only the NREL SPA of the `accurate` feature takes ΔT; the `spa` crate's
+/// PSA is a fit in UT and ignores it. The transit moves by about
+/// ΔT × 0.0027 s, the share of the sun's daily motion in right ascension.
+pub fn calculate_solar_data_with_delta_t(
+    date: NaiveDate,
+    coords: &Coordinates,
+    zenith: f64,
+    refinement: Refinement,
+    delta_t: Option<f64>,
+) -> Result<SolarData, SpaError> {
+    trace!(%date, "computing the solar data");
+    #[cfg(test)]
+    SOLAR_DATA_CALLS.with(|calls| calls.set(calls.get() + 1));
+    // Apparent solar noon at this longitude is within a few seconds of the
+    // transit
+    let noon = date.and_time(chrono::NaiveTime::MIN).and_utc() + Duration::hours(12);
+    let hour_angle = hour_angle(noon, coords.longitude, equation_of_time(date)?);
+    let approx_noon = noon - Duration::milliseconds((hour_angle * 240_000.0) as i64);
+
+    let transit = meridian_passage_near(approx_noon, coords, true, delta_t);
+    let midnight = meridian_passage_near(transit - Duration::hours(12), coords, false, delta_t);
+    let sunrise = zenith_crossing(transit, coords, zenith, true, refinement, delta_t)?;
+    let sunset = zenith_crossing(transit, coords, zenith, false, refinement, delta_t)?;
+
+    Ok(SolarData {
+        sunrise,
+        transit,
+        transit_elevation: solar_position(transit, coords, true)?.elevation,
+        sunset,
+        sunrise_azimuth: azimuth_at(sunrise, coords)?,
+        sunset_azimuth: azimuth_at(sunset, coords)?,
+        midnight,
+    })
+}
+
+/// Daylight of a single UTC day, from sunrise to sunset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayLength {
+    /// The sun rises and sets, this long apart.
+    Daylight(Duration),
+    /// The sun stays above the horizon all day.
+    PolarDay,
+    /// The sun stays below the horizon all day.
+    PolarNight,
+}
+
+impl DayLength {
+    /// Day length of `data`, computed with sunrise and sunset at `zenith`
+    /// degrees. Without both events the day is polar day when the sun is
+    /// above that zenith at transit.
+    pub fn from_data(data: &SolarData, zenith: f64) -> Self {
+        match (data.sunrise, data.sunset) {
+            (Some(sunrise), Some(sunset)) => Self::Daylight(sunset - sunrise),
+            _ if data.transit_elevation > 90.0 - zenith => Self::PolarDay,
+            _ => Self::PolarNight,
+        }
+    }
+
+    /// Time the sun is up: a full day during polar day, zero during polar
+    /// night.
+    pub fn duration(self) -> Duration {
+        match self {
+            Self::Daylight(duration) => duration,
+            Self::PolarDay => Duration::days(1),
+            Self::PolarNight => Duration::zero(),
+        }
+    }
+
+    /// Change from `previous` to this day length, positive when the days are
+    /// getting longer. `None` unless the sun rises and sets on both days: the
+    /// step into or out of polar day or night is not a daylight change.
+    pub fn change_since(self, previous: DayLength) -> Option<Duration> {
+        match (previous, self) {
+            (Self::Daylight(before), Self::Daylight(after)) => Some(after - before),
+            _ => None,
+        }
+    }
+}
+
+/// Day length on `date` at the given coordinates.
+pub fn day_length(date: NaiveDate, coords: &Coordinates) -> Result<DayLength, SpaError> {
+    day_length_with_zenith(date, coords, STANDARD_ZENITH)
+}
+
+/// Day length with sunrise and sunset at `zenith` degrees, like
+/// [`calculate_solar_data_with_zenith`].
+pub fn day_length_with_zenith(
+    date: NaiveDate,
+    coords: &Coordinates,
+    zenith: f64,
+) -> Result<DayLength, SpaError> {
+    let data = calculate_solar_data_with_zenith(date, coords, zenith)?;
+    Ok(DayLength::from_data(&data, zenith))
+}
+
+/// How much longer `date` is than the day before, negative when the days are
+/// getting shorter. `None` when either day has no sunrise or sunset, see
+/// [`DayLength::change_since`].
+pub fn day_length_delta(
+    date: NaiveDate,
+    coords: &Coordinates,
+) -> Result<Option<Duration>, SpaError> {
+    day_length_delta_with_zenith(date, coords, STANDARD_ZENITH)
+}
+
+/// [`day_length_delta`] with sunrise and sunset at `zenith` degrees.
+pub fn day_length_delta_with_zenith(
+    date: NaiveDate,
+    coords: &Coordinates,
+    zenith: f64,
+) -> Result<Option<Duration>, SpaError> {
+    let Some(yesterday) = date.pred_opt() else {
+        return Ok(None);
+    };
+    let before = day_length_with_zenith(yesterday, coords, zenith)?;
+    let after = day_length_with_zenith(date, coords, zenith)?;
+    Ok(after.change_since(before))
+}
+
+/// Azimuth of the sun at `event`, if it occurs.
+fn azimuth_at(event: Option<DateTime<Utc>>, coords: &Coordinates) -> Result<Option<f64>, SpaError> {
+    event
+        .map(|t| solar_position(t, coords, true).map(|pos| pos.azimuth))
+        .transpose()
+}
+
+/// Where the sun is in the sky, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolarPosition {
+    /// Clockwise from north, in `[0, 360)`.
+    pub azimuth: f64,
+    /// Elevation of the sun's centre above the horizon.
+    pub elevation: f64,
+}
+
+/// Position of the sun at `dt` seen from the given coordinates.
+///
+/// The algorithms give the true geometric position, corrected for
+/// parallax. With `refraction` the elevation is raised by standard
+/// atmospheric refraction near the horizon into the apparent one; without
+/// it, it is left geometric.
+pub fn solar_position(
+    dt: DateTime<Utc>,
+    coords: &Coordinates,
+    refraction: bool,
+) -> Result<SolarPosition, SpaError> {
+    let geometric = geometric_position(dt, coords, None)?;
+    let elevation = if refraction {
+        geometric.elevation + horizon_refraction(geometric.elevation)
+    } else {
+        geometric.elevation
+    };
+
+    Ok(SolarPosition {
+        elevation,
+        ..geometric
+    })
+}
+
+/// True geometric position of the sun at `dt`, from the NREL SPA with the
+/// `accurate` feature and from the `spa` crate's PSA without it. `delta_t`
+/// overrides the estimated ΔT, see [`crate::nrel::solar_position_with_delta_t`].
+#[cfg(feature = "accurate")]
+fn geometric_position(
+    dt: DateTime<Utc>,
+    coords: &Coordinates,
+    delta_t: Option<f64>,
+) -> Result<SolarPosition, SpaError> {
+    match delta_t {
+        Some(delta_t) => crate::nrel::solar_position_with_delta_t(dt, coords, delta_t),
+        None => crate::nrel::solar_position(dt, coords),
+    }
+}
+
+/// True geometric position of the sun at `dt`, from the NREL SPA with the
+/// `accurate` feature and from the `spa` crate's PSA without it. The PSA
+/// works in UT and has no use for `delta_t`.
+#[cfg(not(feature = "accurate"))]
+fn geometric_position(
+    dt: DateTime<Utc>,
+    coords: &Coordinates,
+    _delta_t: Option<f64>,
+) -> Result<SolarPosition, SpaError> {
+    let pos = ::spa::solar_position::<StdFloatOps>(dt, coords.latitude, coords.longitude)?;
+    Ok(SolarPosition {
+        azimuth: pos.azimuth.rem_euclid(360.0),
+        elevation: 90.0 - pos.zenith_angle,
+    })
+}
+
+/// Refraction in degrees that raises the sun from the true `elevation`, by
+/// Sæmundsson's formula for 1010 hPa and 10 °C. The SPA applies it only
+/// while the sun's upper limb is above the horizon.
+fn horizon_refraction(elevation: f64) -> f64 {
+    if elevation < 90.0 - STANDARD_ZENITH {
+        return 0.0;
+    }
+    1.02 / (60.0 * (elevation + 10.3 / (elevation + 5.11)).to_radians().tan())
+}
+
+/// Apparent elevation of the sun's centre above the horizon at `dt`, in
+/// degrees, including atmospheric refraction.
+pub fn solar_elevation(dt: DateTime<Utc>, coords: &Coordinates) -> Result<f64, SpaError> {
+    Ok(solar_position(dt, coords, true)?.elevation)
+}
+
+/// Finds the instant at which the sun crosses `elevation` degrees on the day
+/// of `transit`, while rising (before transit) or setting (after transit).
+///
+/// Returns `None` when the sun never reaches that elevation on that side of
+/// the transit.
+pub fn elevation_crossing(
+    transit: DateTime<Utc>,
+    coords: &Coordinates,
+    elevation: f64,
+    rising: bool,
+) -> Result<Option<DateTime<Utc>>, SpaError> {
+    bisect_crossing(transit, elevation, rising, |t| solar_elevation(t, coords))
+}
+
+/// Instant the sun crosses `elevation` degrees, as given by `elevation_at`,
+/// on the rising or setting side of `transit`.
+fn bisect_crossing(
+    transit: DateTime<Utc>,
+    elevation: f64,
+    rising: bool,
+    elevation_at: impl Fn(DateTime<Utc>) -> Result<f64, SpaError>,
+) -> Result<Option<DateTime<Utc>>, SpaError> {
+    let elevation_at =
+        |offset_nanos: i64| elevation_at(transit + Duration::nanoseconds(offset_nanos));
+
+    // Half a day separates the transit from the lower culmination; the
+    // elevation is monotone in between.
+    let half_day_nanos = 12 * 3600 * 1_000_000_000i64;
+    let far = if rising {
+        -half_day_nanos
+    } else {
+        half_day_nanos
+    };
+
+    if elevation_at(0)? < elevation || elevation_at(far)? > elevation {
+        return Ok(None);
+    }
+
+    // Bisection keeping `near` above and `far` below the target elevation
+    let mut near = 0i64;
+    let mut far = far;
+    while (far - near).abs() > 1000 {
+        // 1 microsecond precision (1000 ns)
+        let mid = near + (far - near) / 2;
+        if elevation_at(mid)? >= elevation {
+            near = mid;
+        } else {
+            far = mid;
+        }
+    }
+
+    Ok(Some(
+        transit + Duration::nanoseconds(near + (far - near) / 2),
+    ))
+}
+
+/// Like [`elevation_crossing`], for the zenith angle `zenith` of the sun's
+/// centre, refined as set by `refinement`.
+fn zenith_crossing(
+    transit: DateTime<Utc>,
+    coords: &Coordinates,
+    zenith: f64,
+    rising: bool,
+    refinement: Refinement,
+    delta_t: Option<f64>,
+) -> Result<Option<DateTime<Utc>>, SpaError> {
+    let bisect = || {
+        bisect_crossing(transit, 90.0 - zenith, rising, |t| {
+            Ok(geometric_position(t, coords, delta_t)?.elevation)
+        })
+    };
+    let steps = match refinement {
+        Refinement::SinglePass => 1,
+        Refinement::Iterative => 8,
+    };
+
+    let mut event = transit;
+    let mut step = Duration::zero();
+    for _ in 0..steps {
+        step = match hour_angle_step(event, coords, zenith, rising, delta_t)? {
+            Some(step) => step,
+            None => return bisect(),
+        };
+        event += step;
+        if step.abs() < Duration::milliseconds(1) {
+            break;
+        }
+    }
+
+    // The `spa` crate's positions have a resolution of one second, which
+    // the steps may keep bouncing within
+    let offset = event - transit;
+    let on_its_side = if rising {
+        offset < Duration::zero() && offset > -Duration::hours(12)
+    } else {
+        offset > Duration::zero() && offset < Duration::hours(12)
+    };
+    if !on_its_side || (steps > 1 && step.abs() > Duration::seconds(1)) {
+        return bisect();
+    }
+    Ok(Some(event))
+}
+
+/// Time from `t` to the hour angle at which the sun, with the declination
+/// it has at `t`, is at the geometric `zenith` while rising or setting.
+/// `None` when it does not reach that zenith with that declination.
+fn hour_angle_step(
+    t: DateTime<Utc>,
+    coords: &Coordinates,
+    zenith: f64,
+    rising: bool,
+    delta_t: Option<f64>,
+) -> Result<Option<Duration>, SpaError> {
+    let pos = geometric_position(t, coords, delta_t)?;
+    let latitude = coords.latitude.to_radians();
+    let (elevation, azimuth) = (pos.elevation.to_radians(), pos.azimuth.to_radians());
+    if latitude.cos() < 1e-9 {
+        return Ok(None);
+    }
+
+    // Declination and hour angle from the horizontal position
+    let sin_declination =
+        latitude.sin() * elevation.sin() + latitude.cos() * elevation.cos() * azimuth.cos();
+    let declination = sin_declination.asin();
+    let hour_angle = (-azimuth.sin() * elevation.cos())
+        .atan2((elevation.sin() - latitude.sin() * sin_declination) / latitude.cos());
+
+    let cos_required = ((90.0 - zenith).to_radians().sin() - latitude.sin() * sin_declination)
+        / (latitude.cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&cos_required) {
+        return Ok(None);
+    }
+    let required = if rising {
+        -cos_required.acos()
+    } else {
+        cos_required.acos()
+    };
+
+    // The hour angle grows 15° an hour; take the shorter way round
+    let turn = std::f64::consts::TAU;
+    let change = (required - hour_angle + turn / 2.0).rem_euclid(turn) - turn / 2.0;
+    Ok(Some(Duration::nanoseconds(
+        (change.to_degrees() * 240.0 * 1e9) as i64,
+    )))
+}
+
+/// Refines an approximate solar noon to the instant the sun crosses the
+/// meridian within ±3 hours of `approx_noon`, at an hour angle of zero.
+///
+/// The highest elevation of the day comes up to about 20 seconds off it,
+/// as the declination changes. Where the meridian passage cannot be found,
+/// e.g. at the poles, the transit is taken at the minimum zenith angle
+/// within ±20 minutes.
+pub fn find_true_solar_noon(approx_noon: DateTime<Utc>, lat: f64, lon: f64) -> DateTime<Utc> {
+    let coords = Coordinates {
+        latitude: lat,
+        longitude: lon,
+    };
+    meridian_passage_near(approx_noon, &coords, true, None)
+}
+
+/// Refines an approximate solar midnight to the lower culmination, the
+/// instant the sun crosses the meridian below the pole, falling back to the
+/// maximum zenith angle like [`find_true_solar_noon`].
+pub fn find_solar_midnight(approx_midnight: DateTime<Utc>, lat: f64, lon: f64) -> DateTime<Utc> {
+    let coords = Coordinates {
+        latitude: lat,
+        longitude: lon,
+    };
+    meridian_passage_near(approx_midnight, &coords, false, None)
+}
+
+/// Upper or lower meridian passage near `approx`, falling back to the
+/// extreme of the zenith angle, for [`find_true_solar_noon`] and
+/// [`find_solar_midnight`].
+fn meridian_passage_near(
+    approx: DateTime<Utc>,
+    coords: &Coordinates,
+    upper: bool,
+    delta_t: Option<f64>,
+) -> DateTime<Utc> {
+    find_meridian_passage(approx, coords, upper, delta_t).unwrap_or_else(|| {
+        minimize_near(approx, |t| match geometric_position(t, coords, delta_t) {
+            Ok(pos) if upper => -pos.elevation,
+            Ok(pos) => pos.elevation,
+            Err(_) => f64::MAX, // If it fails, return infinity to discard it
+        })
+    })
+}
+
+/// Instant within ±3 hours of `approx` at which the sun crosses the
+/// meridian, to the microsecond, or `None` when it does not cross it there.
+/// The upper passage goes from east to west of it, the lower one from west
+/// to east.
+fn find_meridian_passage(
+    approx: DateTime<Utc>,
+    coords: &Coordinates,
+    upper: bool,
+    delta_t: Option<f64>,
+) -> Option<DateTime<Utc>> {
+    // The sine of the azimuth is positive while the sun is east of the
+    // meridian, whether it culminates south or north of the zenith
+    let east_at = |offset_nanos: i64| {
+        geometric_position(
+            approx + Duration::nanoseconds(offset_nanos),
+            coords,
+            delta_t,
+        )
+        .ok()
+        .map(|pos| pos.azimuth.to_radians().sin())
+        .filter(|sine| sine.is_finite())
+        .map(|sine| sine > 0.0)
+    };
+
+    let mut low = -3 * 3600 * 1_000_000_000i64;
+    let mut high = -low;
+    if east_at(low)? != upper || east_at(high)? == upper {
+        return None;
+    }
+    while high - low > 1000 {
+        // 1 microsecond precision (1000 ns)
+        let mid = low + (high - low) / 2;
+        if east_at(mid)? == upper {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some(approx + Duration::nanoseconds(low + (high - low) / 2))
+}
+
+/// Instant within ±20 minutes of `approx` that minimizes `objective`, to the
+/// microsecond.
+fn minimize_near(approx: DateTime<Utc>, objective: impl Fn(DateTime<Utc>) -> f64) -> DateTime<Utc> {
+    let value_at = |offset_nanos: i64| objective(approx + Duration::nanoseconds(offset_nanos));
+
+    // Golden Section Search to find the minimum
+    let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+    let resphi = 2.0 - phi;
+
+    // Search window: +/- 20 minutes in nanoseconds
+    let window_nanos = 20 * 60 * 1_000_000_000i64;
+
+    let mut a = -window_nanos;
+    let mut b = window_nanos;
+    let mut c = a + (resphi * (b as f64 - a as f64)) as i64;
+    let mut d = b - (resphi * (b as f64 - a as f64)) as i64;
+
+    // Evaluate the function at points c and d
+    let mut fc = value_at(c);
+    let mut fd = value_at(d);
+
+    // Iterate until we have microsecond precision (or close enough)
+    // 1000 iterations are enough for nanosecond precision, but we stop
+    // when the window is small
+    while (b - a).abs() > 1000 {
+        // 1 microsecond precision (1000 ns)
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = a + (resphi * (b as f64 - a as f64)) as i64;
+            fc = value_at(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = b - (resphi * (b as f64 - a as f64)) as i64;
+            fd = value_at(d);
+        }
+    }
+
+    let optimal_offset = (a + b) / 2;
+    approx + Duration::nanoseconds(optimal_offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, NaiveTime, TimeZone};
+
+    use super::*;
+
+    /// Sunrise, sunset and civil twilight from the U.S. Naval Observatory
+    /// tables, see the header of the file.
+    const USNO: &str = include_str!("../tests/data/usno.csv");
+
+    struct AlmanacDay {
+        kind: &'static str,
+        date: NaiveDate,
+        coords: Coordinates,
+        rise: Option<NaiveTime>,
+        set: Option<NaiveTime>,
+    }
+
+    fn almanac() -> impl Iterator<Item = AlmanacDay> {
+        let time = |field: &str| NaiveTime::parse_from_str(field, "%H:%M").ok();
+        USNO.lines()
+            .filter(|line| !line.starts_with('#'))
+            .map(move |line| {
+                let fields: Vec<&'static str> = line.split(',').collect();
+                AlmanacDay {
+                    kind: fields[0],
+                    date: fields[1].parse().unwrap(),
+                    coords: Coordinates::new(
+                        fields[2].parse().unwrap(),
+                        fields[3].parse().unwrap(),
+                    )
+                    .unwrap(),
+                    rise: time(fields[4]),
+                    set: time(fields[5]),
+                }
+            })
+    }
+
+    /// Rising and setting events of `day.kind` that fall on `day.date`, which
+    /// may belong to the transits of the days on either side.
+    fn events_on(day: &AlmanacDay) -> (Vec<DateTime<Utc>>, Vec<DateTime<Utc>>) {
+        let (mut rises, mut sets) = (Vec::new(), Vec::new());
+        for date in [
+            day.date.pred_opt().unwrap(),
+            day.date,
+            day.date.succ_opt().unwrap(),
+        ] {
+            let (rise, set) = match day.kind {
+                "sun" => {
+                    let data = calculate_solar_data(date, &day.coords).unwrap();
+                    (data.sunrise, data.sunset)
+                }
+                "civil" => {
+                    let data = calculate_twilight_data(date, &day.coords).unwrap();
+                    (data.civil_dawn, data.civil_dusk)
+                }
+                kind => panic!("unknown almanac kind {kind}"),
+            };
+            rises.extend(rise.filter(|rise| rise.date_naive() == day.date));
+            sets.extend(set.filter(|set| set.date_naive() == day.date));
+        }
+        (rises, sets)
+    }
+
+    /// Asserts that `found` holds exactly the `expected` event, if any, to
+    /// within `tolerance` of the almanac's whole minute.
+    fn assert_event(
+        day: &AlmanacDay,
+        what: &str,
+        expected: Option<NaiveTime>,
+        found: &[DateTime<Utc>],
+        tolerance: Duration,
+    ) {
+        let context = format!("{what} of {} on {} at {:?}", day.kind, day.date, day.coords);
+        let Some(expected) = expected else {
+            assert!(found.is_empty(), "unexpected {context}: {found:?}");
+            return;
+        };
+        let [found] = found else {
+            panic!("expected one {context}, found {found:?}");
+        };
+        let expected = day.date.and_time(expected).and_utc();
+        let error = (*found - expected).abs();
+        assert!(
+            error <= tolerance,
+            "{context}: {found} is {error} away from the almanac's {expected}"
+        );
+    }
+
+    #[test]
+    fn events_match_the_usno_almanac() {
+        for day in almanac() {
+            let (rises, sets) = events_on(&day);
+            // The almanac is rounded to the minute
+            let tolerance = Duration::seconds(45);
+            assert_event(&day, "rise", day.rise, &rises, tolerance);
+            assert_event(&day, "set", day.set, &sets, tolerance);
+        }
+    }
+
+    #[test]
+    fn events_fall_on_the_requested_date() {
+        // Regression: querying `sunrise_and_set` at midnight returned the
+        // previous day's events
+        for date in [
+            NaiveDate::from_ymd_opt(2026, 2, 3).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 12, 21).unwrap(),
+        ] {
+            let data = calculate_solar_data(date, &madrid()).unwrap();
+            assert_eq!(data.sunrise.unwrap().date_naive(), date);
+            assert_eq!(data.transit.date_naive(), date);
+            assert_eq!(data.sunset.unwrap().date_naive(), date);
+        }
+    }
+
+    fn madrid() -> Coordinates {
+        Coordinates::new(40.4168, -3.7038).unwrap()
+    }
+
+    /// Time of day of the transit in Madrid on `year-month-day`, in seconds.
+    fn transit_seconds(year: i32, month: u32, day: u32) -> f64 {
+        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let transit = calculate_solar_data(date, &madrid()).unwrap().transit;
+        (transit - date.and_time(NaiveTime::MIN).and_utc()).as_seconds_f64()
+    }
+
+    #[test]
+    fn the_same_date_in_other_years_has_another_transit() {
+        // The calendar drifts against the seasons between leap days, so the
+        // equation of time on a given date changes from year to year
+        let now = transit_seconds(2026, 2, 3);
+        for year in [2016, 2036] {
+            let other = transit_seconds(year, 2, 3);
+            let difference = (other - now).abs();
+            assert!((0.5..60.0).contains(&difference), "{year}: {difference} s");
+        }
+        assert_ne!(transit_seconds(2016, 2, 3), transit_seconds(2036, 2, 3));
+    }
+
+    #[test]
+    fn events_move_smoothly_across_year_ends_and_leap_days() {
+        for days in [
+            [(2025, 12, 30), (2025, 12, 31), (2026, 1, 1)],
+            [(2024, 2, 28), (2024, 2, 29), (2024, 3, 1)],
+        ] {
+            let [first, second, third] = days.map(|(year, month, day)| {
+                let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+                calculate_solar_data(date, &madrid()).unwrap()
+            });
+            let transit = days.map(|(year, month, day)| transit_seconds(year, month, day));
+            let change = transit[2] - transit[1];
+            assert!(change.abs() < 30.0, "{days:?}: {change} s");
+
+            // Sunrise and sunset can move by over a minute a day, but by
+            // about as much on both sides of the date change
+            for event in [
+                |data: &SolarData| data.sunrise,
+                |data: &SolarData| data.sunset,
+            ] {
+                let daily = |earlier: &SolarData, later: &SolarData| {
+                    (event(later).unwrap() - event(earlier).unwrap() - Duration::days(1))
+                        .as_seconds_f64()
+                };
+                let (before, after) = (daily(&first, &second), daily(&second, &third));
+                assert!(
+                    (after - before).abs() < 5.0,
+                    "{days:?}: {before} then {after} s"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn positions_are_smooth_across_midnight() {
+        // Minute by minute from 23:00 to 01:00, the change in elevation and
+        // azimuth itself changes by little, with no step at the date change.
+        // The sun crosses north at about 00:15, where the azimuth wraps
+        let midnight = Utc.with_ymd_and_hms(2026, 2, 3, 0, 0, 0).unwrap();
+        let positions: Vec<SolarPosition> = (-60..=60)
+            .map(|minutes| {
+                solar_position(midnight + Duration::minutes(minutes), &madrid(), false).unwrap()
+            })
+            .collect();
+        for window in positions.windows(3) {
+            let step = |f: fn(&SolarPosition) -> f64| {
+                let change = |a, b| (f(b) - f(a) + 540.0).rem_euclid(360.0) - 180.0;
+                (change(&window[1], &window[2]) - change(&window[0], &window[1])).abs()
+            };
+            assert!(step(|p| p.elevation) < 0.01, "{window:?}");
+            assert!(step(|p| p.azimuth) < 0.01, "{window:?}");
+        }
+    }
+
+    #[test]
+    fn seasons_match_the_published_instants() {
+        // 2026 instants published to the minute by the USNO
+        let markers = solstices_equinoxes(2026).unwrap();
+        let published = [
+            (markers.march_equinox, "2026-03-20T14:46:00Z"),
+            (markers.june_solstice, "2026-06-21T08:24:00Z"),
+            (markers.september_equinox, "2026-09-23T00:05:00Z"),
+            (markers.december_solstice, "2026-12-21T20:50:00Z"),
+        ];
+        for (computed, expected) in published {
+            let expected: DateTime<Utc> = expected.parse().unwrap();
+            let error = (computed - expected).as_seconds_f64();
+            assert!(error.abs() < 90.0, "{computed} vs {expected}");
+        }
+
+        // The declination is zero at the equinoxes and extreme at the
+        // solstices, give or take the sun's ecliptic latitude
+        assert!(declination(markers.march_equinox).unwrap().abs() < 0.01);
+        assert!(declination(markers.september_equinox).unwrap().abs() < 0.01);
+        assert!((declination(markers.june_solstice).unwrap() - 23.436).abs() < 0.01);
+        assert!((declination(markers.december_solstice).unwrap() + 23.436).abs() < 0.01);
+
+        assert!(solstices_equinoxes(-1000).is_some());
+        assert!(solstices_equinoxes(3000).is_some());
+        assert_eq!(solstices_equinoxes(3001), None);
+        assert_eq!(solstices_equinoxes(-1001), None);
+    }
+
+    #[test]
+    fn delta_t_estimate_follows_the_record() {
+        for (year, observed) in [
+            (1900.0, -2.8),
+            (1950.0, 29.1),
+            (2000.0, 63.8),
+            (2020.0, 69.4),
+        ] {
+            let estimate = estimate_delta_t(year);
+            assert!((estimate - observed).abs() < 2.5, "{year}: {estimate}");
+        }
+        // The fits join within a fraction of a second
+        for year in [
+            -500.0, 500.0, 1600.0, 1700.0, 1800.0, 1860.0, 1900.0, 1920.0, 1941.0, 1961.0, 1986.0,
+            2005.0, 2050.0, 2150.0,
+        ] {
+            let step = estimate_delta_t(year) - estimate_delta_t(year - 1e-9);
+            assert!(step.abs() < 0.5, "{year}: {step}");
+        }
+    }
+
+    /// Events computed by JPL Horizons from the DE440s ephemeris with a ΔT
+    /// of 69.184 s, as published with the `solar-positioning` crate's test
+    /// data: latitude, longitude, UTC date, and the transit, sunrise and
+    /// sunset that fall on that date, the latter two at the
+    /// standard zenith.
+    const JPL_EVENTS: [(f64, f64, &str, &str, &str, &str); 4] = [
+        (
+            39.742476,
+            -105.1786,
+            "2003-10-17",
+            "2003-10-17T18:46:04.990Z",
+            "2003-10-17T13:12:44.270Z",
+            "2003-10-17T00:20:18.425Z",
+        ),
+        (
+            48.21,
+            16.37,
+            "2024-03-20",
+            "2024-03-20T11:01:50.467Z",
+            "2024-03-20T04:56:47.557Z",
+            "2024-03-20T17:07:47.200Z",
+        ),
+        (
+            -36.84,
+            174.74,
+            "2024-12-21",
+            "2024-12-21T00:19:05.371Z",
+            "2024-12-21T16:58:49.153Z",
+            "2024-12-21T07:39:51.441Z",
+        ),
+        (
+            61.216667,
+            -149.866667,
+            "2020-07-05",
+            "2020-07-05T22:04:13.916Z",
+            "2020-07-05T12:33:47.798Z",
+            "2020-07-05T07:34:47.404Z",
+        ),
+    ];
+
+    /// The `spa` crate's PSA positions have a resolution of one second.
+    fn jpl_tolerance() -> f64 {
+        if cfg!(feature = "accurate") { 0.5 } else { 2.0 }
+    }
+
+    #[test]
+    fn transit_matches_jpl_horizons() {
+        for (latitude, longitude, date, transit, ..) in JPL_EVENTS {
+            let coords = Coordinates::new(latitude, longitude).unwrap();
+            let data = calculate_solar_data(date.parse().unwrap(), &coords).unwrap();
+            let expected: DateTime<Utc> = transit.parse().unwrap();
+            let error = (data.transit - expected).as_seconds_f64();
+            assert!(
+                error.abs() < jpl_tolerance(),
+                "{date} at {latitude}: off by {error} s"
+            );
+        }
+    }
+
+    #[test]
+    fn delta_t_moves_the_transit() {
+        let (latitude, longitude, date, ..) = JPL_EVENTS[1];
+        let coords = Coordinates::new(latitude, longitude).unwrap();
+        let date: NaiveDate = date.parse().unwrap();
+        let transit = |delta_t| {
+            calculate_solar_data_with_delta_t(
+                date,
+                &coords,
+                STANDARD_ZENITH,
+                Refinement::Iterative,
+                Some(delta_t),
+            )
+            .unwrap()
+            .transit
+        };
+        let shift = (transit(69.0) - transit(0.0)).as_seconds_f64();
+
+        if cfg!(feature = "accurate") {
+            // ΔT later the sun is further east by its motion in right
+            // ascension, cos ε times 360° a year at the equinox, and the
+            // meridian reaches it that much later
+            let expected = 69.0 * 23.44f64.to_radians().cos() / 365.2422;
+            assert!((shift - expected).abs() < 0.01, "{shift} s vs {expected} s");
+        } else {
+            assert_eq!(shift, 0.0);
+        }
+    }
+
+    /// Errors in seconds of the sunrise and sunset on `date` with
+    /// `refinement`, from the days whose transits are around it.
+    fn jpl_errors(
+        (latitude, longitude, date, _, rise, set): (f64, f64, &str, &str, &str, &str),
+        refinement: Refinement,
+    ) -> [f64; 2] {
+        let coords = Coordinates::new(latitude, longitude).unwrap();
+        let date: NaiveDate = date.parse().unwrap();
+        let days = [date.pred_opt().unwrap(), date, date.succ_opt().unwrap()].map(|day| {
+            calculate_solar_data_with_refinement(day, &coords, STANDARD_ZENITH, refinement).unwrap()
+        });
+        let error = |expected: &str, events: [Option<DateTime<Utc>>; 3]| {
+            let expected: DateTime<Utc> = expected.parse().unwrap();
+            let [found] = events
+                .into_iter()
+                .flatten()
+                .filter(|event| event.date_naive() == date)
+                .collect::<Vec<_>>()[..]
+            else {
+                panic!("expected one event on {date} at {latitude}");
+            };
+            (found - expected).as_seconds_f64()
+        };
+        [
+            error(rise, days.map(|data| data.sunrise)),
+            error(set, days.map(|data| data.sunset)),
+        ]
+    }
+
+    #[test]
+    fn refined_events_match_jpl_horizons() {
+        for events in JPL_EVENTS {
+            for error in jpl_errors(events, Refinement::Iterative) {
+                assert!(
+                    error.abs() < jpl_tolerance(),
+                    "{events:?}: off by {error} s"
+                );
+            }
+        }
+
+        // At the March equinox the declination changes fastest: a single
+        // step from the transit misses by tens of seconds
+        let equinox = JPL_EVENTS[1];
+        let single = jpl_errors(equinox, Refinement::SinglePass);
+        let iterative = jpl_errors(equinox, Refinement::Iterative);
+        for (single, iterative) in single.into_iter().zip(iterative) {
+            assert!(single.abs() > 10.0, "{single}");
+            assert!(
+                iterative.abs() < single.abs() / 10.0,
+                "{iterative} vs {single}"
+            );
+        }
+    }
+
+    #[test]
+    fn larger_zenith_widens_the_day() {
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let at = |zenith| calculate_solar_data_with_zenith(date, &madrid(), zenith).unwrap();
+
+        // Every step, including the ones across the standard zenith, moves
+        // sunrise earlier and sunset later
+        let mut zeniths: Vec<f64> = (0..=40).map(|step| 89.0 + f64::from(step) * 0.05).collect();
+        zeniths.extend([STANDARD_ZENITH, 90.84]);
+        zeniths.sort_by(f64::total_cmp);
+        let days: Vec<SolarData> = zeniths.iter().map(|zenith| at(*zenith)).collect();
+        for (pair, zenith) in days.windows(2).zip(&zeniths[1..]) {
+            assert!(pair[1].sunrise < pair[0].sunrise, "sunrise at {zenith}");
+            assert!(pair[1].sunset > pair[0].sunset, "sunset at {zenith}");
+            assert_eq!(pair[1].transit, pair[0].transit);
+        }
+
+        let standard = at(STANDARD_ZENITH);
+        let astronomical = at(108.0);
+        let widening = |data: &SolarData| {
+            (standard.sunrise.unwrap() - data.sunrise.unwrap())
+                + (data.sunset.unwrap() - standard.sunset.unwrap())
+        };
+        // The sun sinks about 12° an hour at 40° N in February
+        let widening = widening(&astronomical);
+        assert!(
+            widening > Duration::minutes(150) && widening < Duration::minutes(200),
+            "{widening}"
+        );
+
+        // A zenith the sun does not reach leaves no sunrise or sunset
+        let data = at(45.0);
+        assert_eq!((data.sunrise, data.sunset), (None, None));
+    }
+
+    #[test]
+    fn sun_centre_is_at_standard_zenith_at_sunrise() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 10).unwrap();
+        let data = calculate_solar_data(date, &madrid()).unwrap();
+        // The sun climbs about 0.003° in the second the events are resolved to
+        for event in [data.sunrise.unwrap(), data.sunset.unwrap()] {
+            let geometric = solar_position(event, &madrid(), false).unwrap().elevation;
+            assert!(
+                (geometric - (90.0 - STANDARD_ZENITH)).abs() < 0.01,
+                "{geometric}"
+            );
+        }
+    }
+
+    #[test]
+    fn elevation_peaks_at_transit_due_south() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 10).unwrap();
+        let data = calculate_solar_data(date, &madrid()).unwrap();
+        let at = |t| solar_position(t, &madrid(), true).unwrap();
+
+        // The azimuth sweeps through the meridian at about half a degree a
+        // minute, and the `spa` crate has a resolution of one second
+        let transit = at(data.transit);
+        assert!((transit.azimuth - 180.0).abs() < 0.02, "{transit:?}");
+        for minutes in [-120, -10, -1, 1, 10, 120] {
+            let other = at(data.transit + Duration::minutes(minutes));
+            assert!(
+                other.elevation < transit.elevation,
+                "{minutes} min: {other:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn refraction_raises_the_sun_near_the_horizon() {
+        let date = NaiveDate::from_ymd_opt(2026, 4, 10).unwrap();
+        let data = calculate_solar_data(date, &madrid()).unwrap();
+        let sunrise = data.sunrise.unwrap();
+
+        // Just after sunrise the sun's centre is near the geometric horizon,
+        // where refraction lifts it by about half a degree
+        let after = sunrise + Duration::minutes(4);
+        let geometric = solar_position(after, &madrid(), false).unwrap();
+        let apparent = solar_position(after, &madrid(), true).unwrap();
+        let lift = apparent.elevation - geometric.elevation;
+        assert!((0.3..0.6).contains(&lift), "{lift}");
+        assert_eq!(apparent.azimuth, geometric.azimuth);
+
+        // High in the sky it is below 0.02°
+        let geometric = solar_position(data.transit, &madrid(), false).unwrap();
+        let apparent = solar_position(data.transit, &madrid(), true).unwrap();
+        let lift = apparent.elevation - geometric.elevation;
+        assert!((0.0..0.02).contains(&lift), "{lift}");
+
+        // Below the horizon it does not apply
+        let night = solar_position(data.midnight, &madrid(), true).unwrap();
+        let geometric = solar_position(data.midnight, &madrid(), false).unwrap();
+        assert_eq!(night, geometric);
+    }
+
+    #[test]
+    fn poles_give_polar_day_and_night() {
+        let solstices = [
+            NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 12, 21).unwrap(),
+        ];
+        for latitude in [90.0, -90.0, 89.9999, -89.9999] {
+            let coords = Coordinates::new(latitude, 0.0).unwrap();
+            for date in solstices {
+                let data = calculate_solar_data(date, &coords).unwrap();
+                assert_eq!(
+                    (data.sunrise, data.sunset),
+                    (None, None),
+                    "{date} at {latitude}"
+                );
+                assert_eq!((data.sunrise_azimuth, data.sunset_azimuth), (None, None));
+                // The sun circles at about the declination all day
+                let summer = (latitude > 0.0) == (date.month() == 6);
+                let expected = if summer { 23.4 } else { -23.4 };
+                assert!(
+                    (data.transit_elevation - expected).abs() < 0.6,
+                    "{date} at {latitude}: {}",
+                    data.transit_elevation
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn poles_never_produce_nan() {
+        let dates = [(3, 20), (6, 21), (9, 23), (12, 21)]
+            .map(|(month, day)| NaiveDate::from_ymd_opt(2026, month, day).unwrap());
+        for latitude in [90.0, -90.0, 89.9999, -89.9999] {
+            for longitude in [-180.0, 0.0, 97.5] {
+                let coords = Coordinates::new(latitude, longitude).unwrap();
+                for date in dates {
+                    let context = format!("{date} at ({latitude}, {longitude})");
+                    let data = calculate_solar_data(date, &coords).unwrap();
+                    assert!(data.transit_elevation.is_finite(), "{context}");
+                    assert_eq!(data.sunrise.is_some(), data.sunrise_azimuth.is_some());
+                    assert_eq!(data.sunset.is_some(), data.sunset_azimuth.is_some());
+                    for azimuth in [data.sunrise_azimuth, data.sunset_azimuth]
+                        .into_iter()
+                        .flatten()
+                    {
+                        assert!((0.0..=360.0).contains(&azimuth), "{context}: {azimuth}");
+                    }
+                    if let (Some(sunrise), Some(sunset)) = (data.sunrise, data.sunset) {
+                        assert!(sunrise < sunset, "{context}");
+                    }
+                    calculate_twilight_data(date, &coords).unwrap();
+
+                    // Nor does a clock there: a result or an error, no panic
+                    let clock = crate::SolarClock::new(
+                        coords,
+                        crate::solar_clock::DEFAULT_TARGETS,
+                        crate::solar_clock::SOLAR_TIMEZONE_OFFSET,
+                    );
+                    if let Ok(result) = clock.solar_time(data.transit) {
+                        assert!(result.delta.is_finite(), "{context}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn twilights_match_noaa() {
+        // The algorithm of the NOAA Solar Calculator for Madrid on 2026-02-03,
+        // in UTC
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let twilight = calculate_twilight_data(date, &madrid()).unwrap();
+        for (name, found, expected) in [
+            ("astronomical dawn", twilight.astronomical_dawn, "05:49:14"),
+            ("nautical dawn", twilight.nautical_dawn, "06:21:09"),
+            ("civil dawn", twilight.civil_dawn, "06:53:38"),
+            ("civil dusk", twilight.civil_dusk, "18:04:07"),
+            ("nautical dusk", twilight.nautical_dusk, "18:36:37"),
+            ("astronomical dusk", twilight.astronomical_dusk, "19:08:34"),
+        ] {
+            let expected = date.and_time(expected.parse().unwrap()).and_utc();
+            let error = (found.unwrap() - expected).abs();
+            assert!(error < Duration::minutes(1), "{name}: {error}");
+        }
+    }
+
+    #[test]
+    fn twilights_that_are_not_reached_are_none() {
+        let at = |latitude, month, day| {
+            let date = NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+            calculate_twilight_data(date, &Coordinates::new(latitude, 0.0).unwrap()).unwrap()
+        };
+
+        // At 49° N astronomical night ends with a last dawn, then a first dusk
+        let last = at(49.0, 6, 10);
+        assert!(last.astronomical_dawn.is_some() && last.astronomical_dusk.is_none());
+        let first = at(49.0, 7, 2);
+        assert!(first.astronomical_dawn.is_none() && first.astronomical_dusk.is_some());
+        let midsummer = at(49.0, 6, 21);
+        assert_eq!(
+            (midsummer.astronomical_dawn, midsummer.astronomical_dusk),
+            (None, None)
+        );
+        assert!(midsummer.nautical_dawn.is_some() && midsummer.nautical_dusk.is_some());
+
+        // The midnight sun of Tromsø reaches none of them
+        let tromso = calculate_twilight_data(
+            NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(),
+            &Coordinates::new(69.65, 18.96).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            tromso,
+            TwilightData {
+                civil_dawn: None,
+                civil_dusk: None,
+                nautical_dawn: None,
+                nautical_dusk: None,
+                astronomical_dawn: None,
+                astronomical_dusk: None,
+            }
+        );
+    }
+
+    #[test]
+    fn twilights_precede_sunrise_in_order() {
+        for (latitude, longitude) in [(40.4168, -3.7038), (-36.84, 174.74), (1.28, 103.83)] {
+            let coords = Coordinates::new(latitude, longitude).unwrap();
+            for month in 1..=12 {
+                let date = NaiveDate::from_ymd_opt(2026, month, 10).unwrap();
+                let data = calculate_solar_data(date, &coords).unwrap();
+                let twilight = calculate_twilight_data(date, &coords).unwrap();
+
+                let morning = [
+                    twilight.astronomical_dawn,
+                    twilight.nautical_dawn,
+                    twilight.civil_dawn,
+                    data.sunrise,
+                    Some(data.transit),
+                ];
+                let evening = [
+                    Some(data.transit),
+                    data.sunset,
+                    twilight.civil_dusk,
+                    twilight.nautical_dusk,
+                    twilight.astronomical_dusk,
+                ];
+                for events in [morning, evening] {
+                    let events: Vec<_> = events.map(Option::unwrap).into();
+                    assert!(
+                        events.windows(2).all(|pair| pair[0] < pair[1]),
+                        "{date} at {latitude}: {events:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn equinox_sun_rises_east_and_sets_west() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+        for (latitude, longitude) in [(40.4168, -3.7038), (-33.87, 151.21), (35.68, 139.69)] {
+            let coords = Coordinates::new(latitude, longitude).unwrap();
+            let data = calculate_solar_data(date, &coords).unwrap();
+            let (rise, set) = (data.sunrise_azimuth.unwrap(), data.sunset_azimuth.unwrap());
+            assert!((rise - 90.0).abs() < 1.0, "{latitude}: {rise}");
+            assert!((set - 270.0).abs() < 1.0, "{latitude}: {set}");
+        }
+
+        // North of east in the northern summer, south of it in winter
+        let summer = calculate_solar_data(NaiveDate::from_ymd_opt(2026, 6, 21).unwrap(), &madrid());
+        assert!(summer.unwrap().sunrise_azimuth.unwrap() < 65.0);
+        let winter =
+            calculate_solar_data(NaiveDate::from_ymd_opt(2026, 12, 21).unwrap(), &madrid());
+        assert!(winter.unwrap().sunrise_azimuth.unwrap() > 115.0);
+
+        // Through polar day and night, an azimuth exactly when its event
+        let tromso = Coordinates::new(69.65, 18.96).unwrap();
+        for day in (0..365).step_by(5) {
+            let date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap() + Duration::days(day);
+            let data = calculate_solar_data(date, &tromso).unwrap();
+            assert_eq!(
+                data.sunrise.is_some(),
+                data.sunrise_azimuth.is_some(),
+                "{date}"
+            );
+            assert_eq!(
+                data.sunset.is_some(),
+                data.sunset_azimuth.is_some(),
+                "{date}"
+            );
+        }
+    }
+
+    #[test]
+    fn transit_elevation_is_the_meridian_altitude() {
+        for (latitude, longitude) in [(38.35, -0.48), (-33.87, 151.21), (64.15, -21.94)] {
+            let coords = Coordinates::new(latitude, longitude).unwrap();
+            for month in 1..=12 {
+                let date = NaiveDate::from_ymd_opt(2026, month, 5).unwrap();
+                let data = calculate_solar_data(date, &coords).unwrap();
+                let meridian = 90.0 - (latitude - declination(data.transit).unwrap()).abs();
+                // Parallax lowers the true position by 0.0024°
+                let geometric = solar_position(data.transit, &coords, false).unwrap();
+                assert!(
+                    (geometric.elevation - meridian).abs() < 0.01,
+                    "{date} at {latitude}: {} vs {meridian}",
+                    geometric.elevation
+                );
+                assert!(data.transit_elevation >= geometric.elevation);
+            }
+        }
+
+        let alicante = Coordinates::new(38.35, -0.48).unwrap();
+        let at = |month, day| {
+            let date = NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+            calculate_solar_data(date, &alicante)
+                .unwrap()
+                .transit_elevation
+        };
+        assert!((at(6, 21) - 75.1).abs() < 0.5, "{}", at(6, 21));
+        assert!((at(12, 21) - 28.2).abs() < 0.5, "{}", at(12, 21));
+    }
+
+    #[test]
+    fn equation_of_time_has_its_canonical_extremes() {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let year: Vec<(NaiveDate, f64)> = (0..365)
+            .map(|day| start + Duration::days(day))
+            .map(|date| (date, equation_of_time(date).unwrap()))
+            .collect();
+        let by_value = |a: &&(NaiveDate, f64), b: &&(NaiveDate, f64)| a.1.total_cmp(&b.1);
+        let (latest, most) = *year.iter().max_by(by_value).unwrap();
+        let (earliest, least) = *year.iter().min_by(by_value).unwrap();
+
+        // The sundial is about 16.5 minutes ahead in early November...
+        assert!((most - 16.5).abs() < 1.0, "{latest}: {most}");
+        assert!((10, 28) <= (latest.month(), latest.day()), "{latest}");
+        assert!((latest.month(), latest.day()) <= (11, 8), "{latest}");
+        // ...and about 14 minutes behind in mid-February
+        assert!((least + 14.2).abs() < 1.0, "{earliest}: {least}");
+        assert!((2, 5) <= (earliest.month(), earliest.day()), "{earliest}");
+        assert!((earliest.month(), earliest.day()) <= (2, 18), "{earliest}");
+
+        // The sign: with the sundial behind, the transit at Greenwich is late
+        let date = NaiveDate::from_ymd_opt(2026, 2, 11).unwrap();
+        let greenwich = Coordinates::new(0.0, 0.0).unwrap();
+        let transit = calculate_solar_data(date, &greenwich).unwrap().transit;
+        let noon = date.and_hms_opt(12, 0, 0).unwrap().and_utc();
+        let late = (transit - noon).as_seconds_f64() / 60.0;
+        assert!(
+            (late + equation_of_time(date).unwrap()).abs() < 0.1,
+            "{late}"
+        );
+    }
+
+    #[test]
+    fn declination_follows_the_seasons() {
+        let seasons = solstices_equinoxes(2026).unwrap();
+        let march = declination(seasons.march_equinox).unwrap();
+        assert!(march.abs() < 0.01, "{march}");
+        assert!((declination(seasons.june_solstice).unwrap() - 23.44).abs() < 0.01);
+        assert!(declination(seasons.september_equinox).unwrap().abs() < 0.01);
+        assert!((declination(seasons.december_solstice).unwrap() + 23.44).abs() < 0.01);
+        // Half a day after the equinox it has moved by about 0.2°
+        let later = declination(seasons.march_equinox + Duration::hours(12)).unwrap();
+        assert!((later - 0.2).abs() < 0.02, "{later}");
+    }
+
+    #[test]
+    fn hour_angle_is_zero_at_transit() {
+        for (latitude, longitude) in [(40.4168, -3.7038), (-33.87, 151.21), (64.15, -21.94)] {
+            let coords = Coordinates::new(latitude, longitude).unwrap();
+            for month in [2, 5, 8, 11] {
+                let date = NaiveDate::from_ymd_opt(2026, month, 3).unwrap();
+                let transit = calculate_solar_data(date, &coords).unwrap().transit;
+                let angle = hour_angle(transit, longitude, equation_of_time(date).unwrap());
+                // The sun moves a degree in four minutes, the equation of
+                // time a few seconds in a day
+                assert!(angle.abs() < 0.05, "{date} at {longitude}: {angle}");
+                let later = hour_angle(
+                    transit + Duration::hours(1),
+                    longitude,
+                    equation_of_time(date).unwrap(),
+                );
+                assert!((later - angle - 15.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn day_length_at_the_equator_and_at_38_north() {
+        let equator = Coordinates::new(0.0, 0.0).unwrap();
+        for day in (0..365).step_by(7) {
+            let date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap() + Duration::days(day);
+            let DayLength::Daylight(length) = day_length(date, &equator).unwrap() else {
+                panic!("{date}: no sunrise or sunset at the equator");
+            };
+            // Refraction and the sun's radius add a few minutes to the half day
+            let excess = length - Duration::hours(12);
+            assert!(
+                excess > Duration::zero() && excess < Duration::minutes(10),
+                "{date}: {length}"
+            );
+        }
+
+        // Textbook solstice values, which tables including refraction and
+        // the sun's radius put a minute or two longer
+        let coords = Coordinates::new(38.0, 0.0).unwrap();
+        for (month, day, expected) in [(6, 21, (14, 46)), (12, 21, (9, 32))] {
+            let date = NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+            let length = day_length(date, &coords).unwrap().duration();
+            let expected = Duration::hours(expected.0) + Duration::minutes(expected.1);
+            assert!(
+                (length - expected).abs() < Duration::minutes(3),
+                "{date}: {length}"
+            );
+        }
+    }
+
+    #[test]
+    fn day_length_represents_polar_day_and_night() {
+        let tromso = Coordinates::new(69.65, 18.96).unwrap();
+        let at = |month, day| {
+            let date = NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+            day_length(date, &tromso).unwrap()
+        };
+
+        assert_eq!(at(6, 21), DayLength::PolarDay);
+        assert_eq!(at(6, 21).duration(), Duration::days(1));
+        assert_eq!(at(12, 21), DayLength::PolarNight);
+        assert_eq!(at(12, 21).duration(), Duration::zero());
+        // No change is reported across the start of polar day
+        assert_eq!(at(6, 21).change_since(at(6, 20)), None);
+        assert_eq!(at(5, 1).change_since(at(6, 21)), None);
+
+        // Elsewhere the days grow in spring, by about three minutes in Madrid
+        let date = NaiveDate::from_ymd_opt(2026, 3, 20).unwrap();
+        let change = day_length_delta(date, &madrid()).unwrap().unwrap();
+        assert!(
+            change > Duration::minutes(2) && change < Duration::minutes(3),
+            "{change}"
+        );
+    }
+
+    #[test]
+    fn photo_hours_match_noaa() {
+        // The elevations of the bands by the algorithm of the NOAA Solar
+        // Calculator, which leaves out refraction, for Madrid on 2026-02-03
+        let date = NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+        let hours = calculate_photo_hours(date, &madrid()).unwrap();
+        let utc = |time: &str| date.and_time(time.parse().unwrap()).and_utc();
+        for (name, found, expected) in [
+            ("morning blue", hours.morning_blue, ("06:53:38", "07:04:39")),
+            (
+                "morning golden",
+                hours.morning_golden,
+                ("07:04:39", "08:01:43"),
+            ),
+            (
+                "evening golden",
+                hours.evening_golden,
+                ("16:56:01", "17:53:06"),
+            ),
+            ("evening blue", hours.evening_blue, ("17:53:06", "18:04:07")),
+        ] {
+            let (start, end) = found.unwrap();
+            for (found, expected) in [(start, expected.0), (end, expected.1)] {
+                let error = (found - utc(expected)).abs();
+                assert!(
+                    error < Duration::minutes(2),
+                    "{name}: {found} vs {expected}"
+                );
+            }
+        }
+        // The bands follow each other without gaps
+        assert_eq!(
+            hours.morning_blue.unwrap().1,
+            hours.morning_golden.unwrap().0
+        );
+        assert_eq!(
+            hours.evening_golden.unwrap().1,
+            hours.evening_blue.unwrap().0
+        );
+    }
+
+    #[test]
+    fn photo_hours_at_high_latitudes() {
+        let at = |latitude, longitude, month| {
+            let date = NaiveDate::from_ymd_opt(2026, month, 21).unwrap();
+            let coords = Coordinates::new(latitude, longitude).unwrap();
+            let transit = calculate_solar_data(date, &coords).unwrap().transit;
+            (calculate_photo_hours(date, &coords).unwrap(), transit)
+        };
+
+        // Polar night in Tromsø: the sun peaks inside the golden band, which
+        // lasts until the transit in the morning and from it in the evening
+        let (hours, transit) = at(69.65, 18.96, 12);
+        assert_eq!(hours.morning_golden.unwrap().1, transit);
+        assert_eq!(hours.evening_golden.unwrap().0, transit);
+        assert!(hours.morning_blue.is_some() && hours.evening_blue.is_some());
+
+        // Midnight sun: it never drops to the blue band, and the golden hours
+        // run from and to the lowest sun, half a day from the transit
+        let (hours, transit) = at(69.65, 18.96, 6);
+        assert_eq!((hours.morning_blue, hours.evening_blue), (None, None));
+        assert_eq!(
+            hours.morning_golden.unwrap().0,
+            transit - Duration::hours(12)
+        );
+        assert_eq!(
+            hours.evening_golden.unwrap().1,
+            transit + Duration::hours(12)
+        );
+
+        // Deep polar night in Longyearbyen: the sun enters neither band
+        let (hours, _) = at(78.22, 15.65, 12);
+        assert_eq!(
+            hours,
+            PhotoHours {
+                morning_blue: None,
+                morning_golden: None,
+                evening_golden: None,
+                evening_blue: None,
+            }
+        );
+    }
+
+    #[test]
+    fn elevation_crossings_agree_with_the_events() {
+        let close = |a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>, what: &str| {
+            let error = (a.unwrap() - b.unwrap()).abs();
+            assert!(error < Duration::seconds(5), "{what}: {error}");
+        };
+        for month in [2, 6, 10] {
+            let date = NaiveDate::from_ymd_opt(2026, month, 3).unwrap();
+            let twilight = calculate_twilight_data(date, &madrid()).unwrap();
+            let (dawn, dusk) = elevation_crossings(date, &madrid(), -6.0).unwrap();
+            close(dawn, twilight.civil_dawn, "civil dawn");
+            close(dusk, twilight.civil_dusk, "civil dusk");
+
+            let data = calculate_solar_data(date, &madrid()).unwrap();
+            let (rise, set) = elevation_crossings(date, &madrid(), 90.0 - STANDARD_ZENITH).unwrap();
+            close(rise, data.sunrise, "sunrise");
+            close(set, data.sunset, "sunset");
+
+            // The sun is at the elevation it was asked for
+            let (morning, evening) = elevation_crossings(date, &madrid(), 15.0).unwrap();
+            for crossing in [morning.unwrap(), evening.unwrap()] {
+                let elevation = solar_elevation(crossing, &madrid()).unwrap();
+                assert!((elevation - 15.0).abs() < 0.01, "{crossing}: {elevation}");
+            }
+        }
+
+        // Out of reach at 38° N in winter
+        let date = NaiveDate::from_ymd_opt(2026, 12, 21).unwrap();
+        let coords = Coordinates::new(38.0, -0.5).unwrap();
+        assert_eq!(
+            elevation_crossings(date, &coords, 80.0).unwrap(),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn sun_is_overhead_twice_a_year_in_honolulu() {
+        let honolulu = Coordinates::new(21.3069, -157.8583).unwrap();
+        let transits = subsolar_transits(2026, &honolulu).unwrap();
+        assert_eq!(transits.len(), 2, "{transits:?}");
+        let (may, july) = (transits[0], transits[1]);
+        // Lahaina noon, published around May 26 and July 16
+        assert_eq!(may.0.month(), 5);
+        assert!((24..=28).contains(&may.0.day()), "{}", may.0);
+        assert_eq!(july.0.month(), 7);
+        assert!((14..=18).contains(&july.0.day()), "{}", july.0);
+        for (date, transit) in transits {
+            let data = calculate_solar_data(date, &honolulu).unwrap();
+            assert_eq!(transit, data.transit);
+            assert!(
+                data.transit_elevation > 89.7,
+                "{date}: {}",
+                data.transit_elevation
+            );
+        }
+
+        // At the equator it happens near the equinoxes
+        let equator = Coordinates::new(0.0, 0.0).unwrap();
+        let months: Vec<u32> = subsolar_transits(2026, &equator)
+            .unwrap()
+            .iter()
+            .map(|(date, _)| date.month())
+            .collect();
+        assert_eq!(months, [3, 9]);
+
+        // Never outside the tropics
+        assert_eq!(subsolar_transits(2026, &madrid()).unwrap(), []);
+        let south = Coordinates::new(-33.87, 151.21).unwrap();
+        assert_eq!(subsolar_transits(2026, &south).unwrap(), []);
+    }
+
+    #[test]
+    fn day_length_changes_fastest_at_the_equinoxes() {
+        let coords = Coordinates::new(38.0, -0.5).unwrap();
+        let change = |month, day| {
+            let date = NaiveDate::from_ymd_opt(2026, month, day).unwrap();
+            day_length_delta(date, &coords).unwrap().unwrap()
+        };
+
+        // Days stop growing at the June solstice and shrinking in December
+        for (month, day) in [(6, 21), (12, 21)] {
+            assert!(
+                change(month, day).abs() < Duration::seconds(5),
+                "{month}-{day}"
+            );
+        }
+        // Positive while the days get longer, negative after June
+        let (march, september) = (change(3, 20), change(9, 23));
+        assert!(
+            march > Duration::minutes(2) && march < Duration::minutes(3),
+            "{march}"
+        );
+        assert!(
+            september < -Duration::minutes(2) && september > -Duration::minutes(3),
+            "{september}"
+        );
+
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let fastest = (0..365)
+            .map(|day| start + Duration::days(day))
+            .max_by_key(|date| day_length_delta(*date, &coords).unwrap().unwrap())
+            .unwrap();
+        assert!(
+            (fastest - NaiveDate::from_ymd_opt(2026, 3, 20).unwrap())
+                .num_days()
+                .abs()
+                <= 5,
+            "{fastest}"
+        );
+
+        // No change into or out of polar day
+        let tromso = Coordinates::new(69.65, 18.96).unwrap();
+        let start = NaiveDate::from_ymd_opt(2026, 5, 1).unwrap();
+        let first = (0..60)
+            .map(|day| start + Duration::days(day))
+            .find(|date| day_length(*date, &tromso).unwrap() == DayLength::PolarDay)
+            .unwrap();
+        assert_eq!(day_length_delta(first, &tromso).unwrap(), None);
+        assert!(
+            day_length_delta(first.pred_opt().unwrap(), &tromso)
+                .unwrap()
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,77 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Entry points for `std::time::SystemTime`, such as file modification times.
+//!
+//! Instants before the Unix epoch fail with [`InteropError::BeforeEpoch`]
+//! instead of panicking in `duration_since`.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::error::InteropError;
+use crate::solar_clock::{SolarClock, SolarClockResult};
+
+/// Same instant as `st`, exact to the nanosecond.
+pub fn from_system_time(st: SystemTime) -> Result<DateTime<Utc>, InteropError> {
+    let since = st
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| InteropError::BeforeEpoch)?;
+    let since = Duration::from_std(since).map_err(|_| InteropError::OutOfRange)?;
+    DateTime::UNIX_EPOCH
+        .checked_add_signed(since)
+        .ok_or(InteropError::OutOfRange)
+}
+
+/// Same instant as `dt`, exact to the nanosecond.
+pub fn to_system_time<Tz: TimeZone>(dt: &DateTime<Tz>) -> Result<SystemTime, InteropError> {
+    let since = (dt.to_utc() - DateTime::UNIX_EPOCH)
+        .to_std()
+        .map_err(|_| InteropError::BeforeEpoch)?;
+    UNIX_EPOCH
+        .checked_add(since)
+        .ok_or(InteropError::OutOfRange)
+}
+
+/// Solar wall time of the result as a `SystemTime`, which has no offset.
+impl TryFrom<&SolarClockResult> for SystemTime {
+    type Error = InteropError;
+
+    fn try_from(result: &SolarClockResult) -> Result<Self, Self::Error> {
+        to_system_time(&result.solar_time)
+    }
+}
+
+impl SolarClock {
+    /// [`solar_time`](Self::solar_time) for a `SystemTime`.
+    pub fn solar_time_from_system(&self, st: SystemTime) -> Result<SolarClockResult, InteropError> {
+        Ok(self.solar_time(from_system_time(st)?)?)
+    }
+
+    /// Solar time at which the file at `path` was last modified. Failures to
+    /// evaluate the clock are reported as [`io::ErrorKind::InvalidData`].
+    pub fn annotate_mtime(&self, path: impl AsRef<Path>) -> io::Result<SolarClockResult> {
+        let modified = fs::metadata(path)?.modified()?;
+        self.solar_time_from_system(modified)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
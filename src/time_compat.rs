@@ -0,0 +1,166 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Interoperability with the `time` crate, behind the `time` feature.
+//!
+//! The solar clock works on chrono types; these functions convert at the
+//! boundary without losing nanoseconds. Offsets beyond chrono's ±24 h and
+//! instants beyond `time`'s supported years fail with
+//! [`InteropError::OutOfRange`].
+
+use chrono::{DateTime, FixedOffset, TimeZone};
+use time::{OffsetDateTime, UtcOffset};
+
+use crate::error::InteropError;
+use crate::interp::InterpolationMethod;
+use crate::solar_clock::{
+    Coordinates, SolarClock, SolarClockResult, SolarTargets, calculate_solar_clock,
+};
+
+/// Same instant and offset as `odt`, exact to the nanosecond.
+pub fn from_offset_date_time(odt: OffsetDateTime) -> Result<DateTime<FixedOffset>, InteropError> {
+    let offset =
+        FixedOffset::east_opt(odt.offset().whole_seconds()).ok_or(InteropError::OutOfRange)?;
+    let utc = DateTime::from_timestamp(odt.unix_timestamp(), odt.nanosecond())
+        .ok_or(InteropError::OutOfRange)?;
+    Ok(utc.with_timezone(&offset))
+}
+
+/// Same instant and offset as `dt`, exact to the nanosecond. Leap seconds,
+/// which `time` does not represent, fail.
+pub fn to_offset_date_time<Tz: TimeZone>(
+    dt: &DateTime<Tz>,
+) -> Result<OffsetDateTime, InteropError> {
+    let dt = dt.fixed_offset();
+    let offset = UtcOffset::from_whole_seconds(dt.offset().local_minus_utc())
+        .map_err(|_| InteropError::OutOfRange)?;
+    OffsetDateTime::from_unix_timestamp(dt.timestamp())
+        .and_then(|utc| utc.replace_nanosecond(dt.timestamp_subsec_nanos()))
+        .ok()
+        .and_then(|utc| utc.checked_to_offset(offset))
+        .ok_or(InteropError::OutOfRange)
+}
+
+/// Solar wall time of the result, in its solar offset.
+impl TryFrom<&SolarClockResult> for OffsetDateTime {
+    type Error = InteropError;
+
+    fn try_from(result: &SolarClockResult) -> Result<Self, Self::Error> {
+        to_offset_date_time(&result.solar_time)
+    }
+}
+
+/// [`calculate_solar_clock`] for an `OffsetDateTime`, returning the solar
+/// wall time in the solar offset.
+pub fn calculate_solar_clock_time(
+    odt: OffsetDateTime,
+    coords: &Coordinates,
+    targets: &SolarTargets,
+    interpolation: InterpolationMethod,
+) -> Result<OffsetDateTime, InteropError> {
+    let dt = from_offset_date_time(odt)?;
+    let result = calculate_solar_clock(dt, coords, targets, interpolation)?;
+    OffsetDateTime::try_from(&result)
+}
+
+impl SolarClock {
+    /// [`solar_time`](Self::solar_time) for an `OffsetDateTime`, returning
+    /// the solar wall time in this clock's offset.
+    pub fn solar_time_odt(&self, odt: OffsetDateTime) -> Result<OffsetDateTime, InteropError> {
+        let dt = from_offset_date_time(odt)?.to_utc();
+        let result = self.solar_time(dt)?;
+        OffsetDateTime::try_from(&result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, Utc};
+
+    use super::*;
+    use crate::solar_clock::{DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET};
+
+    /// 2026-02-03T12:40:00.123456789Z in `offset`.
+    fn odt(offset: UtcOffset) -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp_nanos(1_770_122_400_123_456_789)
+            .unwrap()
+            .to_offset(offset)
+    }
+
+    #[test]
+    fn conversions_keep_the_instant_and_the_offset() {
+        for (hours, minutes) in [(0, 0), (5, 30), (-3, -30), (23, 59), (-23, -59)] {
+            let offset = UtcOffset::from_hms(hours, minutes, 0).unwrap();
+            let original = odt(offset);
+
+            let dt = from_offset_date_time(original).unwrap();
+            assert_eq!(dt.timestamp_nanos_opt(), Some(1_770_122_400_123_456_789));
+            assert_eq!(dt.offset().local_minus_utc(), offset.whole_seconds());
+
+            let back = to_offset_date_time(&dt).unwrap();
+            assert_eq!(back, original);
+            assert_eq!(back.offset(), offset);
+        }
+        // Before the epoch the nanoseconds still count forwards
+        let dt = Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap()
+            + chrono::Duration::milliseconds(250);
+        let converted = to_offset_date_time(&dt).unwrap();
+        assert_eq!(converted.unix_timestamp_nanos(), -750_000_000);
+        assert_eq!(from_offset_date_time(converted).unwrap(), dt);
+    }
+
+    #[test]
+    fn unrepresentable_values_are_out_of_range() {
+        // time allows offsets up to 25:59:59, chrono under 24 hours
+        let offset = UtcOffset::from_hms(25, 0, 0).unwrap();
+        assert_eq!(
+            from_offset_date_time(odt(offset)),
+            Err(InteropError::OutOfRange)
+        );
+        // Leap seconds
+        let leap = NaiveDate::from_ymd_opt(2016, 12, 31)
+            .unwrap()
+            .and_hms_nano_opt(23, 59, 59, 1_500_000_000)
+            .unwrap()
+            .and_utc();
+        assert_eq!(to_offset_date_time(&leap), Err(InteropError::OutOfRange));
+        // And years beyond 9999
+        let far = Utc.with_ymd_and_hms(10_000, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(to_offset_date_time(&far), Err(InteropError::OutOfRange));
+    }
+
+    #[test]
+    fn solar_times_match_the_chrono_api() {
+        let clock = SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+        let civil = odt(UtcOffset::from_hms(-5, 0, 0).unwrap());
+        let expected = clock
+            .solar_time(from_offset_date_time(civil).unwrap().to_utc())
+            .unwrap();
+
+        let solar = clock.solar_time_odt(civil).unwrap();
+        assert_eq!(solar, to_offset_date_time(&expected.solar_time).unwrap());
+        assert_eq!(
+            solar.offset().whole_seconds(),
+            SOLAR_TIMEZONE_OFFSET.local_minus_utc()
+        );
+        assert_eq!(OffsetDateTime::try_from(&expected), Ok(solar));
+
+        let solar = calculate_solar_clock_time(civil, &DEFAULT_COORDINATES, &DEFAULT_TARGETS);
+        assert_eq!(solar, Ok(clock.solar_time_odt(civil).unwrap()));
+    }
+}
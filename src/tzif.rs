@@ -0,0 +1,212 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Minimal TZif version 2 (RFC 8536) writer for zones with a single
+//! designation and no leap seconds.
+
+use std::fmt;
+
+/// Designation of every local time type.
+const DESIGNATION: &str = "SOL";
+
+/// Largest UT offset in seconds, either way, that a version 2 footer TZ
+/// string can state: POSIX limits its hours to 24.
+pub const MAX_OFFSET: i32 = 24 * 3600;
+
+/// Zone that a TZif file cannot represent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// A UT offset in seconds beyond [`MAX_OFFSET`].
+    OffsetOutOfRange(i32),
+    /// More distinct offsets than the 256 local time types a transition
+    /// can index.
+    TooManyOffsets(usize),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OffsetOutOfRange(offset) => write!(
+                f,
+                "UT offset {offset} s is beyond the ±24 h a TZif file can state"
+            ),
+            Self::TooManyOffsets(count) => write!(
+                f,
+                "{count} distinct offsets exceed the 256 a TZif file holds"
+            ),
+        }
+    }
+}
+
+/// Zone whose UT offset changes at each transition.
+pub struct Zone {
+    /// Distinct UT offsets in seconds; the first one applies before the
+    /// first transition.
+    pub offsets: Vec<i32>,
+    /// Unix seconds of each transition, ascending, and the index into
+    /// `offsets` that applies from it on.
+    pub transitions: Vec<(i64, u8)>,
+}
+
+impl Zone {
+    /// Encodes the zone as a TZif file. The footer TZ string keeps the offset
+    /// of the last transition for all later instants.
+    pub fn encode(&self) -> Result<Vec<u8>, EncodeError> {
+        if self.offsets.len() > usize::from(u8::MAX) + 1 {
+            return Err(EncodeError::TooManyOffsets(self.offsets.len()));
+        }
+        if let Some(&offset) = self.offsets.iter().find(|o| o.abs() > MAX_OFFSET) {
+            return Err(EncodeError::OffsetOutOfRange(offset));
+        }
+
+        let mut out = Vec::new();
+        // Version 1 readers only see the transitions that fit in 32 bits
+        let v1: Vec<(i64, u8)> = self
+            .transitions
+            .iter()
+            .copied()
+            .filter(|&(t, _)| i32::try_from(t).is_ok())
+            .collect();
+        self.block(&mut out, &v1, 4);
+        self.block(&mut out, &self.transitions, 8);
+
+        let last = self
+            .transitions
+            .last()
+            .map_or(self.offsets[0], |&(_, index)| {
+                self.offsets[usize::from(index)]
+            });
+        out.extend_from_slice(format!("\n{}\n", posix_tz(last)).as_bytes());
+        Ok(out)
+    }
+
+    /// Header and data block with `width`-byte transition times.
+    fn block(&self, out: &mut Vec<u8>, transitions: &[(i64, u8)], width: usize) {
+        let count = |n: usize| {
+            u32::try_from(n)
+                .expect("TZif counts fit in 32 bits")
+                .to_be_bytes()
+        };
+        out.extend_from_slice(b"TZif2");
+        out.extend_from_slice(&[0; 15]);
+        out.extend_from_slice(&count(0)); // isutcnt
+        out.extend_from_slice(&count(0)); // isstdcnt
+        out.extend_from_slice(&count(0)); // leapcnt
+        out.extend_from_slice(&count(transitions.len()));
+        out.extend_from_slice(&count(self.offsets.len()));
+        out.extend_from_slice(&count(DESIGNATION.len() + 1));
+
+        for &(t, _) in transitions {
+            if width == 4 {
+                let t = i32::try_from(t).expect("version 1 times are filtered");
+                out.extend_from_slice(&t.to_be_bytes());
+            } else {
+                out.extend_from_slice(&t.to_be_bytes());
+            }
+        }
+        out.extend(transitions.iter().map(|&(_, index)| index));
+        for offset in &self.offsets {
+            out.extend_from_slice(&offset.to_be_bytes());
+            out.push(0); // isdst
+            out.push(0); // desigidx
+        }
+        out.extend_from_slice(DESIGNATION.as_bytes());
+        out.push(0);
+    }
+}
+
+/// POSIX TZ string for a fixed UT offset, such as `<SOL>-1:37:12` for
+/// +01:37:12 (POSIX counts positive west of Greenwich).
+fn posix_tz(offset: i32) -> String {
+    let sign = if offset > 0 { '-' } else { '+' };
+    let total = offset.unsigned_abs();
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    let time = match (m, s) {
+        (0, 0) => format!("{h}"),
+        (_, 0) => format!("{h}:{m:02}"),
+        _ => format!("{h}:{m:02}:{s:02}"),
+    };
+    format!("<{DESIGNATION}>{sign}{time}")
+}
+
+#[cfg(test)]
+mod tests {
+    use jiff::Timestamp;
+    use jiff::tz::TimeZone;
+
+    use super::*;
+
+    /// Offset in seconds of `zone`, as parsed back by jiff, at Unix second
+    /// `t`.
+    fn offset_at(zone: &TimeZone, t: i64) -> i32 {
+        zone.to_offset(Timestamp::from_second(t).unwrap()).seconds()
+    }
+
+    #[test]
+    fn round_trips_through_a_tzif_parser() {
+        let zone = Zone {
+            offsets: vec![3600, 5400, -4 * 3600 - 1],
+            transitions: vec![(1_000_000, 1), (2_000_000, 2), (5_000_000_000, 0)],
+        };
+        let parsed = TimeZone::tzif("SOL", &zone.encode().unwrap()).unwrap();
+
+        assert_eq!(offset_at(&parsed, 0), 3600);
+        assert_eq!(offset_at(&parsed, 999_999), 3600);
+        assert_eq!(offset_at(&parsed, 1_000_000), 5400);
+        assert_eq!(offset_at(&parsed, 1_999_999), 5400);
+        assert_eq!(offset_at(&parsed, 2_000_000), -4 * 3600 - 1);
+        // Beyond 32 bits only in the version 2 block, then the footer
+        assert_eq!(offset_at(&parsed, 4_999_999_999), -4 * 3600 - 1);
+        assert_eq!(offset_at(&parsed, 5_000_000_000), 3600);
+        assert_eq!(offset_at(&parsed, 9_000_000_000), 3600);
+    }
+
+    #[test]
+    fn offsets_are_bounded_to_a_day() {
+        for offset in [MAX_OFFSET, -MAX_OFFSET] {
+            let zone = Zone {
+                offsets: vec![0, offset],
+                transitions: vec![(0, 1)],
+            };
+            let parsed = TimeZone::tzif("SOL", &zone.encode().unwrap()).unwrap();
+            assert_eq!(offset_at(&parsed, 1), offset);
+        }
+
+        for offset in [MAX_OFFSET + 1, -MAX_OFFSET - 1, 25 * 3600 + 40 * 60] {
+            let zone = Zone {
+                offsets: vec![0, offset],
+                transitions: vec![(0, 1)],
+            };
+            assert_eq!(zone.encode(), Err(EncodeError::OffsetOutOfRange(offset)));
+        }
+
+        let zone = Zone {
+            offsets: (0..257).collect(),
+            transitions: Vec::new(),
+        };
+        assert_eq!(zone.encode(), Err(EncodeError::TooManyOffsets(257)));
+    }
+
+    #[test]
+    fn footer_states_the_last_offset() {
+        assert_eq!(posix_tz(3600), "<SOL>-1");
+        assert_eq!(posix_tz(5832), "<SOL>-1:37:12");
+        assert_eq!(posix_tz(-9000), "<SOL>+2:30");
+        assert_eq!(posix_tz(0), "<SOL>+0");
+    }
+}
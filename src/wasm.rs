@@ -0,0 +1,158 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! WebAssembly bindings for web pages, behind the `wasm` feature.
+//!
+//! Instants cross the boundary as `f64` milliseconds since the Unix epoch,
+//! as returned by `Date.now()`. Nothing here reads the system clock: the page
+//! supplies every timestamp.
+//!
+//! Build with `cargo build --release -p solar-clock --no-default-features
+//! --features wasm --target wasm32-unknown-unknown`, then run
+//! `wasm-bindgen --target web` on the resulting `solar_clock.wasm`.
+//!
+//! ```js
+//! const clock = new SolarClock(40.4168, -3.7038, "08:00,14:00,20:00");
+//! const solar = new Date(clock.solarTime(Date.now()));
+//! // Wall time on the solar clock, in the clock's offset
+//! const shifted = new Date(solar.getTime() + clock.offsetMinutes * 60000);
+//! console.log(shifted.toISOString().slice(11, 19));
+//! ```
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::builder::SolarClockBuilder;
+use crate::events::events_between;
+use crate::solar_clock::{SolarClock, SolarClockResult};
+
+/// Solar clock as seen from JavaScript, exported as `SolarClock`.
+#[wasm_bindgen(js_name = SolarClock)]
+pub struct WasmSolarClock {
+    clock: SolarClock,
+}
+
+#[wasm_bindgen(js_class = SolarClock)]
+impl WasmSolarClock {
+    /// Clock for the observer at `latitude`, `longitude`, with `targets` as
+    /// `SUNRISE,TRANSIT,SUNSET` wall times such as `08:00,14:00,20:00`, or
+    /// the defaults when omitted.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        latitude: f64,
+        longitude: f64,
+        targets: Option<String>,
+    ) -> Result<WasmSolarClock, JsError> {
+        let mut builder = SolarClockBuilder::new().coordinates(latitude, longitude);
+        if let Some(targets) = targets {
+            let [sunrise, transit, sunset] = parse_targets(&targets)?;
+            builder = builder.targets(sunrise, transit, sunset);
+        }
+        let clock = builder.build().map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(Self { clock })
+    }
+
+    /// UTC offset of the solar wall time, in minutes east of UTC.
+    #[wasm_bindgen(getter, js_name = offsetMinutes)]
+    pub fn offset_minutes(&self) -> i32 {
+        self.clock.offset().local_minus_utc() / 60
+    }
+
+    /// Solar instant of the civil instant `ms`, in milliseconds since the
+    /// epoch: the solar wall time read in [`offsetMinutes`](Self::offset_minutes).
+    #[wasm_bindgen(js_name = solarTime)]
+    pub fn solar_time(&self, ms: f64) -> Result<f64, JsError> {
+        let result = self.evaluate(ms)?;
+        Ok(to_millis(result.solar_time.with_timezone(&Utc)))
+    }
+
+    /// Milliseconds added to the civil instant `ms` to obtain the solar time.
+    pub fn delta(&self, ms: f64) -> Result<f64, JsError> {
+        Ok(self.evaluate(ms)?.delta * 1000.0)
+    }
+
+    /// Sunrise, transit and sunset of the `YYYY-MM-DD` date in the clock's
+    /// offset, as an array of `{kind, time}` objects with `time` in
+    /// milliseconds since the epoch. Events that do not occur are left out.
+    pub fn events(&self, date_iso: &str) -> Result<JsValue, JsError> {
+        let date = NaiveDate::parse_from_str(date_iso, "%Y-%m-%d").map_err(|_| {
+            JsError::new(&format!("invalid date '{date_iso}': expected YYYY-MM-DD"))
+        })?;
+        let start = (date.and_time(NaiveTime::MIN) - self.clock.offset()).and_utc();
+        let end = start + Duration::days(1) - Duration::nanoseconds(1);
+        let events = events_between(start, end, self.clock.coordinates())
+            .map(|event| {
+                event.map(|event| Event {
+                    kind: event.kind.name(),
+                    time: to_millis(event.time),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        serde_wasm_bindgen::to_value(&events).map_err(|e| JsError::new(&e.to_string()))
+    }
+}
+
+impl WasmSolarClock {
+    fn evaluate(&self, ms: f64) -> Result<SolarClockResult, JsError> {
+        let dt = from_millis(ms)?;
+        self.clock
+            .solar_time(dt)
+            .map_err(|e| JsError::new(&e.to_string()))
+    }
+}
+
+/// Event of [`WasmSolarClock::events`].
+#[derive(Serialize)]
+struct Event {
+    kind: &'static str,
+    time: f64,
+}
+
+fn parse_targets(value: &str) -> Result<[NaiveTime; 3], JsError> {
+    let invalid = || {
+        JsError::new(&format!(
+            "invalid targets '{value}': expected HH:MM,HH:MM,HH:MM"
+        ))
+    };
+    let times = value
+        .split(',')
+        .map(|part| {
+            let part = part.trim();
+            NaiveTime::parse_from_str(part, "%H:%M:%S")
+                .or_else(|_| NaiveTime::parse_from_str(part, "%H:%M"))
+                .map_err(|_| invalid())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    times.try_into().map_err(|_| invalid())
+}
+
+/// Instant of `ms` milliseconds since the epoch, to the microsecond.
+fn from_millis(ms: f64) -> Result<DateTime<Utc>, JsError> {
+    let micros = (ms * 1000.0).round();
+    if !micros.is_finite() || micros.abs() > i64::MAX as f64 {
+        return Err(JsError::new(&format!("invalid timestamp {ms}")));
+    }
+    DateTime::from_timestamp_micros(micros as i64)
+        .ok_or_else(|| JsError::new(&format!("invalid timestamp {ms}")))
+}
+
+fn to_millis(dt: DateTime<Utc>) -> f64 {
+    dt.timestamp_micros() as f64 / 1000.0
+}
@@ -0,0 +1,806 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Command-line behaviour of the `solar-clock-rs` binary.
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use chrono::{DateTime, SubsecRound, Utc};
+use solar_clock_rs::SolarClock;
+use solar_clock_rs::solar_clock::{DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET};
+
+/// The binary, isolated from the user's configuration file and
+/// `SOLAR_CLOCK_*` variables.
+fn command() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_solar-clock-rs"));
+    command.env("XDG_CONFIG_HOME", env!("CARGO_TARGET_TMPDIR"));
+    for (variable, _) in std::env::vars_os() {
+        if variable.to_string_lossy().starts_with("SOLAR_CLOCK_") {
+            command.env_remove(variable);
+        }
+    }
+    command
+}
+
+/// Runs the binary with `args`.
+fn run(args: &[&str]) -> Output {
+    command().args(args).output().expect("the binary runs")
+}
+
+/// Runs the binary with `args`, writing `input` to its standard input.
+fn run_with_stdin(args: &[&str], input: &str) -> Output {
+    let mut child = command()
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("the binary runs");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Runs the binary with `args` and `--cached`, keeping the cache in `cache`,
+/// and returns its standard output and how many days of solar data it
+/// computed, from its trace events.
+fn cached_run(cache: &std::path::Path, args: &[&str]) -> (String, usize) {
+    let output = command()
+        .env("XDG_CACHE_HOME", cache)
+        .args(["--cached", "-vvv"])
+        .args(args)
+        .output()
+        .expect("the binary runs");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{args:?}: {stderr}");
+    let days = stderr.matches("computing the solar data").count();
+    (String::from_utf8(output.stdout).unwrap(), days)
+}
+
+/// Empty directory for the cache of one test.
+fn cache_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+/// Standard output of a successful run.
+fn stdout(args: &[&str]) -> String {
+    let output = run(args);
+    assert!(
+        output.status.success(),
+        "{args:?}: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn lat_and_lon_move_the_observer() {
+    let output = run(&["--lat", "51.5", "--lon", "-0.12"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.starts_with("Calculating solar data for coordinates (51.5, -0.12)"),
+        "{stdout}"
+    );
+    // Inline values work too
+    let inline = run(&["--lat=51.5", "--lon=-0.12"]);
+    assert!(
+        String::from_utf8(inline.stdout)
+            .unwrap()
+            .starts_with("Calculating solar data for coordinates (51.5, -0.12)")
+    );
+
+    for (args, message) in [
+        (
+            ["--lat", "91"],
+            "error: latitude 91 is outside the range [-90, 90]",
+        ),
+        (
+            ["--lon", "-180.5"],
+            "error: longitude -180.5 is outside the range",
+        ),
+        (
+            ["--lat", "north"],
+            "error: invalid value 'north' for '--lat'",
+        ),
+    ] {
+        let output = run(&args);
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(output.stdout.is_empty());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.starts_with(message), "{stderr}");
+    }
+}
+
+#[test]
+fn solar_offset_moves_the_targets() {
+    let run_stdout = |args: &[&str]| {
+        let output = run(args);
+        assert!(output.status.success(), "{args:?}");
+        String::from_utf8(output.stdout).unwrap()
+    };
+    let out = run_stdout(&["--at", "2026-02-03T12:00:00Z"]);
+    let transit = out
+        .lines()
+        .find_map(|line| line.strip_prefix("Solar noon: "))
+        .unwrap_or_else(|| panic!("{out}"))
+        .replacen(' ', "T", 1)
+        .replace(" UTC", "Z");
+
+    // The transit reads 14:00 in every offset, e.g. at 06:00 UTC in +08:00
+    for offset in ["+08:00", "+05:30", "+05:45", "-03:30"] {
+        let out = run_stdout(&["--at", &transit, "--solar-offset", offset]);
+        let expected = format!("Solar time: 2026-02-03 14:00:00 {offset}");
+        assert!(out.contains(&expected), "{out}");
+    }
+
+    assert_eq!(run(&["--solar-offset", "+8"]).status.code(), Some(2));
+}
+
+/// `events` at the reference date, with the default and the `accurate`
+/// solar position algorithms, which differ by a second or two.
+const EVENTS_2026_02_03: &str = if cfg!(feature = "accurate") {
+    concat!(
+        "date              2026-02-03\n",
+        "astronomical dawn 2026-02-03T05:49:12Z 2026-02-03T06:49:12+01:00\n",
+        "nautical dawn     2026-02-03T06:21:07Z 2026-02-03T07:21:07+01:00\n",
+        "civil dawn        2026-02-03T06:53:36Z 2026-02-03T07:53:36+01:00\n",
+        "sunrise           2026-02-03T07:22:17Z 2026-02-03T08:22:17+01:00\n",
+        "  azimuth         111.1°\n",
+        "transit           2026-02-03T12:28:35Z 2026-02-03T13:28:35+01:00\n",
+        "  elevation       33.2°\n",
+        "sunset            2026-02-03T17:35:22Z 2026-02-03T18:35:22+01:00\n",
+        "  azimuth         249.0°\n",
+        "civil dusk        2026-02-03T18:04:03Z 2026-02-03T19:04:03+01:00\n",
+        "nautical dusk     2026-02-03T18:36:33Z 2026-02-03T19:36:33+01:00\n",
+        "astronomical dusk 2026-02-03T19:08:30Z 2026-02-03T20:08:30+01:00\n",
+        "equation of time  -13m47s\n",
+    )
+} else {
+    concat!(
+        "date              2026-02-03\n",
+        "astronomical dawn 2026-02-03T05:49:13Z 2026-02-03T06:49:13+01:00\n",
+        "nautical dawn     2026-02-03T06:21:08Z 2026-02-03T07:21:08+01:00\n",
+        "civil dawn        2026-02-03T06:53:38Z 2026-02-03T07:53:38+01:00\n",
+        "sunrise           2026-02-03T07:22:18Z 2026-02-03T08:22:18+01:00\n",
+        "  azimuth         111.1°\n",
+        "transit           2026-02-03T12:28:36Z 2026-02-03T13:28:36+01:00\n",
+        "  elevation       33.2°\n",
+        "sunset            2026-02-03T17:35:24Z 2026-02-03T18:35:24+01:00\n",
+        "  azimuth         249.0°\n",
+        "civil dusk        2026-02-03T18:04:05Z 2026-02-03T19:04:05+01:00\n",
+        "nautical dusk     2026-02-03T18:36:36Z 2026-02-03T19:36:36+01:00\n",
+        "astronomical dusk 2026-02-03T19:08:32Z 2026-02-03T20:08:32+01:00\n",
+        "equation of time  -13m48s\n",
+    )
+};
+
+#[test]
+fn events_prints_a_stable_format() {
+    assert_eq!(
+        stdout(&["events", "--date", "2026-02-03"]),
+        EVENTS_2026_02_03
+    );
+
+    // Polar night in Longyearbyen: absent events read none, with a note
+    let out = stdout(&[
+        "events",
+        "--date",
+        "2026-12-21",
+        "--lat",
+        "78.22",
+        "--lon",
+        "15.65",
+    ]);
+    let lines: Vec<&str> = out.lines().collect();
+    assert!(lines.contains(&"sunrise none"), "{out}");
+    assert!(lines.contains(&"sunset  none"), "{out}");
+    assert_eq!(
+        lines.last(),
+        Some(&"note    the sun does not rise or set on this date (polar day or night)")
+    );
+}
+
+#[test]
+fn next_rolls_over_to_the_next_solar_day() {
+    let next = |time: &str, after: &str| -> (String, String) {
+        let out = stdout(&["next", time, "--after", after]);
+        let field = |name: &str| {
+            out.lines()
+                .find_map(|line| line.strip_prefix(name))
+                .unwrap()
+                .trim()
+                .to_owned()
+        };
+        (field("solar"), field("civil"))
+    };
+
+    // Transit reads 14:00 on its own date, then on the next
+    let date = chrono::NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+    let transit = solar_clock_rs::spa::calculate_solar_data(date, &DEFAULT_COORDINATES)
+        .unwrap()
+        .transit
+        .round_subsecs(0)
+        .with_timezone(&SOLAR_TIMEZONE_OFFSET)
+        .to_rfc3339();
+    assert_eq!(
+        next("14:00", "2026-02-03T12:00:00Z"),
+        ("2026-02-03T14:00:00+01:00".to_owned(), transit)
+    );
+    let (solar, _) = next("14:00", "2026-02-03T13:00:00Z");
+    assert_eq!(solar, "2026-02-04T14:00:00+01:00");
+
+    // Midnight belongs to the next solar date, even before civil midnight
+    let (solar, civil) = next("00:00", "2026-02-03T20:00:00Z");
+    assert_eq!(solar, "2026-02-04T00:00:00+01:00");
+    assert!(civil.starts_with("2026-02-03T23:"), "{civil}");
+    let (solar, _) = next("00:00:00", "2026-02-03T23:30:00Z");
+    assert_eq!(solar, "2026-02-05T00:00:00+01:00");
+    let (solar, _) = next("23:59:59", "2026-02-03T20:00:00Z");
+    assert_eq!(solar, "2026-02-03T23:59:59+01:00");
+
+    assert_eq!(run(&["next", "25:00"]).status.code(), Some(2));
+}
+
+#[test]
+fn format_prints_exactly_one_line() {
+    let format = |template: &str| stdout(&["--at", "2026-02-03T12:00:00Z", "--format", template]);
+
+    assert_eq!(format("%H:%M:%S").as_bytes(), b"13:25:57\n");
+    assert_eq!(
+        format("%Y-%m-%d %H:%M:%S%.3f %z|{delta_s}|{delta_hms}"),
+        "2026-02-03 13:25:57.859 +0100|+1557.859|+25m58s\n"
+    );
+    assert_eq!(format("{epoch} {eot}"), "1770121557.859 -13m48s\n");
+
+    // Invalid templates are rejected before anything is printed
+    for template in ["%Q", "{delta}", "{delta_s"] {
+        let output = run(&["--at", "2026-02-03T12:00:00Z", "--format", template]);
+        assert_eq!(output.status.code(), Some(2), "{template}");
+        assert!(output.stdout.is_empty(), "{template}");
+    }
+}
+
+#[test]
+fn stdin_evaluates_each_line_across_days() {
+    let clock = SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+    // Months apart, so the model cannot serve them all from one window
+    let inputs = [
+        "2026-02-03T12:00:00Z",
+        "2026-06-21T05:30:00+02:00",
+        "2026-11-30T23:59:59.5Z",
+    ];
+    let input = format!(
+        "{}\n\n  \nyesterday\n{}\n2026-02-30T00:00:00Z\n{}\n",
+        inputs[0], inputs[1], inputs[2]
+    );
+    let output = run_with_stdin(&["--stdin"], &input);
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), inputs.len(), "{stdout}");
+    for (line, input) in lines.iter().zip(inputs) {
+        let instant: DateTime<Utc> = DateTime::parse_from_rfc3339(input).unwrap().into();
+        let result = clock.solar_time(instant).unwrap();
+        assert_eq!(
+            *line,
+            format!(
+                "{input} {} {:+.3}",
+                result.solar_time.to_rfc3339(),
+                result.delta
+            )
+        );
+    }
+
+    // Bad lines are reported by number and the run goes on
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let reported: Vec<&str> = stderr
+        .lines()
+        .filter_map(|line| line.split(':').next())
+        .collect();
+    assert_eq!(reported, ["line 4", "line 6"], "{stderr}");
+    assert_eq!(output.status.code(), Some(2));
+
+    let output = run_with_stdin(&["--stdin"], &format!("{}\n", inputs[0]));
+    assert!(output.status.success());
+}
+
+#[test]
+fn epoch_input_round_trips_to_the_millisecond() {
+    // 2026-02-03T12:00:00Z
+    let plain = stdout(&["--at", "2026-02-03T12:00:00.25Z"]);
+    assert_eq!(stdout(&["--at", "@1770120000.25"]), plain);
+    assert_eq!(stdout(&["--epoch", "1770120000.25"]), plain);
+
+    let clock = SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+    for (epoch, input) in [
+        ("1770120000.001", "2026-02-03T12:00:00.001Z"),
+        ("1770120000.999", "2026-02-03T12:00:00.999Z"),
+        ("1770076800", "2026-02-03T00:00:00Z"),
+    ] {
+        let out = stdout(&["--epoch", epoch, "--format", "{epoch}"]);
+        let input: DateTime<Utc> = input.parse().unwrap();
+        let solar = clock.solar_time(input).unwrap().solar_time;
+        let millis = solar.timestamp_millis();
+        assert_eq!(out, format!("{}.{:03}\n", millis / 1000, millis % 1000));
+
+        // Solar instant minus civil instant is the delta
+        let seconds: f64 = out.trim().parse().unwrap();
+        let civil: f64 = epoch.parse().unwrap();
+        let delta: f64 = stdout(&["--epoch", epoch, "--format", "{delta_s}"])
+            .trim()
+            .parse()
+            .unwrap();
+        assert!((seconds - civil - delta).abs() <= 0.002, "{epoch}");
+    }
+
+    for bad in ["@1.1234567891", "@12:00", "@"] {
+        assert_eq!(run(&["--at", bad]).status.code(), Some(2), "{bad}");
+    }
+    assert_eq!(run(&["--epoch", "1e9"]).status.code(), Some(2));
+}
+
+#[test]
+fn missing_polar_events_exit_with_no_solar_data() {
+    let polar = ["--lat", "89.9", "--at", "2025-12-15T12:00:00Z"];
+
+    // No sunrise for months: nothing to wait for
+    let output = run(&[&["sleep-until", "sunrise"], &polar[..]].concat());
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: sunrise does not occur in the next days at this location\n"
+    );
+
+    // The transits are still reached
+    let output = run(&[&["sleep-until", "transit"], &polar[..]].concat());
+    assert!(output.status.success());
+    assert!(
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .starts_with("sleeping until transit at 2025-12-15T13:"),
+    );
+
+    assert_eq!(run(&["--lat", "90.1"]).status.code(), Some(2));
+}
+
+#[test]
+fn clock_exits_with_no_solar_data_in_the_polar_night() {
+    let polar = ["--lat", "89.9", "--at", "2025-12-15T12:00:00Z", "-q"];
+    let output = run(&polar);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: solar clock unavailable: no sunrise or sunset within 15 day(s) of the date \
+         (polar day or night)\n"
+    );
+
+    // Unless it runs on the transits alone
+    let output = run(&[&polar[..], &["--polar-fallback"]].concat());
+    assert!(output.status.success());
+}
+
+#[test]
+fn verbosity_logs_the_anchors_to_stderr() {
+    let at = ["--at", "2026-02-03T12:00:00Z", "-q"];
+    let log = |flag: &str| {
+        let output = run(&[&at[..], &[flag]].concat());
+        assert!(output.status.success());
+        assert_eq!(output.stdout, stdout(&at).as_bytes(), "{flag}");
+        String::from_utf8(output.stderr).unwrap()
+    };
+    // Names of the logged anchors, without the colour codes
+    let anchors = |log: &str| -> Vec<String> {
+        let mut plain = String::new();
+        let mut chars = log.chars();
+        while let Some(c) = chars.next() {
+            if c == '\x1b' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                plain.push(c);
+            }
+        }
+        plain
+            .lines()
+            .filter(|line| line.contains("DEBUG") && line.contains(" anchor "))
+            .filter_map(|line| line.split(" name=").nth(1)?.split(' ').next())
+            .map(str::to_owned)
+            .collect()
+    };
+
+    let debug = log("-vv");
+    assert_eq!(
+        anchors(&debug),
+        ["sunrise", "transit", "sunset"].repeat(3),
+        "{debug}"
+    );
+    assert!(debug.contains("resolved configuration"));
+
+    let info = log("-v");
+    assert!(info.contains("resolved configuration"), "{info}");
+    assert!(anchors(&info).is_empty());
+    assert_eq!(log("--quiet"), "");
+}
+
+#[test]
+fn waybar_prints_one_json_object() {
+    let out = stdout(&["--output", "waybar", "--at", "2026-02-03T12:00:00Z"]);
+    assert_eq!(out.lines().count(), 1, "{out}");
+    let object: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(
+        object["text"].as_str(),
+        Some(stdout(&["--at", "2026-02-03T12:00:00Z", "-q"]).trim_end())
+    );
+    assert!(object["tooltip"].as_str().unwrap().starts_with("Δ +"));
+    assert_eq!(object["class"], "day");
+}
+
+#[test]
+fn verbose_prints_the_anchor_table() {
+    let out = stdout(&["--at", "2026-02-03T12:00:00Z", "-v"]);
+    let table: Vec<&str> = out
+        .lines()
+        .skip_while(|line| *line != "Anchors:")
+        .skip(1)
+        .collect();
+    assert!(table[0].split_whitespace().eq([
+        "event", "date", "real", "(UTC)", "target", "(UTC)", "delta", "(s)"
+    ]));
+    assert_eq!(table.len(), 10, "{out}");
+
+    let clock = SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+    let date = chrono::NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+    for (row, anchor) in table[1..].iter().zip(clock.model_anchors(date).unwrap()) {
+        let fields: Vec<&str> = row.split_whitespace().collect();
+        assert_eq!(fields[0], anchor.name);
+        assert_eq!(fields[1], anchor.date.to_string());
+        assert_eq!(fields[4], format!("{:+.3}", anchor.delta()));
+    }
+}
+
+#[test]
+fn validate_fails_when_minutes_are_skipped_or_repeated() {
+    let output = run(&["validate", "--date", "2026-02-03"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "2026-02-03 ok: every solar minute is shown exactly once\n"
+    );
+
+    // A solar offset 14 hours off skips minutes in February and repeats
+    // them in June
+    for (date, offset) in [("2026-02-03", "+14:00"), ("2026-06-21", "-14:00")] {
+        let output = run(&["validate", "--date", date, "--solar-offset", offset]);
+        assert_eq!(output.status.code(), Some(2), "{offset}");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(!stdout.is_empty());
+        for line in stdout.lines() {
+            let issue = line.strip_prefix(&format!("{date} ")).unwrap();
+            assert!(
+                issue.starts_with("gap ") || issue.starts_with("overlap "),
+                "{line}"
+            );
+        }
+    }
+
+    assert_eq!(
+        run(&["validate", "--date", "2026-02-30"]).status.code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn interp_selects_the_interpolation() {
+    let delta =
+        |at: &str, method: &str| stdout(&["--at", at, "--interp", method, "--format", "{delta_s}"]);
+    // The transit of the reference date is an anchor: every method hits it
+    let date = chrono::NaiveDate::from_ymd_opt(2026, 2, 3).unwrap();
+    let transit = solar_clock_rs::spa::calculate_solar_data(date, &DEFAULT_COORDINATES)
+        .unwrap()
+        .transit
+        .to_rfc3339();
+    let anchor = delta(&transit, "pchip");
+    assert_eq!(delta(&transit, "linear"), anchor);
+    assert_eq!(delta(&transit, "akima"), anchor);
+    // Between anchors they part ways
+    assert_ne!(
+        delta("2026-02-03T10:00:00Z", "linear"),
+        delta("2026-02-03T10:00:00Z", "pchip")
+    );
+
+    assert_eq!(run(&["--interp", "cubic"]).status.code(), Some(2));
+}
+
+#[test]
+fn range_reports_the_day_length() {
+    let out = stdout(&[
+        "range",
+        "--from",
+        "2026-06-20",
+        "--to",
+        "2026-06-21",
+        "--json",
+    ]);
+    let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let days = json["days"].as_array().unwrap();
+    assert_eq!(days.len(), 2);
+    let length = days[1]["day_length"].as_f64().unwrap();
+    let change = days[1]["day_length_change"].as_f64().unwrap();
+    // Madrid's longest day, about 15 hours and barely growing
+    assert!((length - 15.0 * 3600.0).abs() < 300.0, "{length}");
+    assert!(change.abs() < 10.0, "{change}");
+    assert_eq!(days[1]["polar"], serde_json::Value::Null);
+
+    // Midnight sun in Tromsø
+    let out = stdout(&[
+        "range",
+        "--from",
+        "2026-06-21",
+        "--to",
+        "2026-06-21",
+        "--lat",
+        "69.65",
+        "--lon",
+        "18.96",
+    ]);
+    assert!(out.contains(" daylength polar-day change n/a "), "{out}");
+    let out = stdout(&[
+        "range",
+        "--from",
+        "2026-06-21",
+        "--to",
+        "2026-06-21",
+        "--lat",
+        "69.65",
+        "--lon",
+        "18.96",
+        "--json",
+    ]);
+    let json: serde_json::Value = serde_json::from_str(&out).unwrap();
+    let day = &json["days"][0];
+    assert_eq!(day["day_length"].as_f64(), Some(86400.0));
+    assert_eq!(day["polar"], "day");
+    assert_eq!(day["day_length_change"], serde_json::Value::Null);
+}
+
+#[test]
+fn events_photo_adds_the_golden_and_blue_hours() {
+    let out = stdout(&["events", "--date", "2026-02-03", "--photo"]);
+    let (plain, photo) = out.split_at(EVENTS_2026_02_03.len());
+    assert_eq!(plain, EVENTS_2026_02_03);
+    let names: Vec<&str> = photo
+        .lines()
+        .map(|line| line.split("  ").next().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "morning blue",
+            "morning golden",
+            "evening golden",
+            "evening blue"
+        ]
+    );
+    // Each band starts in the solar offset where the previous one ended
+    let fields: Vec<Vec<&str>> = photo
+        .lines()
+        .map(|line| line.split_whitespace().skip(2).collect())
+        .collect();
+    assert_eq!(fields[0][1], fields[1][0]);
+    assert_eq!(fields[2][1], fields[3][0]);
+    assert!(fields.iter().flatten().all(|t| t.ends_with("+01:00")));
+}
+
+#[test]
+fn seasons_prints_the_published_instants() {
+    let out = stdout(&["seasons", "2026"]);
+    let lines: Vec<&str> = out.lines().collect();
+    let expected = [
+        ("march equinox", "2026-03-20T14:46:00Z"),
+        ("june solstice", "2026-06-21T08:24:00Z"),
+        ("september equinox", "2026-09-23T00:05:00Z"),
+        ("december solstice", "2026-12-21T20:50:00Z"),
+    ];
+    assert_eq!(lines.len(), expected.len(), "{out}");
+    for (line, (name, published)) in lines.iter().zip(expected) {
+        assert!(line.starts_with(name), "{line}");
+        // The UTC instant, then the same one in the solar offset
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let utc: DateTime<Utc> = fields[fields.len() - 2].parse().unwrap();
+        let solar = DateTime::parse_from_rfc3339(fields[fields.len() - 1]).unwrap();
+        assert_eq!(solar, utc);
+        // Published to the minute
+        let published: DateTime<Utc> = published.parse().unwrap();
+        assert!((utc - published).num_seconds().abs() < 90, "{line}");
+    }
+
+    let output = run(&["seasons", "3001"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn default_output_reports_the_daylight_change() {
+    let daylight = |args: &[&str]| -> Option<String> {
+        stdout(args)
+            .lines()
+            .find_map(|line| line.strip_prefix("Daylight: "))
+            .map(str::to_owned)
+    };
+    let spring = daylight(&["--at", "2026-03-20T12:00:00Z"]).unwrap();
+    assert!(
+        spring.starts_with("2m") && spring.ends_with("s longer than yesterday"),
+        "{spring}"
+    );
+    let autumn = daylight(&["--at", "2026-09-23T12:00:00Z"]).unwrap();
+    assert!(autumn.ends_with(" shorter than yesterday"), "{autumn}");
+    // Nothing to compare during the midnight sun
+    let polar = [
+        "--at",
+        "2026-06-21T12:00:00Z",
+        "--lat",
+        "69.65",
+        "--lon",
+        "18.96",
+        "--polar-fallback",
+    ];
+    assert_eq!(daylight(&polar), None);
+}
+
+#[test]
+fn tzif_follows_the_solar_clock() {
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("solar.tzif");
+    let path_arg = path.to_str().unwrap();
+    stdout(&[
+        "tzif",
+        "--from",
+        "2026-02-03",
+        "--to",
+        "2026-02-04",
+        "--granularity",
+        "60",
+        path_arg,
+    ]);
+    let zone = jiff::tz::TimeZone::tzif("solar", &std::fs::read(&path).unwrap()).unwrap();
+
+    let clock = SolarClock::new(DEFAULT_COORDINATES, DEFAULT_TARGETS, SOLAR_TIMEZONE_OFFSET);
+    for instant in [
+        "2026-02-03T00:30:00Z",
+        "2026-02-03T07:22:00Z",
+        "2026-02-03T12:00:00Z",
+        "2026-02-04T17:35:00Z",
+    ] {
+        let instant: DateTime<Utc> = instant.parse().unwrap();
+        let timestamp = jiff::Timestamp::from_second(instant.timestamp()).unwrap();
+        let offset = zone.to_offset(timestamp).seconds();
+        let solar = 3600.0 + clock.solar_time(instant).unwrap().delta;
+        // Within half the granularity
+        assert!(
+            (f64::from(offset) - solar).abs() <= 30.0,
+            "{instant}: {offset} vs {solar}"
+        );
+    }
+}
+
+#[test]
+fn publish_checks_the_broker_url() {
+    let code = |args: &[&str]| run(args).status.code();
+    assert_eq!(code(&["publish"]), Some(2));
+    assert_eq!(code(&["publish", "--broker", "http://localhost"]), Some(2));
+    assert_eq!(
+        code(&[
+            "publish",
+            "--broker",
+            "mqtt://localhost",
+            "--ca-file",
+            "ca.pem"
+        ]),
+        Some(2),
+        "--ca-file needs TLS"
+    );
+    // Without the feature a TLS URL is refused rather than sent in the clear
+    if !cfg!(feature = "tls") {
+        assert_eq!(code(&["publish", "--broker", "mqtts://localhost"]), Some(2));
+    }
+}
+
+#[test]
+fn cached_model_skips_the_solar_position_algorithm() {
+    let cache = cache_dir("cache-warm");
+    for targets in ["08:00,14:00,20:00", "07:00,13:30,20:30"] {
+        let args = [
+            "--quiet",
+            "--targets",
+            targets,
+            "--at",
+            "2026-06-21T12:00:00Z",
+        ];
+        let (cold, days) = cached_run(&cache, &args);
+        assert!(days > 0, "the first run builds the model");
+        // Neither the model, the coverage check nor the output computes the
+        // sun's position again
+        let (warm, days) = cached_run(&cache, &args);
+        assert_eq!(days, 0, "the second run loads the model");
+        assert_eq!(warm, cold);
+    }
+}
+
+#[test]
+fn cached_model_of_another_day_is_replaced() {
+    let cache = cache_dir("cache-stale");
+    let files = || -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(cache.join("solar-clock"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        names
+    };
+
+    cached_run(&cache, &["--quiet", "--at", "2026-06-21T12:00:00Z"]);
+    assert_eq!(files().len(), 1);
+    assert!(files()[0].starts_with("2026-06-21_"), "{:?}", files());
+
+    // Yesterday's model does not cover today: it is rebuilt and replaced
+    let (_, days) = cached_run(&cache, &["--quiet", "--at", "2026-06-22T12:00:00Z"]);
+    assert!(days > 0);
+    assert_eq!(files().len(), 1);
+    assert!(files()[0].starts_with("2026-06-22_"), "{:?}", files());
+    let (_, days) = cached_run(&cache, &["--quiet", "--at", "2026-06-22T18:00:00Z"]);
+    assert_eq!(days, 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn night_light_uses_the_requested_instant() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // A stand-in for wlsunset that prints its arguments
+    let bin = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("night-light-bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    let tool = bin.join("wlsunset");
+    std::fs::write(&tool, "#!/bin/sh\necho \"$@\"\n").unwrap();
+    std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    for at in ["2026-06-21T12:00:00Z", "2026-12-21T12:00:00Z"] {
+        let output = command()
+            .env("PATH", &bin)
+            .args(["--at", at, "night-light", "wlsunset"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{at}");
+        let printed = stdout(&["--at", at, "--output", "redshift"]);
+        let times: Vec<&str> = printed
+            .lines()
+            .map(|line| line.split_once('=').unwrap().1)
+            .collect();
+        assert_eq!(
+            String::from_utf8(output.stdout).unwrap(),
+            format!("-S {} -s {}\n", times[0], times[1]),
+            "{at}"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `publish` against a mock MQTT broker: the topic layout, the retained
+//! flags and the payload schema.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use solar_clock_rs::{Coordinates, EventKind, events_between};
+
+/// A PUBLISH packet as received by the broker.
+struct Publish {
+    topic: String,
+    retain: bool,
+    qos: u8,
+    payload: Value,
+}
+
+/// Kills the publisher when the test ends, even on failure.
+struct Publisher(Child);
+
+impl Drop for Publisher {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Starts `publish` at (`latitude`, `longitude`), isolated from the user's
+/// configuration file and `SOLAR_CLOCK_*` variables.
+fn publisher(broker: &str, latitude: f64, longitude: f64, extra: &[&str]) -> Publisher {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_solar-clock-rs"));
+    for variable in [
+        "SOLAR_CLOCK_LAT",
+        "SOLAR_CLOCK_LON",
+        "SOLAR_CLOCK_TARGETS",
+        "SOLAR_CLOCK_MIDNIGHT",
+        "SOLAR_CLOCK_OFFSET",
+    ] {
+        command.env_remove(variable);
+    }
+    let child = command
+        .env("XDG_CONFIG_HOME", env!("CARGO_TARGET_TMPDIR"))
+        .args([
+            "--lat",
+            &latitude.to_string(),
+            "--lon",
+            &longitude.to_string(),
+        ])
+        .args([
+            "publish",
+            "--broker",
+            broker,
+            "--topic",
+            "t",
+            "--interval",
+            "60",
+        ])
+        .args(extra)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("the binary runs");
+    Publisher(child)
+}
+
+fn read_packet(stream: &mut impl Read) -> (u8, Vec<u8>) {
+    let mut byte = [0u8];
+    stream.read_exact(&mut byte).expect("a packet");
+    let header = byte[0];
+    let (mut len, mut shift) = (0usize, 0);
+    loop {
+        stream.read_exact(&mut byte).unwrap();
+        len |= usize::from(byte[0] & 0x7F) << shift;
+        shift += 7;
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+    }
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body).unwrap();
+    (header, body)
+}
+
+/// Accepts the CONNECT of a clean session.
+fn accept_session(stream: &mut (impl Read + Write)) {
+    let (header, body) = read_packet(stream);
+    assert_eq!(header, 0x10, "CONNECT");
+    assert_eq!(&body[..7], b"\0\x04MQTT\x04", "MQTT 3.1.1");
+    assert_eq!(body[7] & 0x02, 0x02, "clean session");
+    stream.write_all(&[0x20, 2, 0, 0]).unwrap();
+}
+
+/// Receives a PUBLISH and acknowledges it.
+fn receive(stream: &mut (impl Read + Write)) -> Publish {
+    let (header, body) = read_packet(stream);
+    assert_eq!(header & 0xF0, 0x30, "PUBLISH");
+    let topic_len = usize::from(u16::from_be_bytes([body[0], body[1]]));
+    let topic = String::from_utf8(body[2..2 + topic_len].to_vec()).unwrap();
+    let id = &body[2 + topic_len..4 + topic_len];
+    stream.write_all(&[0x40, 2, id[0], id[1]]).unwrap();
+    Publish {
+        topic,
+        retain: header & 0x01 == 1,
+        qos: (header >> 1) & 0x03,
+        payload: serde_json::from_slice(&body[4 + topic_len..]).expect("a JSON payload"),
+    }
+}
+
+fn time(value: &Value) -> DateTime<Utc> {
+    let text = value.as_str().expect("an RFC 3339 string");
+    DateTime::parse_from_rfc3339(text).unwrap().into()
+}
+
+/// A longitude on the equator whose solar transit falls at `target`, found
+/// by moving the meridian 1 degree per 240 seconds of error.
+fn longitude_of_transit(target: DateTime<Utc>) -> f64 {
+    let hours = (target - target.date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .num_milliseconds() as f64
+        / 3_600_000.0;
+    let mut longitude = (12.0 - hours) * 15.0;
+    for _ in 0..4 {
+        longitude = (longitude + 540.0).rem_euclid(360.0) - 180.0;
+        let coords = Coordinates::new(0.0, longitude).unwrap();
+        let window = chrono::Duration::hours(12);
+        let transit = events_between(target - window, target + window, &coords)
+            .map(Result::unwrap)
+            .filter(|event| event.kind == EventKind::Transit)
+            .min_by_key(|event| (event.time - target).abs())
+            .expect("a transit within half a day");
+        longitude += (transit.time - target).num_milliseconds() as f64 / 240_000.0;
+    }
+    longitude
+}
+
+/// Checks a session: a retained state message right away, then `transit` as
+/// a non-retained event message.
+fn check_session(stream: &mut (impl Read + Write), transit: DateTime<Utc>) {
+    accept_session(stream);
+
+    let state = receive(stream);
+    assert_eq!(state.topic, "t/state");
+    assert!(state.retain, "state messages are retained");
+    assert_eq!(state.qos, 1);
+    let payload = state.payload.as_object().unwrap();
+    let mut keys: Vec<&str> = payload.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        ["delta_s", "next_event", "solar", "ts"],
+        "{payload:?}"
+    );
+    assert!(payload["delta_s"].is_f64());
+    time(&payload["ts"]);
+    time(&payload["solar"]);
+    let next = &payload["next_event"];
+    assert_eq!(next["kind"], "transit");
+    assert!((time(&next["at"]) - transit).abs() < chrono::Duration::seconds(1));
+
+    let event = receive(stream);
+    assert_eq!(event.topic, "t/event");
+    assert!(!event.retain, "event messages are not retained");
+    assert_eq!(event.qos, 1);
+    let payload = event.payload.as_object().unwrap();
+    let mut keys: Vec<&str> = payload.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["at", "kind", "solar"], "{payload:?}");
+    assert_eq!(payload["kind"], "transit");
+    assert_eq!(time(&payload["at"]), time(&next["at"]));
+    time(&payload["solar"]);
+}
+
+/// A transit a few seconds from now, and the longitude where it happens.
+fn upcoming_transit() -> (DateTime<Utc>, f64) {
+    let target = Utc::now() + chrono::Duration::seconds(4);
+    let longitude = longitude_of_transit(target);
+    let coords = Coordinates::new(0.0, longitude).unwrap();
+    let transit = solar_clock_rs::next_event(Utc::now(), &coords)
+        .unwrap()
+        .expect("an event");
+    assert_eq!(transit.kind, EventKind::Transit);
+    (transit.time, longitude)
+}
+
+#[test]
+fn publishes_state_and_events() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (transit, longitude) = upcoming_transit();
+    let _publisher = publisher(&format!("mqtt://127.0.0.1:{port}"), 0.0, longitude, &[]);
+
+    let (mut stream, _) = listener.accept().unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_secs(30)))
+        .unwrap();
+    check_session(&mut stream, transit);
+}
+
+#[cfg(feature = "tls")]
+#[test]
+fn publishes_over_tls() {
+    use std::sync::Arc;
+
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+    use rustls::{ServerConfig, ServerConnection, StreamOwned};
+
+    let data = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data");
+    let certs = CertificateDer::pem_file_iter(format!("{data}/mqtt-server.pem"))
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let key = PrivateKeyDer::from_pem_file(format!("{data}/mqtt-server.key")).unwrap();
+    let config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (transit, longitude) = upcoming_transit();
+    let ca_file = format!("{data}/mqtt-ca.pem");
+    let _publisher = publisher(
+        &format!("mqtts://localhost:{port}"),
+        0.0,
+        longitude,
+        &["--ca-file", &ca_file],
+    );
+
+    let (tcp, _) = listener.accept().unwrap();
+    tcp.set_read_timeout(Some(Duration::from_secs(30))).unwrap();
+    let connection = ServerConnection::new(Arc::new(config)).unwrap();
+    let mut stream = StreamOwned::new(connection, tcp);
+    check_session(&mut stream, transit);
+}
@@ -0,0 +1,130 @@
+# solar-clock-rs - High-precision solar clock calculator
+# Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+#
+# This program is free software: you can redistribute it and/or modify
+# it under the terms of the GNU General Public License as published by
+# the Free Software Foundation, either version 3 of the License, or
+# (at your option) any later version.
+#
+# This program is distributed in the hope that it will be useful,
+# but WITHOUT ANY WARRANTY; without even the implied warranty of
+# MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+# GNU General Public License for more details.
+#
+# You should have received a copy of the GNU General Public License
+# along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+"""The ``solar_clock`` module, built with ``maturin develop``; run ``pytest``."""
+
+from datetime import date, datetime, time, timedelta, timezone
+
+import pytest
+
+from solar_clock import SolarClock
+
+MADRID = (40.4168, -3.7038)
+TARGETS = (time(8), time(14), time(20))
+OFFSET = timezone(timedelta(hours=1))
+NOON_UTC = datetime(2026, 6, 21, 12, tzinfo=timezone.utc)
+MICROSECOND = timedelta(microseconds=1)
+
+
+def zone(key):
+    zoneinfo = pytest.importorskip("zoneinfo")
+    try:
+        return zoneinfo.ZoneInfo(key)
+    except zoneinfo.ZoneInfoNotFoundError:
+        pytest.skip(f"no time zone database entry for {key}")
+
+
+@pytest.fixture
+def clock():
+    return SolarClock(*MADRID, TARGETS, OFFSET)
+
+
+def test_solar_time_is_the_instant_plus_the_delta(clock):
+    solar = clock.solar_time(NOON_UTC)
+    assert solar.utcoffset() == timedelta(hours=1)
+    assert abs(solar - NOON_UTC - timedelta(seconds=clock.delta(NOON_UTC))) <= MICROSECOND
+
+
+def test_sub_second_instants_are_kept(clock):
+    dt = NOON_UTC + timedelta(microseconds=123457)
+    solar = clock.solar_time(dt)
+    # A truncated input would be off by the whole fraction
+    assert abs(solar - dt - timedelta(seconds=clock.delta(dt))) <= MICROSECOND
+    assert solar.microsecond != clock.solar_time(NOON_UTC).microsecond
+
+
+def test_targets_are_met_at_the_events(clock):
+    events = clock.events(date(2026, 6, 21))
+    assert [kind for kind, _ in events] == ["sunrise", "transit", "sunset"]
+    for (_, at), target in zip(events, TARGETS):
+        assert at.utcoffset() == timedelta(hours=1)
+        assert at.date() == date(2026, 6, 21)
+        solar = clock.solar_time(at)
+        expected = datetime.combine(at.date(), target, OFFSET)
+        assert abs(solar - expected) < timedelta(seconds=1)
+
+
+def test_events_skip_what_does_not_occur():
+    # Polar night in Tromsø: the sun transits below the horizon
+    clock = SolarClock(69.6492, 18.9553)
+    assert [kind for kind, _ in clock.events(date(2026, 12, 21))] == ["transit"]
+
+
+@pytest.mark.parametrize(
+    "tz",
+    [
+        timezone.utc,
+        timezone(timedelta(hours=5, minutes=30)),
+        timezone(timedelta(hours=-9, minutes=-30)),
+    ],
+)
+def test_fixed_offsets_are_read_as_their_instant(clock, tz):
+    assert clock.solar_time(NOON_UTC.astimezone(tz)) == clock.solar_time(NOON_UTC)
+
+
+@pytest.mark.parametrize("key", ["Europe/Madrid", "America/New_York", "Asia/Kolkata"])
+def test_zoneinfo_is_read_as_its_instant(clock, key):
+    dt = NOON_UTC.astimezone(zone(key))
+    assert clock.solar_time(dt) == clock.solar_time(NOON_UTC)
+    assert clock.delta(dt) == clock.delta(NOON_UTC)
+
+
+def test_the_repeated_hour_follows_the_fold(clock):
+    # 02:30 happens twice in Madrid when summer time ends
+    first = datetime(2026, 10, 25, 2, 30, tzinfo=zone("Europe/Madrid"))
+    second = first.replace(fold=1)
+    assert second.astimezone(timezone.utc) - first.astimezone(timezone.utc) == timedelta(hours=1)
+    for dt in (first, second):
+        assert clock.solar_time(dt) == clock.solar_time(dt.astimezone(timezone.utc))
+    assert clock.solar_time(second) > clock.solar_time(first)
+
+
+def test_naive_datetimes_are_rejected(clock):
+    with pytest.raises(ValueError, match="timezone-aware"):
+        clock.solar_time(datetime(2026, 6, 21, 12))
+    with pytest.raises(ValueError, match="timezone-aware"):
+        clock.delta(datetime(2026, 6, 21, 12))
+
+
+@pytest.mark.parametrize(
+    "args",
+    [
+        (91.0, 0.0),
+        (0.0, 181.0),
+        (float("nan"), 0.0),
+        (*MADRID, (time(20), time(14), time(8))),
+    ],
+)
+def test_invalid_clocks_raise_value_error(args):
+    with pytest.raises(ValueError):
+        SolarClock(*args)
+
+
+def test_wrong_types_raise_type_error():
+    with pytest.raises(TypeError):
+        SolarClock(*MADRID, ("08:00", "14:00", "20:00"))
+    with pytest.raises(TypeError):
+        SolarClock(*MADRID).solar_time("2026-06-21T12:00:00Z")
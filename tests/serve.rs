@@ -0,0 +1,250 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `serve` on an ephemeral port: the documents of each endpoint, the JSON
+//! bodies of rejected requests and the WebSocket stream.
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+/// Kills the server when the test ends, even on failure.
+struct Server {
+    child: Child,
+    port: u16,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The binary, isolated from the user's configuration file and
+/// `SOLAR_CLOCK_*` variables.
+fn command() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_solar-clock-rs"));
+    for variable in [
+        "SOLAR_CLOCK_LAT",
+        "SOLAR_CLOCK_LON",
+        "SOLAR_CLOCK_TARGETS",
+        "SOLAR_CLOCK_MIDNIGHT",
+        "SOLAR_CLOCK_OFFSET",
+    ] {
+        command.env_remove(variable);
+    }
+    command.env("XDG_CONFIG_HOME", env!("CARGO_TARGET_TMPDIR"));
+    command
+}
+
+/// Starts `serve` with `extra` arguments on a port the system just handed
+/// out.
+fn server(extra: &[&str]) -> Server {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("a free port")
+        .port();
+    let child = command()
+        .args(["serve", "--listen", &format!("127.0.0.1:{port}")])
+        .args(extra)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("the binary runs");
+    Server { child, port }
+}
+
+/// Connects to `server`, retrying while it starts.
+fn connect(server: &Server) -> TcpStream {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        match TcpStream::connect(("127.0.0.1", server.port)) {
+            Ok(stream) => return stream,
+            Err(_) if Instant::now() < deadline => thread::sleep(Duration::from_millis(20)),
+            Err(e) => panic!("the server does not accept connections: {e}"),
+        }
+    }
+}
+
+/// Sends `GET target` and returns the status and the parsed body.
+fn get(server: &Server, target: &str) -> (u16, Value) {
+    let mut stream = connect(server);
+    write!(stream, "GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").expect("headers and a body");
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    assert!(head.contains("Content-Type: application/json"), "{head}");
+    assert!(
+        head.contains(&format!("Content-Length: {}", body.len())),
+        "{head}"
+    );
+    (status, serde_json::from_str(body).expect("a JSON body"))
+}
+
+/// Parsed standard output of the binary run with `args`.
+fn document(args: &[&str]) -> Value {
+    let Output { status, stdout, .. } = command().args(args).output().unwrap();
+    assert!(status.success(), "{args:?}");
+    serde_json::from_slice(&stdout).unwrap()
+}
+
+#[test]
+fn endpoints_answer_with_the_json_documents() {
+    let server = server(&[]);
+
+    let (status, now) = get(&server, "/now");
+    assert_eq!(status, 200, "{now}");
+    assert!(
+        now["delta"].is_f64() && now["solar_time"].is_string(),
+        "{now}"
+    );
+
+    // Percent-encoded or literal, the offset is kept
+    for target in [
+        "/at?t=2026-02-03T13:00:00+01:00",
+        "/at?t=2026-02-03T13%3A00%3A00%2B01%3A00",
+    ] {
+        assert_eq!(
+            get(&server, target),
+            (
+                200,
+                document(&["--at", "2026-02-03T13:00:00+01:00", "--json"])
+            ),
+            "{target}"
+        );
+    }
+    assert_eq!(
+        get(&server, "/events?date=2026-02-03"),
+        (200, document(&["events", "--date", "2026-02-03", "--json"]))
+    );
+    let after = "2025-12-15T00:00:00Z";
+    assert_eq!(
+        get(&server, &format!("/next?solar=08:00&after={after}")),
+        (
+            200,
+            document(&["next", "08:00", "--after", after, "--json"])
+        )
+    );
+}
+
+#[test]
+fn rejected_requests_get_a_json_error() {
+    let server = server(&[]);
+    for (target, status) in [
+        ("/at?t=yesterday", 400),
+        ("/at", 400),
+        ("/events?date=2026-02-30", 400),
+        ("/next?after=2025-12-15T00:00:00Z", 400),
+        ("/next?solar=25:00", 400),
+        ("/at?t=%zz", 400),
+        ("/nowhere", 404),
+    ] {
+        let (code, body) = get(&server, target);
+        assert_eq!(code, status, "{target}: {body}");
+        let keys: Vec<&String> = body.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["error"], "{target}");
+        assert!(!body["error"].as_str().unwrap().is_empty());
+    }
+}
+
+#[test]
+fn idle_connections_do_not_hold_the_server() {
+    let server = server(&[]);
+    // Connected but silent, until the read timeout of the server
+    let _idle = connect(&server);
+
+    let start = Instant::now();
+    let (status, now) = get(&server, "/now");
+    assert_eq!(status, 200, "{now}");
+    assert!(
+        start.elapsed() < Duration::from_secs(2),
+        "{:?}",
+        start.elapsed()
+    );
+}
+
+/// Reads one unmasked text frame and returns its payload.
+fn read_text_frame(stream: &mut impl Read) -> String {
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(header[0], 0x81, "a final text frame");
+    let len = match header[1] {
+        126 => {
+            let mut len = [0u8; 2];
+            stream.read_exact(&mut len).unwrap();
+            usize::from(u16::from_be_bytes(len))
+        }
+        127 => panic!("an NDJSON line over 64 KiB"),
+        len => usize::from(len),
+    };
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).unwrap();
+    String::from_utf8(payload).unwrap()
+}
+
+#[test]
+fn websocket_pushes_monotonic_ticks() {
+    let server = server(&["--interval", "0.2"]);
+    let mut stream = connect(&server);
+    stream
+        .set_read_timeout(Some(Duration::from_secs(10)))
+        .unwrap();
+    write!(
+        stream,
+        "GET /ws HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n"
+    )
+    .unwrap();
+
+    // The handshake ends with an empty line, then the frames follow
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        let mut byte = [0u8];
+        stream.read_exact(&mut byte).unwrap();
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8(head).unwrap();
+    assert!(head.starts_with("HTTP/1.1 101 "), "{head}");
+    assert!(
+        head.contains("Sec-WebSocket-Accept: s3pPLMBiTxaQ9kYGzzhZRbK+xOo=\r\n"),
+        "{head}"
+    );
+
+    let lines: Vec<Value> = (0..3)
+        .map(|_| serde_json::from_str(&read_text_frame(&mut stream)).unwrap())
+        .collect();
+    let ts: Vec<DateTime<Utc>> = lines
+        .iter()
+        .map(|line| line["ts"].as_str().unwrap().parse().unwrap())
+        .collect();
+    for (seq, line) in lines.iter().enumerate() {
+        assert_eq!(line["seq"], seq, "{line}");
+        assert!(line["delta_s"].is_f64(), "{line}");
+        // Ticks fall on whole multiples of the interval
+        assert_eq!(ts[seq].timestamp_subsec_millis() % 200, 0, "{line}");
+    }
+    assert!(ts.windows(2).all(|pair| pair[0] < pair[1]), "{ts:?}");
+}
@@ -0,0 +1,152 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! `--socket` on a temporary path: concurrent clients, malformed requests
+//! and the exit status of `query`.
+
+#![cfg(unix)]
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+/// Kills the server and removes its socket when the test ends, even on
+/// failure.
+struct Server {
+    child: Child,
+    path: PathBuf,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The binary, isolated from the user's configuration file and
+/// `SOLAR_CLOCK_*` variables.
+fn command() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_solar-clock-rs"));
+    for variable in [
+        "SOLAR_CLOCK_LAT",
+        "SOLAR_CLOCK_LON",
+        "SOLAR_CLOCK_TARGETS",
+        "SOLAR_CLOCK_MIDNIGHT",
+        "SOLAR_CLOCK_OFFSET",
+    ] {
+        command.env_remove(variable);
+    }
+    command.env("XDG_CONFIG_HOME", env!("CARGO_TARGET_TMPDIR"));
+    command
+}
+
+/// Starts `--socket` on a path of its own in the temporary directory.
+fn server(name: &str) -> Server {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{name}.sock"));
+    let _ = std::fs::remove_file(&path);
+    let child = command()
+        .arg("--socket")
+        .arg(&path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("the binary runs");
+    Server { child, path }
+}
+
+/// Connects to `server`, retrying while it starts.
+fn connect(server: &Server) -> UnixStream {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        match UnixStream::connect(&server.path) {
+            Ok(stream) => {
+                stream
+                    .set_read_timeout(Some(Duration::from_secs(10)))
+                    .unwrap();
+                return stream;
+            }
+            Err(_) if Instant::now() < deadline => thread::sleep(Duration::from_millis(20)),
+            Err(e) => panic!("the server does not accept connections: {e}"),
+        }
+    }
+}
+
+/// Sends one request line and parses the answer line.
+fn request(stream: &mut UnixStream, line: &str) -> Value {
+    writeln!(stream, "{line}").unwrap();
+    let mut answer = String::new();
+    BufReader::new(&*stream).read_line(&mut answer).unwrap();
+    assert!(answer.ends_with('\n'), "{answer:?}");
+    serde_json::from_str(&answer).expect("a JSON line")
+}
+
+#[test]
+fn concurrent_clients_get_their_own_answers() {
+    let server = server("concurrent");
+    let mut first = connect(&server);
+    let mut second = connect(&server);
+
+    // The second client is answered while the first one is still open
+    let now = request(&mut second, "NOW");
+    assert!(now["solar_time"].is_string(), "{now}");
+    assert!(now.get("error").is_none(), "{now}");
+
+    // A malformed line is answered with an error and keeps the connection
+    let error = request(&mut first, "BOGUS");
+    let members: Vec<_> = error.as_object().unwrap().keys().collect();
+    assert_eq!(members, ["error"], "{error}");
+    let now = request(&mut first, "now");
+    assert!(now["solar_time"].is_string(), "{now}");
+
+    // And the other connection is unaffected
+    let next = request(&mut second, "NEXT 12:00");
+    assert!(next.get("error").is_none(), "{next}");
+}
+
+#[test]
+fn query_fails_on_error_answers() {
+    let server = server("query");
+    drop(connect(&server));
+
+    let query = |request: &str| {
+        command()
+            .arg("query")
+            .arg("--socket")
+            .arg(&server.path)
+            .arg(request)
+            .output()
+            .unwrap()
+    };
+
+    let Output { status, stdout, .. } = query("NOW");
+    assert!(status.success());
+    let now: Value = serde_json::from_slice(&stdout).unwrap();
+    assert!(now["solar_time"].is_string(), "{now}");
+
+    let Output { status, stdout, .. } = query("AT yesterday");
+    assert_eq!(status.code(), Some(2));
+    let error: Value = serde_json::from_slice(&stdout).unwrap();
+    assert!(error["error"].is_string(), "{error}");
+}
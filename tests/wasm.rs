@@ -0,0 +1,121 @@
+/*!
+solar-clock-rs - High-precision solar clock calculator
+Copyright (C) 2026  Juan Luis Leal Contreras (Kuenlun)
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU General Public License as published by
+the Free Software Foundation, either version 3 of the License, or
+(at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU General Public License for more details.
+
+You should have received a copy of the GNU General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! The `wasm` bindings in a headless JavaScript engine. Run with
+//! `cargo test --target wasm32-unknown-unknown --no-default-features
+//! --features wasm --test wasm`, with `wasm-bindgen-test-runner` installed.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::Deserialize;
+use solar_clock_rs::SolarClockBuilder;
+use solar_clock_rs::wasm::WasmSolarClock;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// 2026-06-21T12:00:00Z, as `Date.now()` would give it.
+const NOON_UTC_MS: f64 = 1_782_043_200_000.0;
+const MINUTE_MS: f64 = 60_000.0;
+
+#[derive(Deserialize)]
+struct Event {
+    kind: String,
+    time: f64,
+}
+
+fn madrid() -> WasmSolarClock {
+    WasmSolarClock::new(40.4168, -3.7038, Some("08:00,14:00,20:00".to_owned())).unwrap()
+}
+
+/// Wall time of the solar instant `ms` in the clock's offset, in
+/// milliseconds after midnight, as a page would display it.
+fn wall_time_ms(clock: &WasmSolarClock, ms: f64) -> f64 {
+    let day = 86_400_000.0;
+    (clock.solar_time(ms).unwrap() + f64::from(clock.offset_minutes()) * MINUTE_MS).rem_euclid(day)
+}
+
+#[wasm_bindgen_test]
+fn solar_time_agrees_with_the_rust_api() {
+    let clock = madrid();
+    let hour = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+    let native = SolarClockBuilder::new()
+        .coordinates(40.4168, -3.7038)
+        .targets(hour(8), hour(14), hour(20))
+        .build()
+        .unwrap();
+    let expected = native
+        .solar_time(DateTime::<Utc>::from_timestamp_millis(NOON_UTC_MS as i64).unwrap())
+        .unwrap();
+
+    let solar = clock.solar_time(NOON_UTC_MS).unwrap();
+    let delta = clock.delta(NOON_UTC_MS).unwrap();
+    assert!((delta - expected.delta * 1000.0).abs() < 1e-3);
+    assert!((solar - expected.solar_time.timestamp_micros() as f64 / 1000.0).abs() < 1e-3);
+    assert!((solar - NOON_UTC_MS - delta).abs() < 1e-3);
+    assert_eq!(
+        clock.offset_minutes(),
+        native.offset().local_minus_utc() / 60
+    );
+}
+
+#[wasm_bindgen_test]
+fn events_are_those_of_the_reference_date() {
+    let clock = madrid();
+    let events: Vec<Event> =
+        serde_wasm_bindgen::from_value(clock.events("2026-06-21").unwrap()).unwrap();
+    let kinds: Vec<&str> = events.iter().map(|event| event.kind.as_str()).collect();
+    assert_eq!(kinds, ["sunrise", "transit", "sunset"]);
+
+    // The date is the clock's, not UTC's: its day starts at local midnight
+    let offset_ms = f64::from(clock.offset_minutes()) * MINUTE_MS;
+    let start = NOON_UTC_MS - 12.0 * 60.0 * MINUTE_MS - offset_ms;
+    for (event, target_hours) in events.iter().zip([8.0, 14.0, 20.0]) {
+        assert!((start..start + 86_400_000.0).contains(&event.time));
+        // Each event reads as its target on the solar clock
+        let target = target_hours * 60.0 * MINUTE_MS;
+        assert!((wall_time_ms(&clock, event.time) - target).abs() < 1000.0);
+    }
+}
+
+#[wasm_bindgen_test]
+fn the_reference_date_changes_smoothly_at_midnight() {
+    let clock = madrid();
+    let offset_ms = f64::from(clock.offset_minutes()) * MINUTE_MS;
+    // Civil midnight between 2026-06-21 and 2026-06-22 in the clock's offset
+    let midnight = NOON_UTC_MS + 12.0 * 60.0 * MINUTE_MS - offset_ms;
+    let before = clock.solar_time(midnight - 1000.0).unwrap();
+    let after = clock.solar_time(midnight + 1000.0).unwrap();
+    // Two seconds apart, give or take the clock's rate, and never backwards
+    assert!(after > before);
+    assert!(
+        (after - before - 2000.0).abs() < 1000.0,
+        "{}",
+        after - before
+    );
+}
+
+#[wasm_bindgen_test]
+fn invalid_input_is_an_error() {
+    assert!(WasmSolarClock::new(91.0, 0.0, None).is_err());
+    assert!(WasmSolarClock::new(40.4, -3.7, Some("08:00,14:00".to_owned())).is_err());
+    assert!(WasmSolarClock::new(40.4, -3.7, Some("20:00,14:00,08:00".to_owned())).is_err());
+    let clock = madrid();
+    assert!(clock.solar_time(f64::NAN).is_err());
+    assert!(clock.delta(f64::INFINITY).is_err());
+    assert!(clock.events("21/06/2026").is_err());
+}